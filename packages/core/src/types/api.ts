@@ -1,6 +1,6 @@
 // API request/response types for Tauri commands
 
-import type { Plan, Task, SubTask, ParsedPlanContent, RecurrenceType } from './models';
+import type { Plan, Task, SubTask, ParsedPlanContent, RecurrenceType, MonthDayPolicy } from './models';
 export type { CoreTime, RecurringPlan, ParsedRecurrencePattern, GeneratedTaskPreview } from './models';
 
 // Plan API
@@ -141,6 +141,8 @@ export interface CreateRecurringPlanInput {
   estimatedDuration?: number;
   startDate: string;
   endDate?: string;
+  rrule?: string;
+  monthDayPolicy?: MonthDayPolicy;
 }
 
 export interface UpdateRecurringPlanInput {
@@ -156,5 +158,44 @@ export interface UpdateRecurringPlanInput {
   estimatedDuration?: number;
   startDate?: string;
   endDate?: string;
+  rrule?: string;
+  monthDayPolicy?: MonthDayPolicy;
   isActive?: boolean;
 }
+
+// LLM command errors (`{ kind, message, retryAfter? }`)
+export type LLMErrorKind =
+  | 'api'
+  | 'request'
+  | 'parse'
+  | 'config'
+  | 'cancelled'
+  | 'validation'
+  | 'auth'
+  | 'rateLimited'
+  | 'overloaded'
+  | 'server'
+  | 'contextTooLong'
+  | 'timeout'
+  | 'budgetExceeded'
+  | 'invalidInput'
+  | 'database';
+
+export interface LLMErrorInfo {
+  kind: LLMErrorKind;
+  message: string;
+  retryAfter?: number;
+}
+
+// Streaming events (`llm-delta`, `llm-done`)
+export interface LlmDeltaEvent {
+  requestId: string;
+  delta: string;
+}
+
+export interface LlmDoneEvent {
+  requestId: string;
+  status: 'completed' | 'cancelled' | 'failed';
+  usage: { input_tokens: number; output_tokens: number } | null;
+  error: LLMErrorInfo | null;
+}
@@ -60,15 +60,19 @@ export interface CoreTime {
 }
 
 export interface RecurrencePattern {
-  type: 'daily' | 'weekly' | 'monthly' | 'custom';
+  type: 'daily' | 'weekly' | 'monthly' | 'yearly' | 'custom';
   interval: number;
   daysOfWeek?: number[];
   dayOfMonth?: number;
   endDate?: string;
+  rrule?: string;  // type이 'custom'일 때 RFC 5545 RRULE
 }
 
 // 반복 플랜 (구조화된 반복 태스크 생성용)
-export type RecurrenceType = 'daily' | 'weekly' | 'monthly';
+export type RecurrenceType = 'daily' | 'weekly' | 'monthly' | 'yearly';
+
+// 그 날짜가 없는 달(31일, 2월 29일 등): 'skip' = 건너뜀, 'clamp' = 그 달의 말일
+export type MonthDayPolicy = 'skip' | 'clamp';
 
 export interface RecurringPlan {
   id: string;
@@ -79,12 +83,14 @@ export interface RecurringPlan {
   recurrenceType: RecurrenceType;
   intervalValue: number;
   daysOfWeek?: number[];  // 0=일, 1=월, ..., 6=토
-  dayOfMonth?: number;
+  dayOfMonth?: number;  // 1-31, -1=말일
   scheduledTime?: string;
   endTime?: string;
   estimatedDuration?: number;
   startDate: string;
   endDate?: string;
+  rrule?: string;  // RFC 5545 RRULE (있으면 위 반복 필드보다 우선)
+  monthDayPolicy: MonthDayPolicy;
   isActive: boolean;
   createdAt: string;
   updatedAt: string;
@@ -102,6 +108,7 @@ export interface ParsedRecurrencePattern {
   endDate?: string;
   title?: string;
   location?: string;  // 장소
+  rrule?: string;
 }
 
 export interface GeneratedTaskPreview {
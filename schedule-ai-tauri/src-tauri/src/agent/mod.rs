@@ -0,0 +1,924 @@
+//! 일정을 바꾸는 AI 에이전트의 도구와 변경안
+//!
+//! 에이전트의 도구는 DB를 바로 고치지 않고 변경안(`ProposedChange`)을 쌓습니다.
+//! 조회 도구는 DB에 지금까지의 변경안을 겹쳐 보여주므로 모델은 자기가 만든 변경을 이어서 다룰 수 있고,
+//! 사용자가 확인한 변경만 `apply`로 한 트랜잭션에 반영됩니다.
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::collections::BTreeSet;
+
+use crate::db;
+use crate::llm::agent::{ToolExecutor, ToolSpec};
+use crate::llm::structured::{check_date, check_range, check_time};
+use crate::llm::StructuredOutput;
+use crate::models::{generate_id, now_iso, Task, TaskStatus, UpdateTaskInput};
+use crate::recurring::rrule::RRule;
+use crate::recurring::{
+    generate_tasks_from_recurring_plan, materialize, MonthDayPolicy, ParsedRecurrencePattern, RecurrenceType, RecurringPlan,
+};
+use crate::schedule::{self, to_minutes, TimeSlot, DEFAULT_DURATION};
+
+/// `list_tasks_for_date` 입력
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListTasksInput {
+    /// 날짜 "YYYY-MM-DD"
+    pub date: String,
+}
+
+/// `create_task` 입력
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTaskToolInput {
+    pub title: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    /// 날짜 "YYYY-MM-DD"
+    pub scheduled_date: String,
+    /// 시작 시간 "HH:MM"
+    pub scheduled_time: Option<String>,
+    /// 예상 소요시간(분)
+    pub estimated_duration: Option<i32>,
+    /// 우선순위 0-3
+    pub priority: Option<i32>,
+    /// 연결할 계획 id
+    pub plan_id: Option<String>,
+}
+
+/// `update_task` 입력 (날짜/시간 변경은 `reschedule_task`)
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTaskToolInput {
+    pub task_id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    /// 예상 소요시간(분)
+    pub estimated_duration: Option<i32>,
+    /// 우선순위 0-3
+    pub priority: Option<i32>,
+    /// "pending", "in_progress", "completed", "skipped" 중 하나
+    pub status: Option<String>,
+}
+
+/// `reschedule_task` 입력
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RescheduleTaskInput {
+    pub task_id: String,
+    /// 새 날짜 "YYYY-MM-DD"
+    pub scheduled_date: String,
+    /// 새 시작 시간 "HH:MM" (없으면 시간 미정)
+    pub scheduled_time: Option<String>,
+}
+
+/// `split_task` 입력
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitTaskInput {
+    pub task_id: String,
+    /// 순서대로 진행할 서브태스크 (3-5개)
+    pub subtasks: Vec<SubTaskDraft>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubTaskDraft {
+    pub title: String,
+    /// 예상 소요시간(분)
+    pub estimated_minutes: i32,
+}
+
+/// 제안된 변경 하나
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Change {
+    CreateTask {
+        task: Task,
+    },
+    UpdateTask {
+        task_id: String,
+        update: UpdateTaskInput,
+    },
+    RescheduleTask {
+        task_id: String,
+        scheduled_date: String,
+        scheduled_time: Option<String>,
+    },
+    SplitTask {
+        task_id: String,
+        subtasks: Vec<SubTaskDraft>,
+    },
+    CreateRecurringPlan {
+        plan: RecurringPlan,
+    },
+}
+
+impl Change {
+    /// 이 변경이 대상으로 하는 태스크 id
+    fn target_task(&self) -> Option<&str> {
+        match self {
+            Change::UpdateTask { task_id, .. }
+            | Change::RescheduleTask { task_id, .. }
+            | Change::SplitTask { task_id, .. } => Some(task_id),
+            _ => None,
+        }
+    }
+}
+
+/// 사용자에게 보여줄 설명이 붙은 변경
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposedChange {
+    pub summary: String,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+/// 제공하는 도구 목록
+pub fn tools() -> Vec<ToolSpec> {
+    vec![
+        ToolSpec::new::<ListTasksInput>(
+            "list_tasks_for_date",
+            "List the tasks, recurring events and free time slots for a date, including changes proposed so far. \
+             Use it to find task ids and to check for conflicts.",
+        ),
+        ToolSpec::new::<CreateTaskToolInput>("create_task", "Propose a new task."),
+        ToolSpec::new::<UpdateTaskToolInput>(
+            "update_task",
+            "Propose changes to a task's title, description, location, duration, priority or status.",
+        ),
+        ToolSpec::new::<RescheduleTaskInput>("reschedule_task", "Propose moving a task to another date and/or time."),
+        ToolSpec::new::<SplitTaskInput>("split_task", "Propose splitting a task into small subtasks."),
+        ToolSpec::new::<ParsedRecurrencePattern>(
+            "create_recurring_plan",
+            "Propose a recurring event (class, workout, commute...). title is required; startDate defaults to today.",
+        ),
+    ]
+}
+
+fn parse_status(status: &str) -> Result<TaskStatus, String> {
+    match status {
+        "pending" => Ok(TaskStatus::Pending),
+        "in_progress" => Ok(TaskStatus::InProgress),
+        "completed" => Ok(TaskStatus::Completed),
+        "skipped" => Ok(TaskStatus::Skipped),
+        other => Err(format!("unknown status \"{}\"", other)),
+    }
+}
+
+fn parse_input<T: serde::de::DeserializeOwned>(input: serde_json::Value) -> Result<T, String> {
+    serde_json::from_value(input).map_err(|e| format!("invalid input: {}", e))
+}
+
+fn time_label(time: Option<&str>) -> String {
+    time.map(|t| format!(" {}", t)).unwrap_or_default()
+}
+
+/// 변경안을 쌓는 도구 실행기
+pub struct ScheduleTools {
+    pool: SqlitePool,
+    today: String,
+    /// 반복 일정 태스크를 미리 만드는 기간 (일)
+    horizon_days: i64,
+    changes: Vec<ProposedChange>,
+}
+
+impl ScheduleTools {
+    pub fn new(pool: SqlitePool, today: &str, horizon_days: i64) -> Self {
+        Self {
+            pool,
+            today: today.to_string(),
+            horizon_days,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn into_changes(self) -> Vec<ProposedChange> {
+        self.changes
+    }
+
+    fn propose(&mut self, summary: String, change: Change) {
+        self.changes.push(ProposedChange { summary, change });
+    }
+
+    fn db_error(e: sqlx::Error) -> String {
+        format!("database error: {}", e)
+    }
+
+    /// 변경안을 반영한 태스크
+    async fn task(&self, id: &str) -> Result<Option<Task>, String> {
+        let created = self.changes.iter().find_map(|c| match &c.change {
+            Change::CreateTask { task } if task.id == id => Some(task.clone()),
+            _ => None,
+        });
+        let task = match created {
+            Some(task) => Some(task),
+            None => db::task_by_id(&self.pool, id).await.map_err(Self::db_error)?,
+        };
+        Ok(task.map(|task| self.overlay(task)))
+    }
+
+    fn overlay(&self, mut task: Task) -> Task {
+        for change in self.changes.iter().map(|c| &c.change) {
+            match change {
+                Change::UpdateTask { task_id, update } if *task_id == task.id => {
+                    if let Some(title) = &update.title {
+                        task.title = title.clone();
+                    }
+                    if update.description.is_some() {
+                        task.description = update.description.clone();
+                    }
+                    if update.location.is_some() {
+                        task.location = update.location.clone();
+                    }
+                    if update.scheduled_time.is_some() {
+                        task.scheduled_time = update.scheduled_time.clone();
+                    }
+                    if update.estimated_duration.is_some() {
+                        task.estimated_duration = update.estimated_duration;
+                    }
+                    if let Some(priority) = update.priority {
+                        task.priority = priority;
+                    }
+                    if let Some(status) = &update.status {
+                        task.status = status.clone();
+                    }
+                }
+                Change::RescheduleTask {
+                    task_id,
+                    scheduled_date,
+                    scheduled_time,
+                } if *task_id == task.id => {
+                    task.scheduled_date = scheduled_date.clone();
+                    task.scheduled_time = scheduled_time.clone();
+                }
+                _ => {}
+            }
+        }
+        task
+    }
+
+    /// 변경안을 반영한 날짜의 태스크
+    async fn tasks_on(&self, date: &str) -> Result<Vec<Task>, String> {
+        let mut ids: Vec<String> = db::tasks_between(&self.pool, date, date)
+            .await
+            .map_err(Self::db_error)?
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        for change in &self.changes {
+            let id = match &change.change {
+                Change::CreateTask { task } => Some(&task.id),
+                Change::RescheduleTask { task_id, .. } => Some(task_id),
+                _ => None,
+            };
+            if let Some(id) = id.filter(|id| !ids.contains(id)) {
+                ids.push(id.clone());
+            }
+        }
+
+        let mut tasks = Vec::new();
+        for id in ids {
+            if let Some(task) = self.task(&id).await?.filter(|t| t.scheduled_date == date) {
+                tasks.push(task);
+            }
+        }
+        tasks.sort_by_key(|t| (t.scheduled_time.is_none(), t.scheduled_time.clone()));
+        Ok(tasks)
+    }
+
+    /// 같은 날 시간이 겹치는 다른 태스크 제목
+    async fn conflicts(
+        &self,
+        date: &str,
+        time: Option<&str>,
+        duration: Option<i32>,
+        exclude: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        let Some(start) = time.and_then(to_minutes) else {
+            return Ok(Vec::new());
+        };
+        let end = start + duration.unwrap_or(DEFAULT_DURATION);
+        Ok(self
+            .tasks_on(date)
+            .await?
+            .into_iter()
+            .filter(|t| Some(t.id.as_str()) != exclude && !matches!(t.status, TaskStatus::Skipped))
+            .filter(|t| {
+                t.scheduled_time.as_deref().and_then(to_minutes).is_some_and(|s| {
+                    let e = s + t.estimated_duration.unwrap_or(DEFAULT_DURATION);
+                    s < end && start < e
+                })
+            })
+            .map(|t| t.title)
+            .collect())
+    }
+
+    async fn existing_task(&self, id: &str) -> Result<Task, String> {
+        self.task(id)
+            .await?
+            .ok_or_else(|| format!("task {} not found; use list_tasks_for_date to find task ids", id))
+    }
+
+    async fn list_tasks_for_date(&self, input: ListTasksInput) -> Result<String, String> {
+        check_date("date", Some(&input.date))?;
+        let tasks = self.tasks_on(&input.date).await?;
+
+        let generated = db::generated_recurring_ids(&self.pool, &input.date)
+            .await
+            .map_err(Self::db_error)?;
+        let mut recurring: Vec<RecurringPlan> = db::active_recurring_plans(&self.pool)
+            .await
+            .map_err(Self::db_error)?
+            .into_iter()
+            .filter(|p| !generated.contains(&p.id))
+            .collect();
+        recurring.extend(self.changes.iter().filter_map(|c| match &c.change {
+            Change::CreateRecurringPlan { plan } => Some(plan.clone()),
+            _ => None,
+        }));
+        let core_times = db::active_core_times(&self.pool).await.map_err(Self::db_error)?;
+        let window = TimeSlot {
+            start: schedule::DAY_START.to_string(),
+            end: schedule::DAY_END.to_string(),
+        };
+        let day = schedule::build_day_schedule(&input.date, &tasks, &recurring, &core_times, &window);
+
+        let tasks: Vec<serde_json::Value> = tasks
+            .iter()
+            .map(|t| {
+                json!({
+                    "id": t.id,
+                    "title": t.title,
+                    "scheduledTime": t.scheduled_time,
+                    "estimatedDuration": t.estimated_duration,
+                    "priority": t.priority,
+                    "status": t.status,
+                    "location": t.location,
+                })
+            })
+            .collect();
+        let recurring: Vec<_> = day
+            .busy
+            .iter()
+            .filter(|b| b.kind == schedule::BusyKind::Recurring)
+            .collect();
+
+        Ok(json!({
+            "date": input.date,
+            "tasks": tasks,
+            "recurring": recurring,
+            "freeSlots": day.free_slots,
+        })
+        .to_string())
+    }
+
+    async fn create_task(&mut self, input: CreateTaskToolInput) -> Result<String, String> {
+        if input.title.trim().is_empty() {
+            return Err("title must not be empty".to_string());
+        }
+        check_date("scheduledDate", Some(&input.scheduled_date))?;
+        check_time("scheduledTime", input.scheduled_time.as_deref())?;
+        check_range("estimatedDuration", input.estimated_duration, 1, 24 * 60)?;
+        check_range("priority", input.priority, 0, 3)?;
+
+        let conflicts = self
+            .conflicts(
+                &input.scheduled_date,
+                input.scheduled_time.as_deref(),
+                input.estimated_duration,
+                None,
+            )
+            .await?;
+
+        let now = now_iso();
+        let task = Task {
+            id: generate_id(),
+            plan_id: input.plan_id,
+            title: input.title.trim().to_string(),
+            description: input.description,
+            location: input.location,
+            scheduled_date: input.scheduled_date,
+            scheduled_time: input.scheduled_time,
+            estimated_duration: input.estimated_duration,
+            actual_duration: None,
+            priority: input.priority.unwrap_or(0),
+            status: TaskStatus::Pending,
+            order_index: 0,
+            subtasks: None,
+            created_at: now.clone(),
+            updated_at: now,
+            completed_at: None,
+        };
+        let id = task.id.clone();
+        let summary = format!(
+            "Create \"{}\" on {}{}",
+            task.title,
+            task.scheduled_date,
+            time_label(task.scheduled_time.as_deref())
+        );
+        self.propose(summary, Change::CreateTask { task });
+
+        Ok(json!({ "taskId": id, "conflicts": conflicts }).to_string())
+    }
+
+    async fn update_task(&mut self, input: UpdateTaskToolInput) -> Result<String, String> {
+        let task = self.existing_task(&input.task_id).await?;
+        if input.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            return Err("title must not be empty".to_string());
+        }
+        check_range("estimatedDuration", input.estimated_duration, 1, 24 * 60)?;
+        check_range("priority", input.priority, 0, 3)?;
+        let status = input.status.as_deref().map(parse_status).transpose()?;
+
+        let update = UpdateTaskInput {
+            title: input.title.map(|t| t.trim().to_string()),
+            description: input.description,
+            location: input.location,
+            scheduled_date: None,
+            scheduled_time: None,
+            estimated_duration: input.estimated_duration,
+            actual_duration: None,
+            priority: input.priority,
+            status,
+            order_index: None,
+        };
+        self.propose(
+            format!("Update \"{}\"", task.title),
+            Change::UpdateTask {
+                task_id: task.id.clone(),
+                update,
+            },
+        );
+        Ok(json!({ "taskId": task.id }).to_string())
+    }
+
+    async fn reschedule_task(&mut self, input: RescheduleTaskInput) -> Result<String, String> {
+        let task = self.existing_task(&input.task_id).await?;
+        check_date("scheduledDate", Some(&input.scheduled_date))?;
+        check_time("scheduledTime", input.scheduled_time.as_deref())?;
+
+        let conflicts = self
+            .conflicts(
+                &input.scheduled_date,
+                input.scheduled_time.as_deref(),
+                task.estimated_duration,
+                Some(&task.id),
+            )
+            .await?;
+
+        let summary = format!(
+            "Move \"{}\" from {}{} to {}{}",
+            task.title,
+            task.scheduled_date,
+            time_label(task.scheduled_time.as_deref()),
+            input.scheduled_date,
+            time_label(input.scheduled_time.as_deref())
+        );
+        self.propose(
+            summary,
+            Change::RescheduleTask {
+                task_id: task.id.clone(),
+                scheduled_date: input.scheduled_date,
+                scheduled_time: input.scheduled_time,
+            },
+        );
+        Ok(json!({ "taskId": task.id, "conflicts": conflicts }).to_string())
+    }
+
+    async fn split_task(&mut self, input: SplitTaskInput) -> Result<String, String> {
+        let task = self.existing_task(&input.task_id).await?;
+        if input.subtasks.is_empty() {
+            return Err("subtasks must not be empty".to_string());
+        }
+        for subtask in &input.subtasks {
+            if subtask.title.trim().is_empty() {
+                return Err("subtasks[].title must not be empty".to_string());
+            }
+            check_range("subtasks[].estimatedMinutes", Some(subtask.estimated_minutes), 1, 240)?;
+        }
+
+        let summary = format!("Split \"{}\" into {} subtasks", task.title, input.subtasks.len());
+        self.propose(
+            summary,
+            Change::SplitTask {
+                task_id: task.id.clone(),
+                subtasks: input.subtasks,
+            },
+        );
+        Ok(json!({ "taskId": task.id }).to_string())
+    }
+
+    fn create_recurring_plan(&mut self, input: ParsedRecurrencePattern) -> Result<String, String> {
+        input.validate()?;
+        let title = input
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or("title must not be empty")?
+            .to_string();
+
+        // validate에서 확인한 RRULE은 정규화해서 저장
+        let rule = input
+            .rrule
+            .as_deref()
+            .filter(|r| !r.trim().is_empty())
+            .map(str::parse::<RRule>)
+            .transpose()?;
+
+        let now = now_iso();
+        let plan = RecurringPlan {
+            id: generate_id(),
+            plan_id: None,
+            title,
+            description: None,
+            location: input.location,
+            recurrence_type: rule.as_ref().map_or(input.recurrence_type, RecurrenceType::from_rule),
+            interval_value: input.interval_value,
+            days_of_week: input.days_of_week,
+            day_of_month: input.day_of_month,
+            scheduled_time: input.scheduled_time,
+            end_time: input.end_time,
+            estimated_duration: input.estimated_duration,
+            start_date: input.start_date.unwrap_or_else(|| self.today.clone()),
+            end_date: input.end_date,
+            rrule: rule.map(|rule| rule.to_string()),
+            month_day_policy: MonthDayPolicy::Clamp,
+            exceptions: Vec::new(),
+            is_active: true,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        let (from, to) = first_window(&plan, self.horizon_days);
+        let occurrences = generate_tasks_from_recurring_plan(&plan, from, to).len();
+        let id = plan.id.clone();
+        let summary = format!(
+            "Create recurring \"{}\" from {} ({} occurrences)",
+            plan.title, plan.start_date, occurrences
+        );
+        self.propose(summary, Change::CreateRecurringPlan { plan });
+        Ok(json!({ "recurringPlanId": id, "occurrences": occurrences }).to_string())
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for ScheduleTools {
+    async fn call(&mut self, name: &str, input: serde_json::Value) -> Result<String, String> {
+        match name {
+            "list_tasks_for_date" => self.list_tasks_for_date(parse_input(input)?).await,
+            "create_task" => self.create_task(parse_input(input)?).await,
+            "update_task" => self.update_task(parse_input(input)?).await,
+            "reschedule_task" => self.reschedule_task(parse_input(input)?).await,
+            "split_task" => self.split_task(parse_input(input)?).await,
+            "create_recurring_plan" => self.create_recurring_plan(parse_input(input)?),
+            other => Err(format!("unknown tool \"{}\"", other)),
+        }
+    }
+}
+
+/// 사용자가 고른 변경만 남김 (제외된 태스크 생성에 의존하는 변경도 제외)
+pub fn select(changes: Vec<ProposedChange>, accepted: Option<&[usize]>) -> Vec<ProposedChange> {
+    let Some(accepted) = accepted else {
+        return changes;
+    };
+
+    let mut rejected_tasks = BTreeSet::new();
+    changes
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, proposed)| {
+            let depends_on_rejected = proposed
+                .change
+                .target_task()
+                .is_some_and(|id| rejected_tasks.contains(id));
+            if accepted.contains(&index) && !depends_on_rejected {
+                return Some(proposed);
+            }
+            if let Change::CreateTask { task } = &proposed.change {
+                rejected_tasks.insert(task.id.clone());
+            }
+            None
+        })
+        .collect()
+}
+
+/// 반영 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedChanges {
+    pub applied: usize,
+    /// 태스크가 바뀐 날짜 (화면 갱신용)
+    pub dates: Vec<String>,
+}
+
+/// 변경안을 한 트랜잭션으로 DB에 반영
+///
+/// 새 반복 일정은 시작일부터 `horizon_days`까지의 태스크를 바로 만듭니다.
+/// 대상 태스크가 그 사이 삭제되었으면 `RowNotFound`로 아무것도 반영하지 않습니다.
+pub async fn apply(
+    pool: &SqlitePool,
+    changes: &[ProposedChange],
+    horizon_days: i64,
+) -> Result<AppliedChanges, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut dates = BTreeSet::new();
+    let now = now_iso();
+
+    for change in changes.iter().map(|c| &c.change) {
+        match change {
+            Change::CreateTask { task } => {
+                db::insert_task(&mut tx, task).await?;
+                dates.insert(task.scheduled_date.clone());
+            }
+            Change::UpdateTask { task_id, update } => {
+                let status = update.status.as_ref().map(ToString::to_string);
+                let completed_at = matches!(update.status, Some(TaskStatus::Completed)).then(|| now.clone());
+                let result = sqlx::query(
+                    "UPDATE tasks SET title = COALESCE(?, title), description = COALESCE(?, description), \
+                     location = COALESCE(?, location), scheduled_time = COALESCE(?, scheduled_time), \
+                     estimated_duration = COALESCE(?, estimated_duration), priority = COALESCE(?, priority), \
+                     status = COALESCE(?, status), order_index = COALESCE(?, order_index), \
+                     completed_at = COALESCE(?, completed_at), updated_at = ? WHERE id = ?",
+                )
+                .bind(&update.title)
+                .bind(&update.description)
+                .bind(&update.location)
+                .bind(&update.scheduled_time)
+                .bind(update.estimated_duration)
+                .bind(update.priority)
+                .bind(status)
+                .bind(update.order_index)
+                .bind(completed_at)
+                .bind(&now)
+                .bind(task_id)
+                .execute(&mut *tx)
+                .await?;
+                ensure_task_found(result.rows_affected())?;
+                dates.extend(task_date(&mut tx, task_id).await?);
+            }
+            Change::RescheduleTask {
+                task_id,
+                scheduled_date,
+                scheduled_time,
+            } => {
+                dates.extend(task_date(&mut tx, task_id).await?);
+                // 건너뛴 태스크를 옮기면 다시 할 일로
+                let result = sqlx::query(
+                    "UPDATE tasks SET scheduled_date = ?, scheduled_time = ?, \
+                     status = CASE WHEN status = 'skipped' THEN 'pending' ELSE status END, updated_at = ? WHERE id = ?",
+                )
+                .bind(scheduled_date)
+                .bind(scheduled_time)
+                .bind(&now)
+                .bind(task_id)
+                .execute(&mut *tx)
+                .await?;
+                ensure_task_found(result.rows_affected())?;
+                dates.insert(scheduled_date.clone());
+            }
+            Change::SplitTask { task_id, subtasks } => {
+                task_date(&mut tx, task_id).await?.ok_or(sqlx::Error::RowNotFound)?;
+                let next: i32 = sqlx::query_scalar(
+                    "SELECT COALESCE(MAX(order_index) + 1, 0) FROM subtasks WHERE task_id = ?",
+                )
+                .bind(task_id)
+                .fetch_one(&mut *tx)
+                .await?;
+                for (offset, subtask) in subtasks.iter().enumerate() {
+                    sqlx::query(
+                        "INSERT INTO subtasks (id, task_id, title, status, order_index, created_at) \
+                         VALUES (?, ?, ?, 'pending', ?, ?)",
+                    )
+                    .bind(generate_id())
+                    .bind(task_id)
+                    .bind(subtask.title.trim())
+                    .bind(next + offset as i32)
+                    .bind(&now)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+            Change::CreateRecurringPlan { plan } => {
+                db::insert_recurring_plan(&mut tx, plan).await?;
+                // 처음에는 시작일부터 설정한 기간만, 이후는 롤링 윈도우가 채움
+                let (from, to) = first_window(plan, horizon_days);
+                dates.extend(materialize::materialize_plan(&mut tx, plan, from, to).await?);
+            }
+        }
+    }
+
+    for date in &dates {
+        db::refresh_daily_progress(&mut tx, date).await?;
+    }
+    tx.commit().await?;
+
+    Ok(AppliedChanges {
+        applied: changes.len(),
+        dates: dates.into_iter().collect(),
+    })
+}
+
+/// 새 반복 일정에서 바로 만드는 기간 (시작일부터 `horizon_days`)
+fn first_window(plan: &RecurringPlan, horizon_days: i64) -> (NaiveDate, NaiveDate) {
+    let start = NaiveDate::parse_from_str(&plan.start_date, "%Y-%m-%d").unwrap_or_default();
+    (start, start + Duration::days(horizon_days))
+}
+
+/// 변경안을 만든 뒤 태스크가 삭제되었으면 `RowNotFound`로 전체 반영을 취소
+fn ensure_task_found(rows_affected: u64) -> Result<(), sqlx::Error> {
+    if rows_affected == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+    Ok(())
+}
+
+async fn task_date(conn: &mut sqlx::SqliteConnection, task_id: &str) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT scheduled_date FROM tasks WHERE id = ?")
+        .bind(task_id)
+        .fetch_optional(conn)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn seeded() -> SqlitePool {
+        let pool = db::test_pool().await;
+        sqlx::raw_sql(
+            "INSERT INTO tasks (id, title, scheduled_date, scheduled_time, estimated_duration, status, created_at, updated_at) VALUES
+                ('gym', 'Gym', '2026-03-02', '18:00', 60, 'pending', '', ''),
+                ('report', 'Report', '2026-03-03', '18:30', 60, 'pending', '', '');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_tools_overlay_proposed_changes() {
+        let pool = seeded().await;
+        let mut tools = ScheduleTools::new(pool.clone(), "2026-03-02", materialize::DEFAULT_HORIZON_DAYS);
+
+        let moved: serde_json::Value = serde_json::from_str(
+            &tools
+                .call(
+                    "reschedule_task",
+                    json!({"taskId": "gym", "scheduledDate": "2026-03-03", "scheduledTime": "18:00"}),
+                )
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(moved["conflicts"], json!(["Report"]));
+
+        let created: serde_json::Value = serde_json::from_str(
+            &tools
+                .call("create_task", json!({"title": "Rest", "scheduledDate": "2026-03-02", "scheduledTime": "19:00"}))
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        let rest_id = created["taskId"].as_str().unwrap().to_string();
+        tools
+            .call("update_task", json!({"taskId": rest_id, "priority": 3}))
+            .await
+            .unwrap();
+
+        let listed: serde_json::Value = serde_json::from_str(
+            &tools.call("list_tasks_for_date", json!({"date": "2026-03-03"})).await.unwrap(),
+        )
+        .unwrap();
+        let titles: Vec<&str> = listed["tasks"].as_array().unwrap().iter().map(|t| t["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, vec!["Gym", "Report"]);
+
+        assert!(tools.call("update_task", json!({"taskId": "nope"})).await.is_err());
+        assert!(tools
+            .call("create_task", json!({"title": "x", "scheduledDate": "tomorrow"}))
+            .await
+            .unwrap_err()
+            .contains("YYYY-MM-DD"));
+        assert!(tools.call("delete_everything", json!({})).await.is_err());
+
+        // 아직 DB는 그대로
+        assert_eq!(db::task_by_id(&pool, "gym").await.unwrap().unwrap().scheduled_date, "2026-03-02");
+        assert_eq!(tools.into_changes().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_apply_selected_changes() {
+        let pool = seeded().await;
+        let mut tools = ScheduleTools::new(pool.clone(), "2026-03-02", materialize::DEFAULT_HORIZON_DAYS);
+        tools
+            .call("reschedule_task", json!({"taskId": "gym", "scheduledDate": "2026-03-04"}))
+            .await
+            .unwrap();
+        let created: serde_json::Value = serde_json::from_str(
+            &tools
+                .call("create_task", json!({"title": "Stretch", "scheduledDate": "2026-03-02"}))
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        let stretch = created["taskId"].as_str().unwrap().to_string();
+        tools
+            .call(
+                "split_task",
+                json!({"taskId": stretch, "subtasks": [{"title": "Neck", "estimatedMinutes": 5}]}),
+            )
+            .await
+            .unwrap();
+        tools
+            .call(
+                "split_task",
+                json!({"taskId": "report", "subtasks": [{"title": "Outline", "estimatedMinutes": 10}]}),
+            )
+            .await
+            .unwrap();
+        tools
+            .call(
+                "create_recurring_plan",
+                json!({"title": "Yoga", "recurrenceType": "weekly", "intervalValue": 1, "daysOfWeek": [1],
+                       "scheduledTime": "07:00", "startDate": "2026-03-02", "endDate": "2026-03-16"}),
+            )
+            .await
+            .unwrap();
+        let changes = tools.into_changes();
+        assert_eq!(changes.len(), 5);
+
+        // Stretch 생성을 빼면 Stretch 분해도 빠짐
+        let selected = select(changes, Some(&[0, 2, 3, 4]));
+        assert_eq!(selected.len(), 3);
+
+        let applied = apply(&pool, &selected, materialize::DEFAULT_HORIZON_DAYS).await.unwrap();
+        assert_eq!(applied.applied, 3);
+        assert_eq!(applied.dates, vec!["2026-03-02", "2026-03-04", "2026-03-09", "2026-03-16"]);
+
+        let gym = db::task_by_id(&pool, "gym").await.unwrap().unwrap();
+        assert_eq!((gym.scheduled_date.as_str(), gym.scheduled_time), ("2026-03-04", None));
+        let subtasks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM subtasks WHERE task_id = 'report'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(subtasks, 1);
+        let yoga = db::tasks_between(&pool, "2026-03-01", "2026-03-31").await.unwrap();
+        assert_eq!(yoga.iter().filter(|t| t.title == "Yoga").count(), 3);
+        let generated: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM generated_tasks")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(generated, 3);
+        let total: i64 = sqlx::query_scalar("SELECT total_tasks FROM daily_progress WHERE date = '2026-03-04'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_rolls_back_when_task_was_deleted() {
+        let pool = seeded().await;
+        let mut tools = ScheduleTools::new(pool.clone(), "2026-03-02", materialize::DEFAULT_HORIZON_DAYS);
+        tools
+            .call("reschedule_task", json!({"taskId": "gym", "scheduledDate": "2026-03-04"}))
+            .await
+            .unwrap();
+        tools
+            .call(
+                "split_task",
+                json!({"taskId": "report", "subtasks": [{"title": "Outline", "estimatedMinutes": 10}]}),
+            )
+            .await
+            .unwrap();
+        let changes = tools.into_changes();
+
+        // 변경안을 만든 뒤 태스크가 삭제됨
+        sqlx::query("DELETE FROM tasks WHERE id = 'report'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(matches!(
+            apply(&pool, &changes, materialize::DEFAULT_HORIZON_DAYS).await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+        let gym = db::task_by_id(&pool, "gym").await.unwrap().unwrap();
+        assert_eq!(gym.scheduled_date, "2026-03-02");
+
+        sqlx::query("DELETE FROM tasks WHERE id = 'gym'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(matches!(
+            apply(&pool, &changes[..1], materialize::DEFAULT_HORIZON_DAYS).await,
+            Err(sqlx::Error::RowNotFound)
+        ));
+    }
+}
@@ -0,0 +1,286 @@
+//! 여러 턴의 AI 일정 상담 대화
+//!
+//! 대화 세션과 메시지를 SQLite(`chat_sessions`, `chat_messages`)에 저장하고,
+//! 요청마다 system prompt(계획, 오늘 일정)와 토큰 예산에 맞춰 자른 이전 대화를 함께 보냅니다.
+
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+
+use crate::llm::LLMMessage;
+use crate::models::{generate_id, now_iso};
+
+/// 요청에 포함할 이전 대화의 토큰 예산 (system prompt 제외)
+pub const HISTORY_TOKEN_BUDGET: usize = 6000;
+/// 제목이 없는 세션의 기본 제목 길이(글자)
+const TITLE_MAX_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatSession {
+    pub id: String,
+    pub title: String,
+    pub message_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessage {
+    pub id: String,
+    pub session_id: String,
+    /// "user" 또는 "assistant"
+    pub role: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+impl ChatMessage {
+    pub fn new(session_id: &str, role: &str, content: &str) -> Self {
+        Self {
+            id: generate_id(),
+            session_id: session_id.to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            created_at: now_iso(),
+        }
+    }
+
+    pub fn to_llm(&self) -> LLMMessage {
+        LLMMessage {
+            role: self.role.clone(),
+            content: self.content.clone(),
+        }
+    }
+}
+
+/// 대략적인 토큰 수 (ASCII 4글자당 1토큰, 한글 등은 글자당 1토큰)
+pub fn estimate_tokens(text: &str) -> usize {
+    let ascii = text.chars().filter(char::is_ascii).count();
+    let other = text.chars().count() - ascii;
+    ascii.div_ceil(4) + other + 4
+}
+
+/// 최근 메시지부터 예산 안에 들어가는 만큼만 남김
+///
+/// 마지막 메시지는 항상 포함하고, 잘린 대화가 assistant 메시지로 시작하지 않게 합니다.
+pub fn trim_history(messages: &[ChatMessage], budget: usize) -> &[ChatMessage] {
+    let mut used = 0;
+    let mut start = messages.len();
+    for (index, message) in messages.iter().enumerate().rev() {
+        used += estimate_tokens(&message.content);
+        if used > budget && start < messages.len() {
+            break;
+        }
+        start = index;
+    }
+
+    while start + 1 < messages.len() && messages[start].role != "user" {
+        start += 1;
+    }
+    &messages[start..]
+}
+
+/// 첫 메시지로 만든 세션 제목
+pub fn title_from_message(content: &str) -> String {
+    let line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+    if line.chars().count() > TITLE_MAX_CHARS {
+        let title: String = line.chars().take(TITLE_MAX_CHARS).collect();
+        format!("{}…", title.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+fn session_from_row(row: &SqliteRow) -> ChatSession {
+    ChatSession {
+        id: row.get("id"),
+        title: row.get("title"),
+        message_count: row.get("message_count"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+const SESSION_QUERY: &str = "SELECT s.id, s.title, s.created_at, s.updated_at, \
+     (SELECT COUNT(*) FROM chat_messages m WHERE m.session_id = s.id) AS message_count \
+     FROM chat_sessions s";
+
+pub async fn create_session(pool: &SqlitePool, title: &str) -> Result<ChatSession, sqlx::Error> {
+    let now = now_iso();
+    let session = ChatSession {
+        id: generate_id(),
+        title: title.to_string(),
+        message_count: 0,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    sqlx::query("INSERT INTO chat_sessions (id, title, created_at, updated_at) VALUES (?, ?, ?, ?)")
+        .bind(&session.id)
+        .bind(&session.title)
+        .bind(&session.created_at)
+        .bind(&session.updated_at)
+        .execute(pool)
+        .await?;
+    Ok(session)
+}
+
+pub async fn get_session(pool: &SqlitePool, id: &str) -> Result<Option<ChatSession>, sqlx::Error> {
+    let row = sqlx::query(&format!("{} WHERE s.id = ?", SESSION_QUERY))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.as_ref().map(session_from_row))
+}
+
+/// 최근 대화 순 세션 목록
+pub async fn list_sessions(pool: &SqlitePool) -> Result<Vec<ChatSession>, sqlx::Error> {
+    let rows = sqlx::query(&format!("{} ORDER BY s.updated_at DESC", SESSION_QUERY))
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.iter().map(session_from_row).collect())
+}
+
+/// 세션의 메시지 (오래된 순)
+pub async fn messages(pool: &SqlitePool, session_id: &str) -> Result<Vec<ChatMessage>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, session_id, role, content, created_at FROM chat_messages \
+         WHERE session_id = ? ORDER BY created_at, rowid",
+    )
+    .bind(session_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ChatMessage {
+            id: row.get("id"),
+            session_id: row.get("session_id"),
+            role: row.get("role"),
+            content: row.get("content"),
+            created_at: row.get("created_at"),
+        })
+        .collect())
+}
+
+/// 한 턴(사용자 메시지와 AI 답변)을 저장하고 세션 갱신
+///
+/// 세션 제목이 비어 있으면 사용자 메시지로 채웁니다.
+pub async fn append_turn(
+    pool: &SqlitePool,
+    session_id: &str,
+    user: &str,
+    assistant: &str,
+) -> Result<ChatMessage, sqlx::Error> {
+    let user = ChatMessage::new(session_id, "user", user);
+    let assistant = ChatMessage::new(session_id, "assistant", assistant);
+
+    let mut tx = pool.begin().await?;
+    for message in [&user, &assistant] {
+        sqlx::query(
+            "INSERT INTO chat_messages (id, session_id, role, content, created_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&message.id)
+        .bind(&message.session_id)
+        .bind(&message.role)
+        .bind(&message.content)
+        .bind(&message.created_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query(
+        "UPDATE chat_sessions SET updated_at = ?, \
+         title = CASE WHEN title = '' THEN ? ELSE title END WHERE id = ?",
+    )
+    .bind(&assistant.created_at)
+    .bind(title_from_message(&user.content))
+    .bind(session_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(assistant)
+}
+
+/// 세션과 메시지 삭제. 세션이 있었으면 true
+pub async fn delete_session(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM chat_messages WHERE session_id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    let result = sqlx::query("DELETE FROM chat_sessions WHERE id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: &str) -> ChatMessage {
+        ChatMessage::new("s", role, content)
+    }
+
+    #[test]
+    fn test_trim_history_keeps_recent_turns() {
+        let long = "x".repeat(400); // 104 토큰
+        let messages = vec![
+            message("user", &long),
+            message("assistant", &long),
+            message("user", &long),
+            message("assistant", &long),
+            message("user", "move gym"),
+        ];
+
+        let kept = trim_history(&messages, 250);
+        assert_eq!(kept.len(), 3);
+        assert_eq!(kept[0].role, "user");
+
+        // assistant로 시작하게 되면 한 칸 더 자름
+        let kept = trim_history(&messages, 150);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].content, "move gym");
+
+        // 예산보다 긴 마지막 메시지도 보냄
+        assert_eq!(trim_history(&messages[..1], 10).len(), 1);
+        assert!(trim_history(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_title_from_message() {
+        assert_eq!(title_from_message("\n  헬스 내일로 옮겨줘\n아파서"), "헬스 내일로 옮겨줘");
+        let title = title_from_message(&"a".repeat(60));
+        assert_eq!(title.chars().count(), TITLE_MAX_CHARS + 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let pool = crate::db::test_pool().await;
+
+        let session = create_session(&pool, "").await.unwrap();
+        let reply = append_turn(&pool, &session.id, "move my gym session to tomorrow", "Moved to 18:00")
+            .await
+            .unwrap();
+        assert_eq!(reply.role, "assistant");
+        append_turn(&pool, &session.id, "make it 19:00", "OK, 19:00").await.unwrap();
+
+        let stored = get_session(&pool, &session.id).await.unwrap().unwrap();
+        assert_eq!(stored.title, "move my gym session to tomorrow");
+        assert_eq!(stored.message_count, 4);
+
+        let history = messages(&pool, &session.id).await.unwrap();
+        let roles: Vec<&str> = history.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["user", "assistant", "user", "assistant"]);
+        assert_eq!(history[3].content, "OK, 19:00");
+
+        assert_eq!(list_sessions(&pool).await.unwrap().len(), 1);
+        assert!(delete_session(&pool, &session.id).await.unwrap());
+        assert!(!delete_session(&pool, &session.id).await.unwrap());
+        assert!(messages(&pool, &session.id).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::agent::{self, AppliedChanges, ProposedChange, ScheduleTools};
+use crate::db;
+use crate::llm::agent::{AgentMessage, AgentRequest};
+use crate::llm::prompts::PromptId;
+use crate::llm::stream::StreamRegistry;
+use crate::llm::LLMError;
+use crate::models::generate_id;
+use crate::schedule::{self, TimeSlot};
+use crate::ApiKeyState;
+
+/// 보관하는 변경안 최대 개수 (넘으면 오래된 것부터 버림)
+const MAX_PROPOSALS: usize = 20;
+/// 변경안 보관 시간
+const PROPOSAL_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// 사용자 확인을 기다리는 에이전트 변경안 (proposal id 기준)
+#[derive(Default)]
+pub struct AgentProposals(Mutex<HashMap<String, (Instant, Vec<ProposedChange>)>>);
+
+impl AgentProposals {
+    /// 변경안을 보관하고 id 반환. 변경이 없으면 None
+    ///
+    /// 만료된 변경안은 버리고, 개수가 넘치면 가장 오래된 것부터 버립니다.
+    pub fn store(&self, changes: &[ProposedChange]) -> Option<String> {
+        if changes.is_empty() {
+            return None;
+        }
+        let now = Instant::now();
+        let mut proposals = self.0.lock().unwrap();
+        proposals.retain(|_, (stored_at, _)| now.duration_since(*stored_at) < PROPOSAL_TTL);
+        while proposals.len() >= MAX_PROPOSALS {
+            let oldest = proposals
+                .iter()
+                .min_by_key(|(_, (stored_at, _))| *stored_at)
+                .map(|(id, _)| id.clone());
+            match oldest {
+                Some(id) => proposals.remove(&id),
+                None => break,
+            };
+        }
+        let id = generate_id();
+        proposals.insert(id.clone(), (now, changes.to_vec()));
+        Some(id)
+    }
+
+    /// 변경안을 꺼냄 (만료됐으면 None)
+    fn take(&self, id: &str) -> Option<(Instant, Vec<ProposedChange>)> {
+        self.0
+            .lock()
+            .unwrap()
+            .remove(id)
+            .filter(|(stored_at, _)| stored_at.elapsed() < PROPOSAL_TTL)
+    }
+
+    /// 반영하지 못한 변경안을 다시 보관
+    fn restore(&self, id: String, proposal: (Instant, Vec<ProposedChange>)) {
+        self.0.lock().unwrap().insert(id, proposal);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentProposal {
+    /// 변경이 없으면 None
+    pub id: Option<String>,
+    pub message: String,
+    pub tool_calls: usize,
+    pub changes: Vec<ProposedChange>,
+}
+
+/// 요청에 따라 에이전트가 일정 변경안을 만듦
+///
+/// 도구 호출로 만든 변경은 바로 저장하지 않고 `apply_agent_changes`로 확인받습니다.
+/// `request_id`가 있으면 `cancel_llm_request`로 취소할 수 있습니다.
+#[tauri::command]
+pub async fn run_planning_agent(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    proposals: State<'_, AgentProposals>,
+    input: String,
+    request_id: Option<String>,
+) -> Result<AgentProposal, LLMError> {
+    let input = input.trim().to_string();
+    if input.is_empty() {
+        return Err(LLMError::InvalidInput("input must not be empty".to_string()));
+    }
+
+    let pool = db::pool(&app).await.map_err(LLMError::DatabaseError)?;
+    let service = crate::create_llm_service(&app, &state, "agent").await?;
+    let prompts = crate::load_prompt_registry(&app);
+
+    let today = crate::llm::cache::today();
+    let window = TimeSlot {
+        start: schedule::DAY_START.to_string(),
+        end: schedule::DAY_END.to_string(),
+    };
+    let mut context = crate::load_day_context(&app, &today, &window).await;
+    context.plans = Some(
+        db::active_plans(&pool)
+            .await
+            .map_err(|e| LLMError::DatabaseError(e.to_string()))?,
+    );
+    let system = prompts.render(
+        PromptId::Agent,
+        &[("current_date", &today), ("context", &context.describe(prompts.locale()))],
+    )?;
+
+    let request = AgentRequest {
+        system: Some(system),
+        messages: vec![AgentMessage::user(&input)],
+        tools: agent::tools(),
+        max_tokens: Some(2048),
+        temperature: Some(0.2),
+    };
+    let mut tools = ScheduleTools::new(pool, &today, crate::load_recurring_horizon(&app));
+    let run = match request_id.as_deref() {
+        Some(id) => {
+            let streams = app.state::<StreamRegistry>();
+            let future = crate::llm::agent::run(&service, request, &mut tools);
+            crate::llm::stream::run_with_events(&app, &streams, id, future).await?
+        }
+        None => crate::llm::agent::run(&service, request, &mut tools).await?,
+    };
+
+    let changes = tools.into_changes();
+    Ok(AgentProposal {
+        id: proposals.store(&changes),
+        message: run.message,
+        tool_calls: run.tool_calls,
+        changes,
+    })
+}
+
+/// 변경안을 저장. `accepted`가 있으면 해당 인덱스의 변경만 저장
+///
+/// 저장에 실패하면 변경안을 다시 보관해 재시도할 수 있습니다.
+#[tauri::command]
+pub async fn apply_agent_changes(
+    app: AppHandle,
+    proposals: State<'_, AgentProposals>,
+    proposal_id: String,
+    accepted: Option<Vec<usize>>,
+) -> Result<AppliedChanges, String> {
+    let proposal = proposals
+        .take(&proposal_id)
+        .ok_or_else(|| format!("Agent proposal not found: {}", proposal_id))?;
+    let changes = agent::select(proposal.1.clone(), accepted.as_deref());
+
+    let result = async {
+        let pool = db::pool(&app).await?;
+        agent::apply(&pool, &changes, crate::load_recurring_horizon(&app))
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => "A task in this proposal no longer exists".to_string(),
+                e => e.to_string(),
+            })
+    }
+    .await;
+    if result.is_err() {
+        proposals.restore(proposal_id, proposal);
+    }
+    result
+}
+
+/// 변경안을 저장하지 않고 버림
+#[tauri::command]
+pub fn discard_agent_changes(proposals: State<'_, AgentProposals>, proposal_id: String) -> bool {
+    proposals.0.lock().unwrap().remove(&proposal_id).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Change;
+
+    fn proposal(title: &str) -> Vec<ProposedChange> {
+        vec![ProposedChange {
+            summary: title.to_string(),
+            change: Change::RescheduleTask {
+                task_id: title.to_string(),
+                scheduled_date: "2026-03-02".to_string(),
+                scheduled_time: None,
+            },
+        }]
+    }
+
+    #[test]
+    fn test_proposals_are_capped_and_restorable() {
+        let proposals = AgentProposals::default();
+        assert!(proposals.store(&[]).is_none());
+
+        let first = proposals.store(&proposal("first")).unwrap();
+        for i in 0..MAX_PROPOSALS {
+            proposals.store(&proposal(&format!("p{}", i))).unwrap();
+        }
+        assert_eq!(proposals.0.lock().unwrap().len(), MAX_PROPOSALS);
+        // 가장 오래된 변경안부터 버림
+        assert!(proposals.take(&first).is_none());
+
+        let id = proposals.store(&proposal("retry")).unwrap();
+        let taken = proposals.take(&id).unwrap();
+        assert!(proposals.take(&id).is_none());
+        proposals.restore(id.clone(), taken);
+        assert_eq!(proposals.take(&id).unwrap().1[0].summary, "retry");
+    }
+}
@@ -0,0 +1,103 @@
+use tauri::{AppHandle, Manager, State};
+
+use crate::chat::{self, ChatMessage, ChatSession, HISTORY_TOKEN_BUDGET};
+use crate::db;
+use crate::llm::prompts::PromptId;
+use crate::llm::stream::StreamRegistry;
+use crate::llm::{LLMError, LLMMessage, LLMRequest};
+use crate::schedule::{self, TimeSlot};
+use crate::ApiKeyState;
+
+type Result<T> = std::result::Result<T, String>;
+
+#[tauri::command]
+pub async fn create_chat_session(app: AppHandle, title: Option<String>) -> Result<ChatSession> {
+    let pool = db::pool(&app).await?;
+    let title = title.map(|t| t.trim().to_string()).unwrap_or_default();
+    chat::create_session(&pool, &title).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_chat_sessions(app: AppHandle) -> Result<Vec<ChatSession>> {
+    let pool = db::pool(&app).await?;
+    chat::list_sessions(&pool).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_chat_messages(app: AppHandle, session_id: String) -> Result<Vec<ChatMessage>> {
+    let pool = db::pool(&app).await?;
+    chat::messages(&pool, &session_id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_chat_session(app: AppHandle, session_id: String) -> Result<bool> {
+    let pool = db::pool(&app).await?;
+    chat::delete_session(&pool, &session_id).await.map_err(|e| e.to_string())
+}
+
+/// 세션에 메시지를 보내고 AI 답변을 받음
+///
+/// system prompt에 진행 중인 계획과 오늘 일정을 넣고, 이전 대화는 토큰 예산에 맞게 잘라 보냅니다.
+/// `request_id`가 있으면 답변을 `llm-delta` 이벤트로 스트리밍합니다.
+/// 성공한 턴만 저장하므로 실패하면 같은 메시지로 다시 보낼 수 있습니다.
+#[tauri::command]
+pub async fn send_chat_message(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    session_id: String,
+    content: String,
+    request_id: Option<String>,
+) -> std::result::Result<ChatMessage, LLMError> {
+    let content = content.trim().to_string();
+    if content.is_empty() {
+        return Err(LLMError::InvalidInput("message must not be empty".to_string()));
+    }
+
+    let pool = db::pool(&app).await.map_err(LLMError::DatabaseError)?;
+    let database = |e: sqlx::Error| LLMError::DatabaseError(e.to_string());
+    if chat::get_session(&pool, &session_id).await.map_err(database)?.is_none() {
+        return Err(LLMError::InvalidInput(format!("Chat session not found: {}", session_id)));
+    }
+    let mut history = chat::messages(&pool, &session_id).await.map_err(database)?;
+    history.push(ChatMessage::new(&session_id, "user", &content));
+
+    let service = crate::create_llm_service(&app, &state, "chat").await?;
+    let prompts = crate::load_prompt_registry(&app);
+
+    let today = crate::llm::cache::today();
+    let window = TimeSlot {
+        start: schedule::DAY_START.to_string(),
+        end: schedule::DAY_END.to_string(),
+    };
+    let mut context = crate::load_day_context(&app, &today, &window).await;
+    context.plans = Some(db::active_plans(&pool).await.map_err(database)?);
+    let system = prompts.render(
+        PromptId::Chat,
+        &[("current_date", &today), ("context", &context.describe(prompts.locale()))],
+    )?;
+
+    let mut messages = vec![LLMMessage {
+        role: "system".to_string(),
+        content: system,
+    }];
+    messages.extend(chat::trim_history(&history, HISTORY_TOKEN_BUDGET).iter().map(ChatMessage::to_llm));
+    let request = LLMRequest {
+        messages,
+        max_tokens: Some(1024),
+        temperature: Some(0.7),
+    };
+
+    let response = match request_id.as_deref() {
+        Some(id) => {
+            let streams = app.state::<StreamRegistry>();
+            let on_delta = crate::llm::stream::delta_emitter(&app, id);
+            let future = service.complete_stream(request, &on_delta);
+            crate::llm::stream::run_with_events(&app, &streams, id, future).await?
+        }
+        None => service.complete(request).await?,
+    };
+
+    chat::append_turn(&pool, &session_id, &content, &response.content)
+        .await
+        .map_err(database)
+}
@@ -0,0 +1,58 @@
+use chrono::{Duration, NaiveDate};
+use tauri::AppHandle;
+
+use crate::conflicts::{self, Conflict, EventKind, ScheduleEvent, MAX_RANGE_DAYS};
+use crate::db;
+use crate::schedule::{format_minutes, to_minutes, DEFAULT_DURATION};
+
+type Result<T> = std::result::Result<T, String>;
+
+fn parse_date(date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("Invalid date: {}", date))
+}
+
+/// `from`~`to` (포함) 기간의 일정 충돌
+#[tauri::command]
+pub async fn find_schedule_conflicts(app: AppHandle, from: String, to: String) -> Result<Vec<Conflict>> {
+    let (from, to) = (parse_date(&from)?, parse_date(&to)?);
+    if to < from || to - from >= Duration::days(MAX_RANGE_DAYS) {
+        return Err(format!("Date range must be 1-{} days", MAX_RANGE_DAYS));
+    }
+    let pool = db::pool(&app).await?;
+    let events = conflicts::load_events(&pool, from, to)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(conflicts::find_conflicts(&events))
+}
+
+/// 저장하기 전에 새(또는 수정 중인) 태스크가 기존 일정과 겹치는지 검사
+///
+/// 시간이 없으면 충돌도 없습니다. 태스크를 수정할 때는 `task_id`를 넘겨 자기 자신을 제외합니다.
+#[tauri::command]
+pub async fn check_task_conflicts(
+    app: AppHandle,
+    date: String,
+    scheduled_time: Option<String>,
+    estimated_duration: Option<i32>,
+    task_id: Option<String>,
+) -> Result<Vec<Conflict>> {
+    let day = parse_date(&date)?;
+    let Some(time) = scheduled_time.as_deref() else {
+        return Ok(Vec::new());
+    };
+    let start = to_minutes(time).ok_or_else(|| format!("Invalid time: {}", time))?;
+    let candidate = ScheduleEvent {
+        kind: EventKind::Task,
+        id: task_id.clone().unwrap_or_default(),
+        title: String::new(),
+        date: date.clone(),
+        start: format_minutes(start),
+        end: format_minutes(start + estimated_duration.filter(|d| *d > 0).unwrap_or(DEFAULT_DURATION)),
+    };
+
+    let pool = db::pool(&app).await?;
+    let events = conflicts::load_events(&pool, day, day)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(conflicts::conflicts_with(&candidate, &events, task_id.as_deref()))
+}
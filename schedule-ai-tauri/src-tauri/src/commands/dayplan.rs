@@ -0,0 +1,55 @@
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::agent::ProposedChange;
+use crate::commands::agent::AgentProposals;
+use crate::dayplan::{self, DayPlan, PlannerPreferences};
+use crate::db;
+use crate::schedule;
+
+type Result<T> = std::result::Result<T, String>;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayPlanProposal {
+    /// `apply_agent_changes`에 넘길 id. 바뀌는 태스크가 없으면 None
+    pub id: Option<String>,
+    pub plan: DayPlan,
+    pub changes: Vec<ProposedChange>,
+}
+
+/// 하루 일정을 자동으로 배치한 타임라인과 변경안 (AI 호출 없음)
+///
+/// `preferences`를 넘기지 않으면 저장된 설정을 씁니다. 오늘이면 현재 시각 이후에만 배치합니다.
+/// 변경안은 바로 저장하지 않고 `apply_agent_changes`로 확인받습니다.
+#[tauri::command]
+pub async fn plan_day(
+    app: AppHandle,
+    proposals: State<'_, AgentProposals>,
+    date: String,
+    preferences: Option<PlannerPreferences>,
+) -> Result<DayPlanProposal> {
+    let preferences = preferences.unwrap_or_else(|| crate::load_planner_preferences(&app));
+    let pool = db::pool(&app).await?;
+    let database = |e: sqlx::Error| e.to_string();
+
+    let tasks = db::tasks_between(&pool, &date, &date).await.map_err(database)?;
+    let generated = db::generated_recurring_ids(&pool, &date).await.map_err(database)?;
+    let recurring: Vec<_> = db::active_recurring_plans(&pool)
+        .await
+        .map_err(database)?
+        .into_iter()
+        .filter(|plan| !generated.contains(&plan.id))
+        .collect();
+    let core_times = db::active_core_times(&pool).await.map_err(database)?;
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let now = (date == today).then(schedule::now_rounded);
+    let plan = dayplan::plan_day(&date, &tasks, &recurring, &core_times, &preferences, now.as_deref());
+    let changes = plan.changes(&tasks);
+    Ok(DayPlanProposal {
+        id: proposals.store(&changes),
+        plan,
+        changes,
+    })
+}
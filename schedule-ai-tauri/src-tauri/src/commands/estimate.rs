@@ -0,0 +1,33 @@
+use tauri::AppHandle;
+
+use crate::db;
+use crate::estimate::{self, EstimationSummary, Prediction};
+
+type Result<T> = std::result::Result<T, String>;
+
+/// 새 태스크의 소요시간 예측
+///
+/// `estimated_duration`(사용자가 적은 예상 시간)이 있으면 보정하고, 없으면 비슷한 태스크의 실제 시간으로 예측합니다.
+#[tauri::command]
+pub async fn predict_task_duration(
+    app: AppHandle,
+    title: String,
+    plan_id: Option<String>,
+    estimated_duration: Option<i32>,
+) -> Result<Prediction> {
+    let pool = db::pool(&app).await?;
+    let estimator = estimate::load(&pool, chrono::Local::now().date_naive())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(estimator.predict(&title, plan_id.as_deref(), estimated_duration))
+}
+
+/// 전체와 키워드별 소요시간 보정 계수
+#[tauri::command]
+pub async fn get_estimation_summary(app: AppHandle) -> Result<EstimationSummary> {
+    let pool = db::pool(&app).await?;
+    let estimator = estimate::load(&pool, chrono::Local::now().date_naive())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(estimator.summary())
+}
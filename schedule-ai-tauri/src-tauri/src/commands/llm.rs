@@ -1,5 +1,16 @@
-use crate::models::{ParsedPlanContent, Plan, SuggestedTask, Task};
+use crate::db;
+use crate::estimate::EstimationSummary;
+use crate::llm::prompts::{Locale, PromptId};
+use crate::llm::structured::{check_date, check_range, check_time};
+use crate::llm::{LLMError, LLMMessage, LLMRequest, StructuredOutput};
+use crate::models::{Milestone, ParsedPlanContent, Plan, RecurrencePattern, RecurrenceType, SuggestedTask, Task};
+use crate::plans::{self, MaterializedPlan};
+use crate::recurring::ParsedRecurrencePattern;
+use crate::schedule::{DaySchedule, RecentStats, SlotAllocator, DEFAULT_DURATION};
+use crate::ApiKeyState;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
 
 type Result<T> = std::result::Result<T, String>;
 
@@ -9,6 +20,14 @@ pub struct LLMContext {
     pub plans: Option<Vec<Plan>>,
     pub recent_tasks: Option<Vec<Task>>,
     pub current_date: Option<String>,
+    /// `current_date`의 기존 일정과 빈 시간
+    #[serde(default)]
+    pub schedule: Option<DaySchedule>,
+    #[serde(default)]
+    pub recent_stats: Option<RecentStats>,
+    /// 완료 기록으로 학습한 소요시간 보정
+    #[serde(default)]
+    pub estimation: Option<EstimationSummary>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,126 +50,725 @@ pub struct ParsePlanResponse {
     pub parsed_content: ParsedPlanContent,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// `parse_plan_with_ai`의 모델 출력
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanParseOutput {
+    /// 구체적이고 측정 가능한 목표
+    pub goals: Vec<String>,
+    /// 중간 목표
+    #[serde(default)]
+    pub milestones: Vec<PlanMilestoneOutput>,
+    /// 계획을 이루기 위한 일일 태스크
+    pub suggested_tasks: Vec<PlanTaskOutput>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanMilestoneOutput {
+    pub title: String,
+    /// 목표 날짜 "YYYY-MM-DD"
+    pub target_date: Option<String>,
+    /// 이 마일스톤에 속하는 suggestedTasks의 title
+    #[serde(default)]
+    pub tasks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTaskOutput {
+    pub title: String,
+    /// 예상 소요시간(분)
+    pub estimated_duration: Option<i32>,
+    /// 우선순위 0-3
+    pub priority: i32,
+    /// 반복하는 태스크면 반복 주기 (한 번만 하면 생략)
+    pub frequency: Option<PlanFrequencyOutput>,
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanFrequencyOutput {
+    pub recurrence_type: crate::recurring::RecurrenceType,
+    /// 반복 간격 (기본값 1, 격주면 2)
+    pub interval: Option<i32>,
+    /// 요일 배열 (0=일, 1=월, ..., 6=토)
+    pub days_of_week: Option<Vec<i32>>,
+    /// 월간 반복 날짜 (1-31)
+    pub day_of_month: Option<i32>,
+}
+
+impl StructuredOutput for PlanParseOutput {
+    const TOOL_NAME: &'static str = "parse_plan";
+
+    fn validate(&self) -> Result<()> {
+        if self.suggested_tasks.iter().any(|t| t.title.trim().is_empty()) {
+            return Err("suggestedTasks[].title must not be empty".to_string());
+        }
+        for task in &self.suggested_tasks {
+            check_range("suggestedTasks[].priority", Some(task.priority), 0, 3)?;
+            check_range("suggestedTasks[].estimatedDuration", task.estimated_duration, 1, 24 * 60)?;
+            if let Some(frequency) = &task.frequency {
+                check_range("suggestedTasks[].frequency.interval", frequency.interval, 1, 365)?;
+                for day in frequency.days_of_week.iter().flatten() {
+                    check_range("suggestedTasks[].frequency.daysOfWeek[]", Some(*day), 0, 6)?;
+                }
+                check_range("suggestedTasks[].frequency.dayOfMonth", frequency.day_of_month, 1, 31)?;
+            }
+        }
+        for milestone in &self.milestones {
+            if milestone.title.trim().is_empty() {
+                return Err("milestones[].title must not be empty".to_string());
+            }
+            check_date("milestones[].targetDate", milestone.target_date.as_deref())?;
+        }
+        Ok(())
+    }
+}
+
+impl PlanFrequencyOutput {
+    pub fn to_pattern(&self) -> RecurrencePattern {
+        RecurrencePattern {
+            recurrence_type: match self.recurrence_type {
+                crate::recurring::RecurrenceType::Daily => RecurrenceType::Daily,
+                crate::recurring::RecurrenceType::Weekly => RecurrenceType::Weekly,
+                crate::recurring::RecurrenceType::Monthly => RecurrenceType::Monthly,
+                crate::recurring::RecurrenceType::Yearly => RecurrenceType::Yearly,
+            },
+            interval: self.interval.unwrap_or(1),
+            days_of_week: self.days_of_week.clone().filter(|d| !d.is_empty()),
+            day_of_month: self.day_of_month,
+            end_date: None,
+            rrule: None,
+        }
+    }
+}
+
+impl PlanParseOutput {
+    /// 저장 형식으로 변환
+    ///
+    /// 마일스톤의 태스크는 suggestedTasks의 제목으로 연결하고 (대소문자/공백 무시),
+    /// 없는 제목은 버립니다.
+    pub fn into_content(self) -> ParsedPlanContent {
+        let normalize = |title: &str| title.trim().to_lowercase();
+        let milestones = self
+            .milestones
+            .into_iter()
+            .map(|milestone| Milestone {
+                title: milestone.title.trim().to_string(),
+                target_date: milestone.target_date,
+                tasks: milestone
+                    .tasks
+                    .iter()
+                    .filter_map(|title| {
+                        self.suggested_tasks
+                            .iter()
+                            .find(|t| normalize(&t.title) == normalize(title))
+                            .map(|t| t.title.trim().to_string())
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let suggested_tasks = self
+            .suggested_tasks
+            .into_iter()
+            .map(|task| SuggestedTask {
+                title: task.title.trim().to_string(),
+                estimated_duration: task.estimated_duration,
+                priority: task.priority,
+                frequency: task.frequency.as_ref().map(PlanFrequencyOutput::to_pattern),
+            })
+            .collect();
+
+        ParsedPlanContent {
+            goals: self.goals,
+            milestones,
+            suggested_tasks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GenerateDailyTasksResponse {
     pub tasks: Vec<GeneratedTask>,
+    /// 오늘 할 일에 대한 한두 문장 요약
     pub summary: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneratedTask {
     pub title: String,
     pub description: Option<String>,
+    /// 예상 소요시간(분)
     pub estimated_duration: Option<i32>,
+    /// 우선순위 0-3
     pub priority: i32,
+    /// 시작 시간 "HH:MM"
     pub scheduled_time: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl StructuredOutput for GenerateDailyTasksResponse {
+    const TOOL_NAME: &'static str = "generate_daily_tasks";
+
+    fn validate(&self) -> Result<()> {
+        if self.tasks.is_empty() {
+            return Err("tasks must not be empty".to_string());
+        }
+        for task in &self.tasks {
+            if task.title.trim().is_empty() {
+                return Err("tasks[].title must not be empty".to_string());
+            }
+            check_range("tasks[].priority", Some(task.priority), 0, 3)?;
+            check_range("tasks[].estimatedDuration", task.estimated_duration, 1, 24 * 60)?;
+            check_time("tasks[].scheduledTime", task.scheduled_time.as_deref())?;
+        }
+        Ok(())
+    }
+}
+
+impl GenerateDailyTasksResponse {
+    /// 생성된 태스크를 빈 슬롯에 겹치지 않게 배치
+    ///
+    /// 모델이 정한 시간이 빈 슬롯에 들어가면 유지하고, 아니면 가장 이른 빈 시간으로 옮깁니다.
+    /// 들어갈 자리가 없으면 시간을 비웁니다.
+    pub fn fit_to(&mut self, schedule: &DaySchedule) {
+        let mut slots = SlotAllocator::new(&schedule.free_slots);
+        let duration = |task: &GeneratedTask| task.estimated_duration.unwrap_or(DEFAULT_DURATION);
+
+        let mut placed = vec![false; self.tasks.len()];
+        for (task, placed) in self.tasks.iter_mut().zip(placed.iter_mut()) {
+            let start = task.scheduled_time.as_deref().and_then(|t| slots.take_at(t, duration(task)));
+            if let Some(start) = start {
+                task.scheduled_time = Some(start);
+                *placed = true;
+            }
+        }
+        for (task, _) in self.tasks.iter_mut().zip(placed).filter(|(_, placed)| !placed) {
+            task.scheduled_time = slots.take_earliest(duration(task));
+        }
+
+        self.tasks
+            .sort_by_key(|task| (task.scheduled_time.is_none(), task.scheduled_time.clone()));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SplitTaskResponse {
     pub subtasks: Vec<GeneratedSubTask>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneratedSubTask {
     pub title: String,
+    /// 예상 소요시간(분)
     pub estimated_minutes: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl StructuredOutput for SplitTaskResponse {
+    const TOOL_NAME: &'static str = "split_task";
+
+    fn validate(&self) -> Result<()> {
+        if self.subtasks.is_empty() {
+            return Err("subtasks must not be empty".to_string());
+        }
+        for subtask in &self.subtasks {
+            if subtask.title.trim().is_empty() {
+                return Err("subtasks[].title must not be empty".to_string());
+            }
+            check_range("subtasks[].estimatedMinutes", Some(subtask.estimated_minutes), 1, 240)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ParseTaskResponse {
     pub title: String,
+    /// 날짜 "YYYY-MM-DD"
     pub scheduled_date: Option<String>,
+    /// 시작 시간 "HH:MM"
     pub scheduled_time: Option<String>,
+    /// 종료 시간 "HH:MM"
     pub end_time: Option<String>,
     pub location: Option<String>,
     pub subtasks: Option<Vec<String>>,
+    /// 우선순위 0-3
     pub priority: Option<i32>,
+    /// 예상 소요시간(분)
     pub estimated_duration: Option<i32>,
+    /// AI 대신 규칙 기반 파서로 해석했는지
+    #[serde(default)]
+    #[schemars(skip)]
+    pub parsed_offline: bool,
+}
+
+impl StructuredOutput for ParseTaskResponse {
+    const TOOL_NAME: &'static str = "parse_task";
+
+    fn validate(&self) -> Result<()> {
+        if self.title.trim().is_empty() {
+            return Err("title must not be empty".to_string());
+        }
+        check_date("scheduledDate", self.scheduled_date.as_deref())?;
+        check_time("scheduledTime", self.scheduled_time.as_deref())?;
+        check_time("endTime", self.end_time.as_deref())?;
+        check_range("priority", self.priority, 0, 3)?;
+        check_range("estimatedDuration", self.estimated_duration, 1, 24 * 60)
+    }
+}
+
+/// 확신도가 이보다 낮은 항목은 사용자 확인 필요
+pub const CAPTURE_CONFIRM_THRESHOLD: f32 = 0.6;
+
+/// `capture_tasks_with_ai`의 모델 출력
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureTasksOutput {
+    /// 입력에 있는 할 일 (입력 순서대로)
+    pub items: Vec<CapturedItem>,
+}
+
+/// 입력에서 찾은 할 일 하나. `task`와 `recurrence` 중 하나만 채움
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedItem {
+    /// 이 항목에 해당하는 입력 부분
+    pub source_text: String,
+    /// 해석 확신도 0-1
+    pub confidence: f32,
+    /// 한 번 하는 일
+    pub task: Option<ParseTaskResponse>,
+    /// 반복하는 일 (매주, 매일 등)
+    pub recurrence: Option<ParsedRecurrencePattern>,
+    /// 날짜가 모호하면 사용자에게 물어볼 내용 (마감일인지 하는 날인지 등)
+    pub date_question: Option<String>,
+    /// 사용자 확인이 필요한지 (모호한 날짜, 낮은 확신도, 지난 날짜)
+    #[serde(default)]
+    #[schemars(skip)]
+    pub needs_confirmation: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureTasksResponse {
+    pub items: Vec<CapturedItem>,
+}
+
+impl StructuredOutput for CaptureTasksOutput {
+    const TOOL_NAME: &'static str = "capture_tasks";
+
+    fn validate(&self) -> Result<()> {
+        for (index, item) in self.items.iter().enumerate() {
+            let field = |name: &str| format!("items[{}].{}", index, name);
+            if !(0.0..=1.0).contains(&item.confidence) {
+                return Err(format!("{} must be between 0 and 1", field("confidence")));
+            }
+            match (&item.task, &item.recurrence) {
+                (Some(task), None) => task.validate().map_err(|e| format!("{}: {}", field("task"), e))?,
+                (None, Some(recurrence)) => {
+                    recurrence
+                        .validate()
+                        .map_err(|e| format!("{}: {}", field("recurrence"), e))?;
+                    if recurrence.title.as_deref().is_none_or(|t| t.trim().is_empty()) {
+                        return Err(format!("{} must not be empty", field("recurrence.title")));
+                    }
+                }
+                _ => return Err(format!("{} must have exactly one of task or recurrence", field(""))),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CaptureTasksOutput {
+    /// 확인이 필요한 항목 표시
+    pub fn finish(self, today: &str) -> CaptureTasksResponse {
+        let items = self
+            .items
+            .into_iter()
+            .map(|mut item| {
+                let past = item
+                    .task
+                    .as_ref()
+                    .and_then(|t| t.scheduled_date.as_deref())
+                    .is_some_and(|date| date < today);
+                item.date_question = item.date_question.filter(|q| !q.trim().is_empty());
+                item.needs_confirmation =
+                    item.date_question.is_some() || item.confidence < CAPTURE_CONFIRM_THRESHOLD || past;
+                item
+            })
+            .collect();
+        CaptureTasksResponse { items }
+    }
+}
+
+/// 계획 한 줄 요약 ("- 제목: 설명 (목표: ...)")
+fn describe_plan(plan: &Plan) -> String {
+    let mut line = format!("- {}", plan.title);
+    if let Some(description) = plan.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        line.push_str(&format!(": {}", description.trim()));
+    }
+    if let Some(content) = plan.parsed_content.as_ref().filter(|c| !c.goals.is_empty()) {
+        line.push_str(&format!(" ({})", content.goals.join("; ")));
+    }
+    line
+}
+
+/// 최근 태스크 중 context에 넣는 개수
+const CONTEXT_TASK_LIMIT: usize = 30;
+
+impl LLMContext {
+    /// prompt에 넣을 context 요약
+    pub fn describe(&self, locale: Locale) -> String {
+        let labels = match locale {
+            Locale::En => ["Plans", "Schedule", "Free slots", "Recent statistics", "Duration estimates", "Recent tasks"],
+            Locale::Ko => ["계획", "일정", "빈 시간", "최근 통계", "소요시간 보정", "최근 태스크"],
+        };
+        let mut sections = Vec::new();
+
+        if let Some(plans) = self.plans.as_ref().filter(|p| !p.is_empty()) {
+            let lines: Vec<String> = plans.iter().map(describe_plan).collect();
+            sections.push(format!("{}:\n{}", labels[0], lines.join("\n")));
+        }
+        if let Some(schedule) = &self.schedule {
+            sections.push(format!("{} ({}):\n{}", labels[1], schedule.date, schedule.describe_busy(locale)));
+            sections.push(format!("{}:\n{}", labels[2], schedule.describe_free_slots(locale)));
+        }
+        if let Some(stats) = &self.recent_stats {
+            sections.push(format!("{}:\n{}", labels[3], stats.describe(locale)));
+        }
+        if let Some(estimation) = self.estimation.as_ref().filter(|e| e.sample_count > 0) {
+            sections.push(format!("{}:\n{}", labels[4], estimation.describe(locale)));
+        }
+        if let Some(tasks) = self.recent_tasks.as_ref().filter(|t| !t.is_empty()) {
+            let skip = tasks.len().saturating_sub(CONTEXT_TASK_LIMIT);
+            let lines: Vec<String> = tasks
+                .iter()
+                .skip(skip)
+                .map(|t| match &t.scheduled_time {
+                    Some(time) => format!("- {} {} {} ({})", t.scheduled_date, time, t.title, t.status),
+                    None => format!("- {} {} ({})", t.scheduled_date, t.title, t.status),
+                })
+                .collect();
+            sections.push(format!("{}:\n{}", labels[5], lines.join("\n")));
+        }
+
+        if sections.is_empty() {
+            locale.none_label().to_string()
+        } else {
+            sections.join("\n\n")
+        }
+    }
 }
 
+/// 여러 계획을 하루 태스크 생성용 제목/설명 하나로 합침
+pub fn combine_plans(plans: &[Plan]) -> (String, String) {
+    let title = plans.iter().map(|p| p.title.as_str()).collect::<Vec<_>>().join(" + ");
+    let description = plans.iter().map(describe_plan).collect::<Vec<_>>().join("\n");
+    (title, description)
+}
+
+/// context(계획, 일정, 최근 태스크)를 바탕으로 한 일반 대화
 #[tauri::command]
-pub async fn process_with_llm(prompt: String, _context: LLMContext) -> Result<LLMResponse> {
-    // TODO: Implement actual LLM call
-    // For now, return a placeholder response
+pub async fn process_with_llm(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    prompt: String,
+    context: LLMContext,
+) -> std::result::Result<LLMResponse, LLMError> {
+    if prompt.trim().is_empty() {
+        return Err(LLMError::InvalidInput("prompt must not be empty".to_string()));
+    }
+
+    let service = crate::create_llm_service(&app, &state, "process_with_llm").await?;
+    let prompts = crate::load_prompt_registry(&app);
+    let current_date = context
+        .current_date
+        .clone()
+        .unwrap_or_else(crate::llm::cache::today);
+    let content = prompts.render(
+        PromptId::Assistant,
+        &[
+            ("current_date", &current_date),
+            ("context", &context.describe(prompts.locale())),
+            ("input", &prompt),
+        ],
+    )?;
+
+    let request = LLMRequest {
+        messages: vec![LLMMessage {
+            role: "user".to_string(),
+            content,
+        }],
+        max_tokens: Some(1024),
+        temperature: Some(0.7),
+    };
+
+    let response = service.complete(request).await?;
     Ok(LLMResponse {
-        content: format!("Processed: {}", prompt),
-        usage: Some(TokenUsage {
-            input_tokens: 100,
-            output_tokens: 50,
+        content: response.content,
+        usage: response.usage.map(|u| TokenUsage {
+            input_tokens: u.input_tokens,
+            output_tokens: u.output_tokens,
         }),
     })
 }
 
+/// 자연어 계획을 목표와 추천 태스크로 구조화 (`parse_plan_with_ai`와 동일)
 #[tauri::command]
-pub async fn parse_plan(input: String) -> Result<ParsePlanResponse> {
-    // TODO: Call LLM to parse the plan
-    // For now, return a basic parsed structure
-    Ok(ParsePlanResponse {
-        parsed_content: ParsedPlanContent {
-            goals: vec![format!("Goal from: {}", input)],
-            milestones: vec![],
-            suggested_tasks: vec![SuggestedTask {
-                title: "Sample task".to_string(),
-                estimated_duration: Some(25),
-                priority: 1,
-                frequency: None,
-            }],
-        },
-    })
+pub async fn parse_plan(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    input: String,
+    plan_rules: Option<String>,
+    request_id: Option<String>,
+) -> std::result::Result<ParsePlanResponse, LLMError> {
+    crate::parse_plan_with_ai(app, state, input, plan_rules, request_id).await
 }
 
+/// 저장된 계획들을 합쳐 하루 태스크 생성
 #[tauri::command]
 pub async fn generate_daily_tasks(
-    _plan_ids: Vec<String>,
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    plan_ids: Vec<String>,
     date: String,
-) -> Result<GenerateDailyTasksResponse> {
-    // TODO: Call LLM to generate daily tasks based on plans
-    // For now, return sample tasks
-    Ok(GenerateDailyTasksResponse {
-        tasks: vec![
-            GeneratedTask {
-                title: "Morning planning".to_string(),
-                description: Some("Review today's goals".to_string()),
-                estimated_duration: Some(15),
-                priority: 2,
-                scheduled_time: Some("09:00".to_string()),
-            },
-            GeneratedTask {
-                title: "Focus work block".to_string(),
-                description: Some("Deep work session".to_string()),
-                estimated_duration: Some(45),
-                priority: 3,
-                scheduled_time: Some("10:00".to_string()),
-            },
-        ],
-        summary: format!("Generated 2 tasks for {}", date),
-    })
+    plan_rules: Option<String>,
+    request_id: Option<String>,
+) -> std::result::Result<GenerateDailyTasksResponse, LLMError> {
+    if plan_ids.is_empty() {
+        return Err(LLMError::InvalidInput("planIds must not be empty".to_string()));
+    }
+
+    let pool = db::pool(&app).await.map_err(LLMError::DatabaseError)?;
+    let plans = db::plans_by_ids(&pool, &plan_ids)
+        .await
+        .map_err(|e| LLMError::DatabaseError(e.to_string()))?;
+    let missing: Vec<&str> = plan_ids
+        .iter()
+        .filter(|id| !plans.iter().any(|p| &p.id == *id))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(LLMError::InvalidInput(format!("Plan not found: {}", missing.join(", "))));
+    }
+
+    let (title, description) = combine_plans(&plans);
+    crate::generate_daily_tasks_with_ai(app, state, title, description, date, plan_rules, None, None, request_id).await
 }
 
+/// 계획 구조(마일스톤, 반복 태스크)로 반복 일정과 날짜가 정해진 태스크를 만듦
+///
+/// `parsed_content`가 없으면 계획에 저장된 구조를 사용하고, 있으면 계획에 저장합니다.
+/// 이미 만든 반복 일정과 태스크는 다시 만들지 않습니다.
 #[tauri::command]
-pub async fn split_task(_task_id: String) -> Result<SplitTaskResponse> {
-    // TODO: Call LLM to split task
-    // For now, return sample subtasks
-    Ok(SplitTaskResponse {
-        subtasks: vec![
-            GeneratedSubTask {
-                title: "Step 1: Prepare".to_string(),
-                estimated_minutes: 5,
-            },
-            GeneratedSubTask {
-                title: "Step 2: Execute".to_string(),
-                estimated_minutes: 15,
-            },
-            GeneratedSubTask {
-                title: "Step 3: Review".to_string(),
-                estimated_minutes: 5,
-            },
-        ],
-    })
+pub async fn materialize_plan(
+    app: AppHandle,
+    plan_id: String,
+    parsed_content: Option<ParsedPlanContent>,
+) -> Result<MaterializedPlan> {
+    let pool = db::pool(&app).await?;
+    let plan = db::plans_by_ids(&pool, std::slice::from_ref(&plan_id))
+        .await
+        .map_err(|e| e.to_string())?
+        .pop()
+        .ok_or_else(|| format!("Plan not found: {}", plan_id))?;
+    let content = parsed_content
+        .or_else(|| plan.parsed_content.clone())
+        .ok_or_else(|| format!("Plan has no parsed content: {}", plan_id))?;
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    plans::materialize_plan(&pool, &plan, &content, &today, crate::load_recurring_horizon(&app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// DB에 저장된 태스크를 작은 서브태스크로 분해
+#[tauri::command]
+pub async fn split_task(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    task_id: String,
+    request_id: Option<String>,
+) -> std::result::Result<SplitTaskResponse, LLMError> {
+    let pool = db::pool(&app).await.map_err(LLMError::DatabaseError)?;
+    let task = db::task_by_id(&pool, &task_id)
+        .await
+        .map_err(|e| LLMError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| LLMError::InvalidInput(format!("Task not found: {}", task_id)))?;
+
+    crate::split_task_with_ai(app, state, task.title, request_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::TimeSlot;
+
+    fn generated(title: &str, time: Option<&str>, minutes: i32) -> GeneratedTask {
+        GeneratedTask {
+            title: title.to_string(),
+            description: None,
+            estimated_duration: Some(minutes),
+            priority: 1,
+            scheduled_time: time.map(String::from),
+        }
+    }
+
+    fn plan(title: &str, description: Option<&str>, goals: &[&str]) -> Plan {
+        Plan {
+            id: title.to_string(),
+            title: title.to_string(),
+            description: description.map(String::from),
+            original_input: None,
+            parsed_content: Some(ParsedPlanContent {
+                goals: goals.iter().map(|g| g.to_string()).collect(),
+                milestones: vec![],
+                suggested_tasks: vec![],
+            }),
+            priority: 0,
+            start_date: None,
+            end_date: None,
+            recurrence: None,
+            status: Default::default(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_combine_plans_and_describe_context() {
+        let plans = vec![
+            plan("영어", Some("토익 준비"), &["900점"]),
+            plan("운동", None, &[]),
+        ];
+        let (title, description) = combine_plans(&plans);
+        assert_eq!(title, "영어 + 운동");
+        assert_eq!(description, "- 영어: 토익 준비 (900점)\n- 운동");
+
+        let empty = LLMContext {
+            plans: None,
+            recent_tasks: Some(vec![]),
+            current_date: None,
+            schedule: None,
+            recent_stats: None,
+            estimation: None,
+        };
+        assert_eq!(empty.describe(Locale::Ko), "없음");
+
+        let context = LLMContext {
+            plans: Some(plans),
+            ..empty
+        };
+        assert!(context.describe(Locale::En).starts_with("Plans:\n- 영어: 토익 준비"));
+    }
+
+    #[test]
+    fn test_fit_generated_tasks_into_free_slots() {
+        let schedule = DaySchedule {
+            date: "2026-03-02".to_string(),
+            busy: vec![],
+            unscheduled: vec![],
+            focus_windows: vec![],
+            free_slots: vec![
+                TimeSlot { start: "08:00".to_string(), end: "08:30".to_string() },
+                TimeSlot { start: "10:00".to_string(), end: "11:00".to_string() },
+            ],
+        };
+        let mut response = GenerateDailyTasksResponse {
+            tasks: vec![
+                generated("a", Some("09:00"), 30),
+                generated("b", Some("10:15"), 30),
+                generated("c", None, 45),
+                generated("d", Some("10:00"), 15),
+            ],
+            summary: String::new(),
+        };
+
+        response.fit_to(&schedule);
+
+        let times: Vec<(&str, Option<&str>)> = response
+            .tasks
+            .iter()
+            .map(|t| (t.title.as_str(), t.scheduled_time.as_deref()))
+            .collect();
+        assert_eq!(
+            times,
+            vec![("a", Some("08:00")), ("d", Some("10:00")), ("b", Some("10:15")), ("c", None)]
+        );
+    }
+
+    #[test]
+    fn test_plan_output_keeps_milestones_and_frequency() {
+        let output: PlanParseOutput = serde_json::from_value(serde_json::json!({
+            "goals": ["Score 100 on TOEFL"],
+            "milestones": [
+                {"title": "Mock test", "targetDate": "2026-03-20", "tasks": ["take mock test ", "Unknown"]}
+            ],
+            "suggestedTasks": [
+                {"title": "Vocabulary", "priority": 2,
+                 "frequency": {"recurrenceType": "weekly", "daysOfWeek": [1, 3]}},
+                {"title": "Take mock test", "estimatedDuration": 120, "priority": 3}
+            ]
+        }))
+        .unwrap();
+        assert!(output.validate().is_ok());
+
+        let content = output.into_content();
+        assert_eq!(content.milestones[0].target_date.as_deref(), Some("2026-03-20"));
+        assert_eq!(content.milestones[0].tasks, vec!["Take mock test"]);
+        let frequency = content.suggested_tasks[0].frequency.as_ref().unwrap();
+        assert!(matches!(frequency.recurrence_type, RecurrenceType::Weekly));
+        assert_eq!((frequency.interval, frequency.days_of_week.clone()), (1, Some(vec![1, 3])));
+        assert!(content.suggested_tasks[1].frequency.is_none());
+
+        let invalid: PlanParseOutput = serde_json::from_value(serde_json::json!({
+            "goals": [],
+            "milestones": [{"title": "Mock", "targetDate": "next month"}],
+            "suggestedTasks": []
+        }))
+        .unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_capture_flags_items_for_confirmation() {
+        let output: CaptureTasksOutput = serde_json::from_value(serde_json::json!({
+            "items": [
+                {"sourceText": "tomorrow dentist at 3", "confidence": 0.95,
+                 "task": {"title": "Dentist", "scheduledDate": "2026-03-03", "scheduledTime": "15:00"}},
+                {"sourceText": "call mom", "confidence": 0.4,
+                 "task": {"title": "Call mom", "scheduledDate": "2026-03-02"}},
+                {"sourceText": "finish report by friday", "confidence": 0.8,
+                 "task": {"title": "Finish report", "scheduledDate": "2026-03-06"},
+                 "dateQuestion": "Is Friday the deadline or the day to work on it?"},
+                {"sourceText": "gym every monday", "confidence": 0.9,
+                 "recurrence": {"title": "Gym", "recurrenceType": "weekly", "intervalValue": 1, "daysOfWeek": [1]}},
+                {"sourceText": "pay rent", "confidence": 0.9, "dateQuestion": " ",
+                 "task": {"title": "Pay rent", "scheduledDate": "2026-03-01"}}
+            ]
+        }))
+        .unwrap();
+        assert!(output.validate().is_ok());
+        assert!(!crate::llm::structured::schema_for::<CaptureTasksOutput>()
+            .to_string()
+            .contains("needsConfirmation"));
+
+        let response = output.finish("2026-03-02");
+        let flags: Vec<bool> = response.items.iter().map(|i| i.needs_confirmation).collect();
+        assert_eq!(flags, vec![false, true, true, false, true]);
+        assert!(response.items[4].date_question.is_none());
+
+        let both: CaptureTasksOutput = serde_json::from_value(serde_json::json!({
+            "items": [{"sourceText": "x", "confidence": 0.9}]
+        }))
+        .unwrap();
+        assert!(both.validate().unwrap_err().contains("exactly one"));
+    }
 }
@@ -1 +1,9 @@
+pub mod agent;
+pub mod chat;
+pub mod conflicts;
+pub mod dayplan;
+pub mod estimate;
 pub mod llm;
+pub mod recurring;
+pub mod reschedule;
+pub mod review;
@@ -0,0 +1,74 @@
+use tauri::AppHandle;
+
+use crate::db;
+use crate::recurring::exceptions::{self, EditScope, OccurrenceChanges, OccurrenceEdit, RecurrenceException};
+use crate::recurring::materialize::{self, MaterializeSummary};
+use crate::recurring::RecurringPlan;
+
+type Result<T> = std::result::Result<T, String>;
+
+/// 반복 일정의 발생별 예외 (원래 날짜 순)
+#[tauri::command]
+pub async fn list_recurrence_exceptions(app: AppHandle, recurring_plan_id: String) -> Result<Vec<RecurrenceException>> {
+    let pool = db::pool(&app).await?;
+    db::recurrence_exceptions(&pool, &recurring_plan_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// `date`에 보이는 발생을 수정 ("이번만", "이후 모두", "전체")
+///
+/// 이미 생성된 태스크 중 사용자가 고치지 않은 것도 함께 바꿉니다 (전체 수정은 오늘 이후만).
+/// 날짜 이동은 "이번만"에서만 할 수 있습니다.
+#[tauri::command]
+pub async fn edit_recurring_occurrence(
+    app: AppHandle,
+    recurring_plan_id: String,
+    date: String,
+    scope: EditScope,
+    edit: OccurrenceEdit,
+) -> Result<OccurrenceChanges> {
+    let pool = db::pool(&app).await?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    exceptions::edit_occurrence(&pool, &recurring_plan_id, &date, scope, &edit, &today).await
+}
+
+/// `date`에 보이는 발생을 취소 ("이번만", "이후 모두", "전체")
+///
+/// 생성된 태스크 중 완료하지 않은 것은 지웁니다.
+#[tauri::command]
+pub async fn skip_recurring_occurrence(
+    app: AppHandle,
+    recurring_plan_id: String,
+    date: String,
+    scope: EditScope,
+) -> Result<OccurrenceChanges> {
+    let pool = db::pool(&app).await?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    exceptions::skip_occurrence(&pool, &recurring_plan_id, &date, scope, &today).await
+}
+
+/// 활성 반복 일정의 태스크를 오늘부터 설정한 기간까지 채움 (앱 시작, 날짜 변경, 기간 변경 때)
+pub(crate) async fn top_up(app: &AppHandle) -> Result<MaterializeSummary> {
+    let pool = db::pool(app).await?;
+    let today = chrono::Local::now().date_naive();
+    materialize::top_up(&pool, today, crate::load_recurring_horizon(app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 반복 일정 태스크를 지금 채움 (새 반복 일정을 만든 뒤 등)
+#[tauri::command]
+pub async fn materialize_recurring_tasks(app: AppHandle) -> Result<MaterializeSummary> {
+    top_up(&app).await
+}
+
+/// 반복 일정을 수정하거나 끈 뒤 오늘부터 이미 만든 태스크를 새 규칙에 맞춤 (사용자가 고친 태스크는 그대로)
+///
+/// `previous`는 수정하기 전 반복 일정입니다 (사용자가 지운 태스크를 다시 만들지 않도록).
+#[tauri::command]
+pub async fn refresh_recurring_plan_tasks(app: AppHandle, previous: RecurringPlan) -> Result<MaterializeSummary> {
+    let pool = db::pool(&app).await?;
+    let today = chrono::Local::now().date_naive();
+    materialize::reconcile_plan(&pool, &previous, today, crate::load_recurring_horizon(&app)).await
+}
@@ -0,0 +1,87 @@
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::agent::ProposedChange;
+use crate::commands::agent::AgentProposals;
+use crate::db;
+use crate::llm::prompts::PromptId;
+use crate::llm::{LLMError, LLMMessage, LLMRequest};
+use crate::reschedule::{self, Preferred, RescheduleOptions, RescheduleOutput, UnplacedTask};
+use crate::schedule;
+use crate::ApiKeyState;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RescheduleProposal {
+    /// `apply_agent_changes`에 넘길 id. 변경이 없으면 None
+    pub id: Option<String>,
+    pub changes: Vec<ProposedChange>,
+    pub unplaced: Vec<UnplacedTask>,
+    /// AI 제안을 반영했는지 (AI를 쓸 수 없으면 규칙만 사용)
+    pub used_ai: bool,
+}
+
+/// 밀린 태스크를 오늘부터 며칠 안으로 옮기는 변경안
+///
+/// `use_ai`면 AI가 날짜를 제안하고, 용량이나 빈 시간에 맞지 않는 제안은 규칙대로 배치합니다.
+/// 변경안은 바로 저장하지 않고 `apply_agent_changes`로 확인받습니다.
+#[tauri::command]
+pub async fn propose_reschedule(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    proposals: State<'_, AgentProposals>,
+    options: Option<RescheduleOptions>,
+    use_ai: Option<bool>,
+    request_id: Option<String>,
+) -> Result<RescheduleProposal, LLMError> {
+    let options = options.unwrap_or_default();
+    let pool = db::pool(&app).await.map_err(LLMError::DatabaseError)?;
+    let today = chrono::Local::now().date_naive();
+    let (overdue, mut planner) = reschedule::load(&pool, today, &schedule::now_rounded(), &options)
+        .await
+        .map_err(|e| LLMError::DatabaseError(e.to_string()))?;
+
+    let mut preferred = Preferred::new();
+    let mut used_ai = false;
+    if use_ai.unwrap_or(false) && !overdue.is_empty() {
+        let suggested = async {
+            let service = crate::create_llm_service(&app, &state, "reschedule").await?;
+            let prompts = crate::load_prompt_registry(&app);
+            let prompt = prompts.render(
+                PromptId::Reschedule,
+                &[
+                    ("current_date", &today.format("%Y-%m-%d").to_string()),
+                    ("overdue_tasks", &reschedule::describe_overdue(&overdue, prompts.locale())),
+                    ("available_days", &planner.describe(prompts.locale())),
+                ],
+            )?;
+            let request = LLMRequest {
+                messages: vec![LLMMessage {
+                    role: "user".to_string(),
+                    content: prompt,
+                }],
+                max_tokens: Some(2048),
+                temperature: Some(0.2),
+            };
+            crate::complete_typed_with_events::<RescheduleOutput>(&app, &service, request, request_id.as_deref())
+                .await
+        }
+        .await;
+        match suggested {
+            Ok(output) => {
+                preferred = output.into_preferred();
+                used_ai = true;
+            }
+            Err(LLMError::Cancelled) => return Err(LLMError::Cancelled),
+            Err(error) => eprintln!("AI rescheduling unavailable, using rules only: {}", error),
+        }
+    }
+
+    let result = reschedule::reschedule(&overdue, &mut planner, &preferred);
+    Ok(RescheduleProposal {
+        id: proposals.store(&result.changes),
+        changes: result.changes,
+        unplaced: result.unplaced,
+        used_ai,
+    })
+}
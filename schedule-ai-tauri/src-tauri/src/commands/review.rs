@@ -0,0 +1,100 @@
+use chrono::NaiveDate;
+use tauri::{AppHandle, State};
+
+use crate::db;
+use crate::llm::prompts::PromptId;
+use crate::llm::{LLMError, LLMMessage, LLMRequest};
+use crate::review::{self, WeeklyReview, WeeklyReviewResponse, WeeklyStats};
+use crate::ApiKeyState;
+
+type Result<T> = std::result::Result<T, String>;
+
+fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+/// "YYYY-MM-DD" 날짜, 없으면 오늘
+fn week_date(date: Option<&str>) -> std::result::Result<NaiveDate, LLMError> {
+    match date {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| LLMError::InvalidInput(format!("Invalid date: {}", date))),
+        None => Ok(today()),
+    }
+}
+
+/// `date`가 속한 주의 통계 (AI 호출 없음)
+#[tauri::command]
+pub async fn get_weekly_stats(app: AppHandle, date: Option<String>) -> Result<WeeklyStats> {
+    let date = week_date(date.as_deref()).map_err(|e| e.to_string())?;
+    let pool = db::pool(&app).await?;
+    let today = today().format("%Y-%m-%d").to_string();
+    review::collect(&pool, date, &today).await.map_err(|e| e.to_string())
+}
+
+/// `date`가 속한 주(기본: 이번 주)의 AI 회고를 만들어 저장
+///
+/// 같은 주의 회고가 이미 있으면 새 회고로 덮어씁니다.
+/// `request_id`를 넘기면 생성 중인 JSON이 `llm-delta` 이벤트로 스트리밍됩니다.
+#[tauri::command]
+pub async fn generate_weekly_review(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    date: Option<String>,
+    request_id: Option<String>,
+) -> std::result::Result<WeeklyReview, LLMError> {
+    let date = week_date(date.as_deref())?;
+    let pool = db::pool(&app).await.map_err(LLMError::DatabaseError)?;
+    let database = |e: sqlx::Error| LLMError::DatabaseError(e.to_string());
+
+    let today = today().format("%Y-%m-%d").to_string();
+    let stats = review::collect(&pool, date, &today).await.map_err(database)?;
+    if stats.is_empty() {
+        return Err(LLMError::InvalidInput(format!(
+            "No tasks or focus data for the week of {}",
+            stats.week_start
+        )));
+    }
+
+    let service = crate::create_llm_service(&app, &state, "weekly_review").await?;
+    let prompts = crate::load_prompt_registry(&app);
+    let prompt = prompts.render(
+        PromptId::WeeklyReview,
+        &[
+            ("week_start", &stats.week_start),
+            ("week_end", &stats.week_end),
+            ("weekly_stats", &stats.describe(prompts.locale())),
+        ],
+    )?;
+    let request = LLMRequest {
+        messages: vec![LLMMessage {
+            role: "user".to_string(),
+            content: prompt,
+        }],
+        max_tokens: Some(2048),
+        temperature: Some(0.5),
+    };
+
+    let response: WeeklyReviewResponse =
+        crate::complete_typed_with_events(&app, &service, request, request_id.as_deref()).await?;
+    review::save_review(&pool, &stats, &response).await.map_err(database)
+}
+
+/// 저장된 주간 회고 (최근 주 순)
+#[tauri::command]
+pub async fn list_weekly_reviews(app: AppHandle) -> Result<Vec<WeeklyReview>> {
+    let pool = db::pool(&app).await?;
+    review::list_reviews(&pool).await.map_err(|e| e.to_string())
+}
+
+/// 주 시작일(월요일)로 회고 조회
+#[tauri::command]
+pub async fn get_weekly_review(app: AppHandle, week_start: String) -> Result<Option<WeeklyReview>> {
+    let pool = db::pool(&app).await?;
+    review::get_review(&pool, &week_start).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_weekly_review(app: AppHandle, review_id: String) -> Result<bool> {
+    let pool = db::pool(&app).await?;
+    review::delete_review(&pool, &review_id).await.map_err(|e| e.to_string())
+}
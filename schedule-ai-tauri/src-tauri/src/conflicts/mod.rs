@@ -0,0 +1,337 @@
+//! 일정 충돌 감지
+//!
+//! 태스크, 아직 태스크로 생성되지 않은 반복 일정, 코어 타임을 날짜별 시간 블록으로 펼쳐서
+//! 서로 겹치는 곳을 찾습니다. 건너뛴 태스크와 시간이 없는 일정은 시간을 차지하지 않습니다.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+use crate::db;
+use crate::models::{CoreTime, Task, TaskStatus};
+use crate::recurring::{occurrence_on, Occurrence, RecurringPlan};
+use crate::schedule::{format_minutes, to_minutes, DEFAULT_DURATION};
+
+/// 한 번에 검사하는 최대 기간(일)
+pub const MAX_RANGE_DAYS: i64 = 92;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EventKind {
+    Task,
+    Recurring,
+    CoreTime,
+}
+
+/// 하루 안의 시간 블록
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleEvent {
+    pub kind: EventKind,
+    /// 태스크, 반복 일정, 코어 타임 id (검사할 새 태스크는 빈 문자열)
+    pub id: String,
+    pub title: String,
+    pub date: String,
+    pub start: String,
+    pub end: String,
+}
+
+impl ScheduleEvent {
+    fn range(&self) -> Option<(i32, i32)> {
+        Some((to_minutes(&self.start)?, to_minutes(&self.end)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictKind {
+    /// 두 일정이 같은 시간을 차지
+    Overlap,
+    /// 일정이 코어 타임(집중 시간)을 침범
+    CoreTime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Conflict {
+    pub kind: ConflictKind,
+    pub date: String,
+    pub first: ScheduleEvent,
+    pub second: ScheduleEvent,
+    /// 겹치는 시간
+    pub start: String,
+    pub end: String,
+    pub minutes: i32,
+}
+
+fn block(kind: EventKind, id: &str, title: &str, date: &str, start: i32, end: i32) -> ScheduleEvent {
+    ScheduleEvent {
+        kind,
+        id: id.to_string(),
+        title: title.to_string(),
+        date: date.to_string(),
+        start: format_minutes(start),
+        end: format_minutes(end),
+    }
+}
+
+/// 태스크의 시간 블록 (`scheduled_time`부터 예상 소요시간만큼)
+pub fn task_event(task: &Task) -> Option<ScheduleEvent> {
+    if matches!(task.status, TaskStatus::Skipped) {
+        return None;
+    }
+    let start = task.scheduled_time.as_deref().and_then(to_minutes)?;
+    let minutes = task.estimated_duration.filter(|d| *d > 0).unwrap_or(DEFAULT_DURATION);
+    Some(block(
+        EventKind::Task,
+        &task.id,
+        &task.title,
+        &task.scheduled_date,
+        start,
+        start + minutes,
+    ))
+}
+
+/// 반복 일정 발생의 시간 블록 (`end_time`이 없으면 예상 소요시간만큼)
+fn recurring_event(plan: &RecurringPlan, occurrence: &Occurrence, date: &str) -> Option<ScheduleEvent> {
+    let start = occurrence.scheduled_time.as_deref().and_then(to_minutes)?;
+    let end = occurrence
+        .end_time
+        .as_deref()
+        .and_then(to_minutes)
+        .filter(|end| *end > start)
+        .unwrap_or(start + plan.estimated_duration.filter(|d| *d > 0).unwrap_or(DEFAULT_DURATION));
+    Some(block(EventKind::Recurring, &plan.id, &occurrence.title, date, start, end))
+}
+
+/// 하루의 시간 블록
+///
+/// `recurring`은 그날 아직 태스크로 생성되지 않은 활성 반복 일정입니다.
+pub fn day_events(
+    date: &str,
+    tasks: &[Task],
+    recurring: &[RecurringPlan],
+    core_times: &[CoreTime],
+) -> Vec<ScheduleEvent> {
+    let weekday = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.weekday().num_days_from_sunday() as i32)
+        .ok();
+
+    let mut events: Vec<ScheduleEvent> = tasks
+        .iter()
+        .filter(|t| t.scheduled_date == date)
+        .filter_map(task_event)
+        .collect();
+    events.extend(
+        recurring
+            .iter()
+            .filter_map(|p| recurring_event(p, &occurrence_on(p, date)?, date)),
+    );
+    events.extend(
+        core_times
+            .iter()
+            .filter(|c| c.is_active && weekday.is_some_and(|w| c.days_of_week.contains(&w)))
+            .filter_map(|c| {
+                let (start, end) = (to_minutes(&c.start_time)?, to_minutes(&c.end_time)?);
+                (end > start).then(|| block(EventKind::CoreTime, &c.id, &c.name, date, start, end))
+            }),
+    );
+    events.sort_by(|a, b| (&a.start, &a.end).cmp(&(&b.start, &b.end)));
+    events
+}
+
+/// 두 블록이 겹치면 충돌 (코어 타임끼리는 충돌로 보지 않음)
+fn conflict(first: &ScheduleEvent, second: &ScheduleEvent) -> Option<Conflict> {
+    if first.date != second.date || (first.kind == EventKind::CoreTime && second.kind == EventKind::CoreTime) {
+        return None;
+    }
+    let (a_start, a_end) = first.range()?;
+    let (b_start, b_end) = second.range()?;
+    let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+    if start >= end {
+        return None;
+    }
+    let kind = if first.kind == EventKind::CoreTime || second.kind == EventKind::CoreTime {
+        ConflictKind::CoreTime
+    } else {
+        ConflictKind::Overlap
+    };
+    Some(Conflict {
+        kind,
+        date: first.date.clone(),
+        first: first.clone(),
+        second: second.clone(),
+        start: format_minutes(start),
+        end: format_minutes(end),
+        minutes: end - start,
+    })
+}
+
+/// 블록들 사이의 모든 충돌 (날짜, 시간 순)
+pub fn find_conflicts(events: &[ScheduleEvent]) -> Vec<Conflict> {
+    let mut conflicts: Vec<Conflict> = events
+        .iter()
+        .enumerate()
+        .flat_map(|(index, first)| {
+            events[index + 1..]
+                .iter()
+                .filter_map(move |second| conflict(first, second))
+        })
+        .collect();
+    conflicts.sort_by(|a, b| (&a.date, &a.start).cmp(&(&b.date, &b.start)));
+    conflicts
+}
+
+/// 새 블록이 기존 블록과 겹치는 곳 (`exclude` id의 블록은 제외, 예: 수정 중인 태스크 자신)
+pub fn conflicts_with(candidate: &ScheduleEvent, events: &[ScheduleEvent], exclude: Option<&str>) -> Vec<Conflict> {
+    events
+        .iter()
+        .filter(|e| !(e.kind == EventKind::Task && Some(e.id.as_str()) == exclude))
+        .filter_map(|e| conflict(candidate, e))
+        .collect()
+}
+
+/// `from`~`to` (포함) 날짜의 시간 블록
+pub async fn load_events(pool: &SqlitePool, from: NaiveDate, to: NaiveDate) -> Result<Vec<ScheduleEvent>, sqlx::Error> {
+    let (from_str, to_str) = (from.format("%Y-%m-%d").to_string(), to.format("%Y-%m-%d").to_string());
+    let tasks = db::tasks_between(pool, &from_str, &to_str).await?;
+    let generated: HashSet<(String, String)> = db::generated_recurring_between(pool, &from_str, &to_str)
+        .await?
+        .into_iter()
+        .collect();
+    let recurring = db::active_recurring_plans(pool).await?;
+    let core_times = db::active_core_times(pool).await?;
+
+    let mut events = Vec::new();
+    let mut date = from;
+    while date <= to {
+        let day = date.format("%Y-%m-%d").to_string();
+        let pending: Vec<RecurringPlan> = recurring
+            .iter()
+            .filter(|p| !generated.contains(&(p.id.clone(), day.clone())))
+            .cloned()
+            .collect();
+        events.extend(day_events(&day, &tasks, &pending, &core_times));
+        date += Duration::days(1);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recurring::{MonthDayPolicy, RecurrenceType};
+
+    const DATE: &str = "2026-03-02"; // 월요일
+
+    fn task(id: &str, time: Option<&str>, minutes: Option<i32>) -> Task {
+        Task {
+            scheduled_time: time.map(String::from),
+            estimated_duration: minutes,
+            priority: 1,
+            ..Task::test(id, DATE)
+        }
+    }
+
+    fn workshop() -> RecurringPlan {
+        RecurringPlan {
+            id: "workshop".to_string(),
+            plan_id: None,
+            title: "Workshop".to_string(),
+            description: None,
+            location: None,
+            recurrence_type: RecurrenceType::Weekly,
+            interval_value: 1,
+            days_of_week: Some(vec![1]),
+            day_of_month: None,
+            scheduled_time: Some("12:00".to_string()),
+            end_time: Some("16:00".to_string()),
+            estimated_duration: None,
+            start_date: "2026-03-01".to_string(),
+            end_date: None,
+            rrule: None,
+            month_day_policy: MonthDayPolicy::Clamp,
+            exceptions: Vec::new(),
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn core_time() -> CoreTime {
+        CoreTime {
+            id: "core".to_string(),
+            name: "Deep work".to_string(),
+            start_time: "09:00".to_string(),
+            end_time: "11:00".to_string(),
+            days_of_week: vec![1, 2, 3, 4, 5],
+            blocked_apps: None,
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let mut skipped = task("skipped", Some("14:00"), Some(30));
+        skipped.status = TaskStatus::Skipped;
+        let tasks = vec![
+            task("call", Some("10:30"), Some(60)),
+            task("review", Some("15:30"), None),
+            task("untimed", None, Some(60)),
+            skipped,
+            task("after", Some("16:00"), Some(30)),
+        ];
+
+        let events = day_events(DATE, &tasks, &[workshop()], &[core_time()]);
+        assert_eq!(events.len(), 5);
+
+        let conflicts = find_conflicts(&events);
+        let summary: Vec<(ConflictKind, &str, &str, &str, i32)> = conflicts
+            .iter()
+            .map(|c| {
+                (
+                    c.kind,
+                    c.first.id.as_str(),
+                    c.second.id.as_str(),
+                    c.start.as_str(),
+                    c.minutes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ConflictKind::CoreTime, "core", "call", "10:30", 30),
+                (ConflictKind::Overlap, "workshop", "review", "15:30", 30),
+            ]
+        );
+
+        // 화요일에는 워크숍이 없음
+        assert!(find_conflicts(&day_events("2026-03-03", &tasks, &[workshop()], &[])).is_empty());
+    }
+
+    #[test]
+    fn test_conflicts_with_candidate() {
+        let tasks = vec![task("call", Some("13:30"), Some(60))];
+        let events = day_events(DATE, &tasks, &[workshop()], &[core_time()]);
+
+        let mut candidate = task("call", Some("14:00"), Some(60));
+        let candidate_event = task_event(&candidate).unwrap();
+        let conflicts = conflicts_with(&candidate_event, &events, Some("call"));
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].second.kind, EventKind::Recurring);
+        assert_eq!(
+            (conflicts[0].start.as_str(), conflicts[0].end.as_str()),
+            ("14:00", "15:00")
+        );
+
+        candidate.scheduled_time = Some("16:00".to_string());
+        assert!(conflicts_with(&task_event(&candidate).unwrap(), &events, Some("call")).is_empty());
+        candidate.id = "other".to_string();
+        candidate.scheduled_time = Some("14:15".to_string());
+        assert_eq!(conflicts_with(&task_event(&candidate).unwrap(), &events, None).len(), 2);
+    }
+}
@@ -0,0 +1,573 @@
+//! 하루 자동 타임 블로킹
+//!
+//! 그날의 태스크를 근무 시간 안에 겹치지 않게 배치하고 순서(`order_index`)를 정합니다.
+//! 시간이 정해진 태스크와 반복 일정은 고정으로 두고, 우선순위가 높은 태스크는 에너지가 높은
+//! 시간대(사용자가 지정한 시간대와 코어 타임)에 먼저 넣습니다. 태스크 사이에는 짧은 휴식을 두고,
+//! 오래 이어서 일하게 되면 긴 휴식을 넣습니다. 같은 입력에는 항상 같은 결과를 냅니다.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::agent::{Change, ProposedChange};
+use crate::models::{CoreTime, Task, TaskStatus, UpdateTaskInput};
+use crate::recurring::RecurringPlan;
+use crate::schedule::{self, format_minutes, to_minutes, TimeSlot, DEFAULT_DURATION};
+
+/// 에너지가 높은 시간대
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnergyWindow {
+    pub start: String,
+    pub end: String,
+    /// 0=일요일. 비어 있으면 매일
+    #[serde(default)]
+    pub days_of_week: Vec<i32>,
+}
+
+/// 자동 배치 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlannerPreferences {
+    pub work_start: String,
+    pub work_end: String,
+    /// 태스크 사이 휴식(분)
+    pub break_minutes: i32,
+    /// 이보다 오래 이어서 일하지 않음(분)
+    pub max_focus_minutes: i32,
+    /// 오래 일한 뒤 휴식(분)
+    pub long_break_minutes: i32,
+    pub peak_windows: Vec<EnergyWindow>,
+    /// 이 우선순위 이상이면 에너지가 높은 시간대에 먼저 배치
+    pub peak_min_priority: i32,
+    /// 코어 타임도 에너지가 높은 시간대로 취급
+    pub core_times_as_peak: bool,
+    /// 시간이 정해진 할 일은 옮기지 않음
+    pub keep_scheduled_times: bool,
+}
+
+impl Default for PlannerPreferences {
+    fn default() -> Self {
+        Self {
+            work_start: "09:00".to_string(),
+            work_end: "18:00".to_string(),
+            break_minutes: 10,
+            max_focus_minutes: 90,
+            long_break_minutes: 20,
+            peak_windows: vec![EnergyWindow {
+                start: "09:00".to_string(),
+                end: "12:00".to_string(),
+                days_of_week: Vec::new(),
+            }],
+            peak_min_priority: 2,
+            core_times_as_peak: true,
+            keep_scheduled_times: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockKind {
+    /// 시간이 정해져 있던 태스크
+    Fixed,
+    Recurring,
+    /// 이번에 배치한 태스크
+    Planned,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineBlock {
+    pub task_id: Option<String>,
+    pub title: String,
+    pub start: String,
+    pub end: String,
+    pub kind: BlockKind,
+    /// 에너지가 높은 시간대 안인지
+    pub peak: bool,
+}
+
+/// 태스크에 정할 시간과 순서
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Assignment {
+    pub task_id: String,
+    pub scheduled_time: Option<String>,
+    pub order_index: i32,
+}
+
+/// 근무 시간 안에 넣지 못한 태스크
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnplacedTask {
+    pub task_id: String,
+    pub title: String,
+    pub minutes: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayPlan {
+    pub date: String,
+    /// 시간 순 일정
+    pub timeline: Vec<TimelineBlock>,
+    pub assignments: Vec<Assignment>,
+    pub unplaced: Vec<UnplacedTask>,
+}
+
+fn duration(task: &Task) -> i32 {
+    task.estimated_duration.filter(|d| *d > 0).unwrap_or(DEFAULT_DURATION)
+}
+
+fn range(start: &str, end: &str) -> Option<(i32, i32)> {
+    Some((to_minutes(start)?, to_minutes(end)?)).filter(|(s, e)| e > s)
+}
+
+/// 차지한 시간과 휴식 규칙
+struct Board {
+    blocks: Vec<(i32, i32)>,
+    break_minutes: i32,
+    max_focus: i32,
+    long_break: i32,
+}
+
+impl Board {
+    fn occupy(&mut self, start: i32, end: i32) {
+        self.blocks.push((start, end));
+        self.blocks.sort();
+    }
+
+    /// `window` 안에서 다른 일정과 휴식 시간만큼 떨어져 있고 연속 작업 한도를 넘지 않는지
+    fn fits(&self, start: i32, minutes: i32, window: (i32, i32)) -> bool {
+        let end = start + minutes;
+        start >= window.0
+            && end <= window.1
+            && self
+                .blocks
+                .iter()
+                .all(|&(s, e)| end + self.break_minutes <= s || e + self.break_minutes <= start)
+            && self.focus_ok(start, end)
+    }
+
+    /// 긴 휴식 없이 이어지는 일정의 작업 시간 합이 한도 이내인지 (한 일정만으로 넘는 건 허용)
+    fn focus_ok(&self, start: i32, end: i32) -> bool {
+        let mut chain = vec![(start, end)];
+        chain.extend(self.blocks.iter().copied());
+        chain.sort();
+        let index = chain.iter().position(|b| *b == (start, end)).unwrap_or(0);
+
+        let mut first = index;
+        while first > 0 && chain[first].0 - end_of(&chain[..first]) < self.long_break {
+            first -= 1;
+        }
+        let mut last = index;
+        while last + 1 < chain.len() && chain[last + 1].0 - end_of(&chain[..=last]) < self.long_break {
+            last += 1;
+        }
+        let linked = &chain[first..=last];
+        linked.len() == 1 || linked.iter().map(|(s, e)| e - s).sum::<i32>() <= self.max_focus
+    }
+
+    /// `windows` 중 들어갈 수 있는 가장 이른 시작 시간
+    ///
+    /// 가장 이른 시작은 항상 시간대 시작이나 어떤 일정이 끝난 뒤 휴식만큼 지난 시각입니다.
+    fn earliest(&self, minutes: i32, windows: &[(i32, i32)]) -> Option<i32> {
+        let mut candidates: Vec<i32> = windows.iter().map(|w| w.0).collect();
+        for &(_, end) in &self.blocks {
+            candidates.push(end + self.break_minutes);
+            candidates.push(end + self.long_break);
+        }
+        candidates.sort();
+        candidates.dedup();
+        candidates
+            .into_iter()
+            .find(|&start| windows.iter().any(|w| self.fits(start, minutes, *w)))
+    }
+}
+
+/// 앞쪽 일정들 중 가장 늦게 끝나는 시각
+fn end_of(chain: &[(i32, i32)]) -> i32 {
+    chain.iter().map(|(_, e)| *e).max().unwrap_or(i32::MIN)
+}
+
+/// 그날의 에너지가 높은 시간대 (근무 시간으로 자름)
+fn peak_windows(
+    date: &str,
+    preferences: &PlannerPreferences,
+    core_times: &[CoreTime],
+    work: (i32, i32),
+) -> Vec<(i32, i32)> {
+    let weekday = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.weekday().num_days_from_sunday() as i32)
+        .ok();
+    let on_day = |days: &[i32]| days.is_empty() || weekday.is_some_and(|w| days.contains(&w));
+
+    let declared = preferences
+        .peak_windows
+        .iter()
+        .filter(|w| on_day(&w.days_of_week))
+        .filter_map(|w| range(&w.start, &w.end));
+    let core = core_times
+        .iter()
+        .filter(|c| preferences.core_times_as_peak && c.is_active && !c.days_of_week.is_empty())
+        .filter(|c| on_day(&c.days_of_week))
+        .filter_map(|c| range(&c.start_time, &c.end_time));
+
+    let mut windows: Vec<(i32, i32)> = declared
+        .chain(core)
+        .map(|(s, e)| (s.max(work.0), e.min(work.1)))
+        .filter(|(s, e)| e > s)
+        .collect();
+    windows.sort();
+    windows
+}
+
+/// 하루 일정 배치
+///
+/// `recurring`은 그날 아직 태스크로 생성되지 않은 활성 반복 일정입니다. `not_before`를 넘기면
+/// (오늘을 계획할 때 현재 시각) 그보다 이른 시간에는 새로 배치하지 않습니다.
+pub fn plan_day(
+    date: &str,
+    tasks: &[Task],
+    recurring: &[RecurringPlan],
+    core_times: &[CoreTime],
+    preferences: &PlannerPreferences,
+    not_before: Option<&str>,
+) -> DayPlan {
+    let work_start = to_minutes(&preferences.work_start).unwrap_or(9 * 60);
+    let work_end = to_minutes(&preferences.work_end).unwrap_or(18 * 60).max(work_start);
+    let earliest = not_before.and_then(to_minutes).unwrap_or(work_start).max(work_start);
+    let work = (earliest, work_end);
+
+    let mut board = Board {
+        blocks: Vec::new(),
+        break_minutes: preferences.break_minutes.max(0),
+        max_focus: preferences.max_focus_minutes.max(DEFAULT_DURATION),
+        long_break: preferences.long_break_minutes.max(preferences.break_minutes).max(0),
+    };
+    let peaks = peak_windows(date, preferences, core_times, work);
+    let in_peak = |start: i32, end: i32| peaks.iter().any(|(s, e)| *s <= start && end <= *e);
+    let mut timeline: Vec<(i32, i32, TimelineBlock)> = Vec::new();
+
+    // 반복 일정 (종일 창으로 계산해 근무 시간 밖도 고정으로 둠)
+    let all_day = TimeSlot {
+        start: "00:00".to_string(),
+        end: "23:59".to_string(),
+    };
+    let day = schedule::build_day_schedule(date, &[], recurring, &[], &all_day);
+    for busy in &day.busy {
+        if let Some((start, end)) = range(&busy.start, &busy.end) {
+            board.occupy(start, end);
+            timeline.push((
+                start,
+                end,
+                TimelineBlock {
+                    task_id: None,
+                    title: busy.title.clone(),
+                    start: busy.start.clone(),
+                    end: busy.end.clone(),
+                    kind: BlockKind::Recurring,
+                    peak: in_peak(start, end),
+                },
+            ));
+        }
+    }
+
+    let day_tasks: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.scheduled_date == date && !matches!(t.status, TaskStatus::Skipped))
+        .collect();
+
+    // 시간이 정해진 태스크는 고정. 완료했거나 진행 중인 태스크는 항상 그대로
+    let mut movable = Vec::new();
+    let mut done_untimed = Vec::new();
+    for task in &day_tasks {
+        let time = task.scheduled_time.as_deref().and_then(to_minutes);
+        let settled = matches!(task.status, TaskStatus::Completed | TaskStatus::InProgress);
+        match time {
+            Some(start) if settled || preferences.keep_scheduled_times => {
+                let end = start + duration(task);
+                board.occupy(start, end);
+                timeline.push((
+                    start,
+                    end,
+                    TimelineBlock {
+                        task_id: Some(task.id.clone()),
+                        title: task.title.clone(),
+                        start: format_minutes(start),
+                        end: format_minutes(end),
+                        kind: BlockKind::Fixed,
+                        peak: in_peak(start, end),
+                    },
+                ));
+            }
+            _ if settled => done_untimed.push(*task),
+            _ => movable.push(*task),
+        }
+    }
+
+    movable.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.order_index.cmp(&b.order_index))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    // 1) 우선순위가 높은 태스크를 에너지가 높은 시간대에, 2) 나머지를 가장 이른 빈 시간에
+    let mut placed: Vec<Option<i32>> = vec![None; movable.len()];
+    for (index, task) in movable.iter().enumerate() {
+        if task.priority >= preferences.peak_min_priority {
+            placed[index] = board.earliest(duration(task), &peaks);
+            if let Some(start) = placed[index] {
+                board.occupy(start, start + duration(task));
+            }
+        }
+    }
+    let mut unplaced = Vec::new();
+    for (index, task) in movable.iter().enumerate() {
+        if placed[index].is_none() {
+            placed[index] = board.earliest(duration(task), &[work]);
+            match placed[index] {
+                Some(start) => board.occupy(start, start + duration(task)),
+                None => unplaced.push(*task),
+            }
+        }
+    }
+    for (task, start) in movable.iter().zip(&placed) {
+        if let Some(start) = *start {
+            let end = start + duration(task);
+            timeline.push((
+                start,
+                end,
+                TimelineBlock {
+                    task_id: Some(task.id.clone()),
+                    title: task.title.clone(),
+                    start: format_minutes(start),
+                    end: format_minutes(end),
+                    kind: BlockKind::Planned,
+                    peak: in_peak(start, end),
+                },
+            ));
+        }
+    }
+    timeline.sort_by_key(|(start, end, _)| (*start, *end));
+
+    // 순서: 시간 순, 넣지 못한 할 일, 시간 없이 끝낸 태스크
+    let mut assignments: Vec<Assignment> = timeline
+        .iter()
+        .filter_map(|(_, _, block)| {
+            Some(Assignment {
+                task_id: block.task_id.clone()?,
+                scheduled_time: Some(block.start.clone()),
+                order_index: 0,
+            })
+        })
+        .collect();
+    assignments.extend(unplaced.iter().chain(&done_untimed).map(|task| Assignment {
+        task_id: task.id.clone(),
+        scheduled_time: task.scheduled_time.clone().filter(|_| preferences.keep_scheduled_times),
+        order_index: 0,
+    }));
+    for (index, assignment) in assignments.iter_mut().enumerate() {
+        assignment.order_index = index as i32;
+    }
+
+    DayPlan {
+        date: date.to_string(),
+        timeline: timeline.into_iter().map(|(_, _, block)| block).collect(),
+        assignments,
+        unplaced: unplaced
+            .into_iter()
+            .map(|task| UnplacedTask {
+                task_id: task.id.clone(),
+                title: task.title.clone(),
+                minutes: duration(task),
+            })
+            .collect(),
+    }
+}
+
+impl DayPlan {
+    /// 시간이나 순서가 바뀌는 태스크의 변경안
+    pub fn changes(&self, tasks: &[Task]) -> Vec<ProposedChange> {
+        self.assignments
+            .iter()
+            .filter_map(|assignment| {
+                let task = tasks.iter().find(|t| t.id == assignment.task_id)?;
+                let time_changed = task.scheduled_time != assignment.scheduled_time;
+                if !time_changed && task.order_index == assignment.order_index {
+                    return None;
+                }
+                let summary = match (&assignment.scheduled_time, time_changed) {
+                    (Some(time), true) => format!("Schedule \"{}\" at {}", task.title, time),
+                    _ => format!("Reorder \"{}\"", task.title),
+                };
+                Some(ProposedChange {
+                    summary,
+                    change: Change::UpdateTask {
+                        task_id: task.id.clone(),
+                        update: UpdateTaskInput {
+                            title: None,
+                            description: None,
+                            location: None,
+                            scheduled_date: None,
+                            scheduled_time: assignment.scheduled_time.clone().filter(|_| time_changed),
+                            estimated_duration: None,
+                            actual_duration: None,
+                            priority: None,
+                            status: None,
+                            order_index: Some(assignment.order_index),
+                        },
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recurring::{MonthDayPolicy, RecurrenceType};
+
+    const DATE: &str = "2026-03-02"; // 월요일
+
+    fn task(id: &str, minutes: i32, priority: i32, time: Option<&str>) -> Task {
+        Task {
+            scheduled_time: time.map(String::from),
+            estimated_duration: Some(minutes),
+            priority,
+            ..Task::test(id, DATE)
+        }
+    }
+
+    fn preferences() -> PlannerPreferences {
+        PlannerPreferences {
+            work_start: "09:00".to_string(),
+            work_end: "13:00".to_string(),
+            break_minutes: 10,
+            max_focus_minutes: 90,
+            long_break_minutes: 30,
+            peak_windows: vec![EnergyWindow {
+                start: "10:00".to_string(),
+                end: "12:00".to_string(),
+                days_of_week: vec![1, 2, 3, 4, 5],
+            }],
+            ..PlannerPreferences::default()
+        }
+    }
+
+    fn starts(plan: &DayPlan) -> Vec<(String, String, BlockKind)> {
+        plan.timeline
+            .iter()
+            .map(|b| (b.title.clone(), b.start.clone(), b.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_day_peak_windows_and_breaks() {
+        let standup = RecurringPlan {
+            id: "r".to_string(),
+            plan_id: None,
+            title: "standup".to_string(),
+            description: None,
+            location: None,
+            recurrence_type: RecurrenceType::Daily,
+            interval_value: 1,
+            days_of_week: None,
+            day_of_month: None,
+            scheduled_time: Some("09:00".to_string()),
+            end_time: Some("09:15".to_string()),
+            estimated_duration: None,
+            start_date: "2026-03-01".to_string(),
+            end_date: None,
+            rrule: None,
+            month_day_policy: MonthDayPolicy::Clamp,
+            exceptions: Vec::new(),
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let mut skipped = task("skipped", 30, 3, None);
+        skipped.status = TaskStatus::Skipped;
+        let tasks = vec![
+            task("email", 20, 0, None),
+            task("report", 60, 3, None),
+            task("review", 30, 2, None),
+            task("meeting", 30, 1, Some("12:30")),
+            skipped,
+            task("huge", 600, 1, None),
+        ];
+        let prefs = PlannerPreferences {
+            long_break_minutes: 15,
+            ..preferences()
+        };
+
+        let plan = plan_day(DATE, &tasks, std::slice::from_ref(&standup), &[], &prefs, None);
+        let expected = |items: &[(&str, &str, BlockKind)]| {
+            items
+                .iter()
+                .map(|(title, start, kind)| (title.to_string(), start.to_string(), *kind))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            starts(&plan),
+            expected(&[
+                ("standup", "09:00", BlockKind::Recurring),
+                ("email", "09:25", BlockKind::Planned),
+                ("report", "10:00", BlockKind::Planned),
+                ("review", "11:10", BlockKind::Planned),
+                ("meeting", "12:30", BlockKind::Fixed),
+            ])
+        );
+        assert!(plan.timeline[2].peak && plan.timeline[3].peak && !plan.timeline[1].peak);
+        assert_eq!(plan.unplaced.len(), 1);
+        assert_eq!(plan.unplaced[0].task_id, "huge");
+        let order: Vec<&str> = plan.assignments.iter().map(|a| a.task_id.as_str()).collect();
+        assert_eq!(order, vec!["email", "report", "review", "meeting", "huge"]);
+
+        // 연속 작업 한도를 줄이면 report 뒤에 긴 휴식을 둠
+        let prefs = PlannerPreferences {
+            max_focus_minutes: 80,
+            ..prefs
+        };
+        let plan = plan_day(DATE, &tasks, &[standup], &[], &prefs, None);
+        assert_eq!(plan.timeline[3].title, "review");
+        assert_eq!(plan.timeline[3].start, "11:15");
+    }
+
+    #[test]
+    fn test_plan_day_is_conflict_free_and_deterministic() {
+        let tasks: Vec<Task> = (0..12)
+            .map(|i| task(&format!("t{:02}", i), 15 + (i * 7) % 50, i % 4, None))
+            .chain([task("fixed", 30, 1, Some("11:00"))])
+            .collect();
+        let prefs = PlannerPreferences {
+            work_end: "17:00".to_string(),
+            ..preferences()
+        };
+
+        let plan = plan_day(DATE, &tasks, &[], &[], &prefs, Some("09:20"));
+        let again = plan_day(DATE, &tasks, &[], &[], &prefs, Some("09:20"));
+        assert_eq!(plan.assignments, again.assignments);
+
+        let ranges: Vec<(i32, i32)> = plan
+            .timeline
+            .iter()
+            .map(|b| (to_minutes(&b.start).unwrap(), to_minutes(&b.end).unwrap()))
+            .collect();
+        for pair in ranges.windows(2) {
+            assert!(pair[0].1 + prefs.break_minutes <= pair[1].0, "{:?}", pair);
+        }
+        assert!(ranges.iter().all(|(s, e)| *s >= 9 * 60 + 20 && *e <= 17 * 60));
+        assert_eq!(plan.timeline.iter().find(|b| b.title == "fixed").unwrap().start, "11:00");
+        assert_eq!(plan.timeline.len() + plan.unplaced.len(), tasks.len());
+
+        // 순서는 시간 순이고 바뀐 태스크만 변경안에 들어감
+        let orders: Vec<i32> = plan.assignments.iter().map(|a| a.order_index).collect();
+        assert_eq!(orders, (0..tasks.len() as i32).collect::<Vec<_>>());
+        let changes = plan.changes(&tasks);
+        assert!(changes.iter().all(|c| !c.summary.contains("\"fixed\" at")));
+        assert!(changes.iter().any(|c| c.summary.starts_with("Schedule \"t03\" at ")));
+    }
+}
@@ -0,0 +1,19 @@
+-- LLM 호출 사용량 기록
+CREATE TABLE IF NOT EXISTS llm_usage (
+    id TEXT PRIMARY KEY NOT NULL,
+    operation TEXT NOT NULL,        -- "split_task", "parse_plan", ...
+    provider TEXT NOT NULL,         -- "claude", "openai", "ollama"
+    model TEXT NOT NULL,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cost_usd REAL,                  -- 가격을 모르는 모델은 NULL
+    latency_ms INTEGER NOT NULL,
+    success INTEGER NOT NULL,
+    error_kind TEXT,                -- 실패 시 LLMErrorKind
+    usage_date TEXT NOT NULL,       -- 로컬 날짜 "YYYY-MM-DD"
+    created_at TEXT NOT NULL
+);
+
+-- 인덱스
+CREATE INDEX IF NOT EXISTS idx_llm_usage_usage_date ON llm_usage(usage_date);
+CREATE INDEX IF NOT EXISTS idx_llm_usage_operation ON llm_usage(operation);
@@ -0,0 +1,13 @@
+-- 주간 회고 (주마다 하나, 다시 생성하면 덮어씀)
+CREATE TABLE IF NOT EXISTS weekly_reviews (
+    id TEXT PRIMARY KEY NOT NULL,
+    week_start TEXT NOT NULL UNIQUE,
+    week_end TEXT NOT NULL,
+    stats TEXT NOT NULL,
+    review TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- 인덱스
+CREATE INDEX IF NOT EXISTS idx_weekly_reviews_week_start ON weekly_reviews(week_start);
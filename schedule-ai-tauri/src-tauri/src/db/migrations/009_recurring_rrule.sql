@@ -0,0 +1,53 @@
+-- 반복 일정에 RRULE 저장, 'yearly' 허용
+-- SQLite는 CHECK 제약을 바꿀 수 없어서 테이블을 다시 만듭니다.
+-- recurring_plans를 지우면 generated_tasks가 CASCADE로 지워지므로 먼저 복사해 둡니다.
+
+CREATE TEMP TABLE generated_tasks_backup AS SELECT * FROM generated_tasks;
+
+CREATE TABLE recurring_plans_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    plan_id TEXT REFERENCES plans(id) ON DELETE CASCADE,
+
+    -- 기본 정보
+    title TEXT NOT NULL,
+    description TEXT,
+    location TEXT,                  -- 장소
+
+    -- 반복 패턴
+    recurrence_type TEXT NOT NULL CHECK(recurrence_type IN ('daily', 'weekly', 'monthly', 'yearly')),
+    interval_value INTEGER DEFAULT 1,
+    days_of_week TEXT,              -- JSON array: [0,1,2,3,4,5,6] (일~토)
+    day_of_month INTEGER,           -- 월간 반복 시 날짜 (1-31)
+    rrule TEXT,                     -- RFC 5545 RRULE (있으면 위 필드보다 우선)
+
+    -- 시간 정보
+    scheduled_time TEXT,            -- "HH:MM"
+    end_time TEXT,                  -- "HH:MM" (optional)
+    estimated_duration INTEGER,     -- minutes
+
+    -- 기간
+    start_date TEXT NOT NULL,
+    end_date TEXT,                  -- null = 무기한 (1년치만 생성)
+
+    -- 상태
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+INSERT INTO recurring_plans_new (id, plan_id, title, description, location, recurrence_type, interval_value,
+    days_of_week, day_of_month, scheduled_time, end_time, estimated_duration, start_date, end_date, is_active,
+    created_at, updated_at)
+SELECT id, plan_id, title, description, location, recurrence_type, interval_value,
+    days_of_week, day_of_month, scheduled_time, end_time, estimated_duration, start_date, end_date, is_active,
+    created_at, updated_at
+FROM recurring_plans;
+
+DROP TABLE recurring_plans;
+ALTER TABLE recurring_plans_new RENAME TO recurring_plans;
+
+INSERT OR IGNORE INTO generated_tasks SELECT * FROM generated_tasks_backup;
+DROP TABLE generated_tasks_backup;
+
+CREATE INDEX IF NOT EXISTS idx_recurring_plans_plan_id ON recurring_plans(plan_id);
+CREATE INDEX IF NOT EXISTS idx_recurring_plans_active ON recurring_plans(is_active);
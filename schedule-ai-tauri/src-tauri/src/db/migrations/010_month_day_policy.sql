@@ -0,0 +1,3 @@
+-- 그 날짜가 없는 달(31일, 2월 29일 등)의 처리: 'skip' = 건너뜀, 'clamp' = 그 달의 말일
+ALTER TABLE recurring_plans ADD COLUMN month_day_policy TEXT NOT NULL DEFAULT 'clamp'
+    CHECK(month_day_policy IN ('skip', 'clamp'));
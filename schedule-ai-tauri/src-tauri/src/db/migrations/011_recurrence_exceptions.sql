@@ -0,0 +1,21 @@
+-- 반복 일정의 발생별 예외 (EXDATE, 발생 하나만 옮기거나 바꾸기)
+CREATE TABLE IF NOT EXISTS recurrence_exceptions (
+    id TEXT PRIMARY KEY NOT NULL,
+    recurring_plan_id TEXT NOT NULL REFERENCES recurring_plans(id) ON DELETE CASCADE,
+    original_date TEXT NOT NULL,    -- 규칙상 날짜 (RECURRENCE-ID)
+    kind TEXT NOT NULL CHECK(kind IN ('skip', 'override')),
+
+    -- override일 때 바꾼 값 (NULL = 반복 일정 값 그대로)
+    date TEXT,                      -- 옮긴 날짜
+    scheduled_time TEXT,            -- "HH:MM"
+    end_time TEXT,                  -- "HH:MM"
+    title TEXT,
+    location TEXT,
+
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+
+    UNIQUE(recurring_plan_id, original_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_recurrence_exceptions_recurring_plan_id ON recurrence_exceptions(recurring_plan_id);
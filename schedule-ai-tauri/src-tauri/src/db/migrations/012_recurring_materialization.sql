@@ -0,0 +1,7 @@
+-- 반복 일정 태스크를 어디까지 미리 만들었는지 (롤링 윈도우, YYYY-MM-DD)
+ALTER TABLE recurring_plans ADD COLUMN materialized_until TEXT;
+
+-- 예전처럼 한 번에 1년치를 만든 반복 일정은 마지막으로 만든 날짜까지 만든 것으로 봄
+UPDATE recurring_plans SET materialized_until = (
+    SELECT MAX(scheduled_date) FROM generated_tasks WHERE generated_tasks.recurring_plan_id = recurring_plans.id
+);
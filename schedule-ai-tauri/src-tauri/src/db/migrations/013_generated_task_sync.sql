@@ -0,0 +1,5 @@
+-- 반복 일정이 태스크를 마지막으로 맞춘 시각. 태스크의 updated_at과 같으면 사용자가 고치지 않은 태스크
+ALTER TABLE generated_tasks ADD COLUMN synced_at TEXT;
+
+-- 기존 태스크는 만든 뒤 바뀌지 않았으면 그대로인 것으로 봄
+UPDATE generated_tasks SET synced_at = (SELECT created_at FROM tasks WHERE tasks.id = generated_tasks.task_id);
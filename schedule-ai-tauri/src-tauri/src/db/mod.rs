@@ -0,0 +1,725 @@
+//! Rust 쪽 SQLite 접근
+//!
+//! 프론트엔드와 같은 DB(`sqlite:schedule.db`)를 tauri-plugin-sql이 preload한 pool로 사용합니다.
+
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_sql::{DbInstances, DbPool};
+
+use crate::models::{generate_id, now_iso, CoreTime, Plan, PlanStatus, Task, TaskStatus};
+use crate::recurring::exceptions::{ExceptionKind, RecurrenceException};
+use crate::recurring::{MonthDayPolicy, RecurrenceType, RecurringPlan};
+
+pub const DB_URL: &str = "sqlite:schedule.db";
+
+/// tauri-plugin-sql이 연 SQLite pool
+pub async fn pool(app: &AppHandle) -> Result<SqlitePool, String> {
+    let instances = app
+        .try_state::<DbInstances>()
+        .ok_or("Database plugin not initialized")?;
+    let instances = instances.0.read().await;
+    match instances.get(DB_URL) {
+        Some(DbPool::Sqlite(pool)) => Ok(pool.clone()),
+        _ => Err(format!("Database not loaded: {}", DB_URL)),
+    }
+}
+
+const TASK_COLUMNS: &str = "id, plan_id, title, description, location, scheduled_date, scheduled_time, \
+     estimated_duration, actual_duration, priority, status, order_index, created_at, updated_at, completed_at";
+
+fn parse_status(status: &str) -> TaskStatus {
+    match status {
+        "in_progress" => TaskStatus::InProgress,
+        "completed" => TaskStatus::Completed,
+        "skipped" => TaskStatus::Skipped,
+        _ => TaskStatus::Pending,
+    }
+}
+
+/// JSON 배열 컬럼 (`[1,3,5]`)
+fn parse_json_list<T: serde::de::DeserializeOwned>(value: Option<String>) -> Option<Vec<T>> {
+    value.and_then(|v| serde_json::from_str(&v).ok())
+}
+
+fn task_from_row(row: &SqliteRow) -> Task {
+    let status: Option<String> = row.get("status");
+    Task {
+        id: row.get("id"),
+        plan_id: row.get("plan_id"),
+        title: row.get("title"),
+        description: row.get("description"),
+        location: row.get("location"),
+        scheduled_date: row.get("scheduled_date"),
+        scheduled_time: row.get("scheduled_time"),
+        estimated_duration: row.get("estimated_duration"),
+        actual_duration: row.get("actual_duration"),
+        priority: row.get::<Option<i32>, _>("priority").unwrap_or(0),
+        status: parse_status(status.as_deref().unwrap_or("pending")),
+        order_index: row.get::<Option<i32>, _>("order_index").unwrap_or(0),
+        subtasks: None,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        completed_at: row.get("completed_at"),
+    }
+}
+
+/// `from`~`to` (포함) 날짜의 태스크
+pub async fn tasks_between(pool: &SqlitePool, from: &str, to: &str) -> Result<Vec<Task>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM tasks WHERE scheduled_date BETWEEN ? AND ? \
+         ORDER BY scheduled_date, scheduled_time, order_index",
+        TASK_COLUMNS
+    ))
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().map(task_from_row).collect())
+}
+
+/// `before` 이전 날짜에 끝내지 못한 태스크 (`include_skipped`면 건너뛴 태스크 포함)
+pub async fn unfinished_tasks_before(
+    pool: &SqlitePool,
+    before: &str,
+    include_skipped: bool,
+) -> Result<Vec<Task>, sqlx::Error> {
+    let statuses = if include_skipped {
+        "'pending', 'in_progress', 'skipped'"
+    } else {
+        "'pending', 'in_progress'"
+    };
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM tasks WHERE scheduled_date < ? AND status IN ({}) \
+         ORDER BY scheduled_date, scheduled_time, order_index",
+        TASK_COLUMNS, statuses
+    ))
+    .bind(before)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().map(task_from_row).collect())
+}
+
+/// `since` 이후 예상/실제 소요시간이 모두 있는 완료 태스크
+pub async fn completed_tasks_with_durations(pool: &SqlitePool, since: &str) -> Result<Vec<Task>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM tasks WHERE status = 'completed' AND scheduled_date >= ? \
+         AND estimated_duration > 0 AND actual_duration > 0",
+        TASK_COLUMNS
+    ))
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().map(task_from_row).collect())
+}
+
+/// id로 태스크 조회
+pub async fn task_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Task>, sqlx::Error> {
+    let row = sqlx::query(&format!("SELECT {} FROM tasks WHERE id = ?", TASK_COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.as_ref().map(task_from_row))
+}
+
+const PLAN_COLUMNS: &str = "id, title, description, original_input, parsed_content, priority, start_date, \
+     end_date, recurrence, status, created_at, updated_at";
+
+fn plan_from_row(row: &SqliteRow) -> Plan {
+    let status = match row.get::<Option<String>, _>("status").as_deref() {
+        Some("paused") => PlanStatus::Paused,
+        Some("completed") => PlanStatus::Completed,
+        Some("archived") => PlanStatus::Archived,
+        _ => PlanStatus::Active,
+    };
+    let json = |column: &str| row.get::<Option<String>, _>(column);
+    Plan {
+        id: row.get("id"),
+        title: row.get("title"),
+        description: row.get("description"),
+        original_input: row.get("original_input"),
+        parsed_content: json("parsed_content").and_then(|v| serde_json::from_str(&v).ok()),
+        priority: row.get::<Option<i32>, _>("priority").unwrap_or(0),
+        start_date: row.get("start_date"),
+        end_date: row.get("end_date"),
+        recurrence: json("recurrence").and_then(|v| serde_json::from_str(&v).ok()),
+        status,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+/// id 목록의 계획 (없는 id는 건너뜀, 요청 순서 유지)
+pub async fn plans_by_ids(pool: &SqlitePool, ids: &[String]) -> Result<Vec<Plan>, sqlx::Error> {
+    let mut plans = Vec::with_capacity(ids.len());
+    for id in ids {
+        let row = sqlx::query(&format!("SELECT {} FROM plans WHERE id = ?", PLAN_COLUMNS))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+        plans.extend(row.as_ref().map(plan_from_row));
+    }
+    Ok(plans)
+}
+
+/// 진행 중인 계획 (우선순위 순)
+pub async fn active_plans(pool: &SqlitePool) -> Result<Vec<Plan>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM plans WHERE status = 'active' ORDER BY priority DESC, created_at",
+        PLAN_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().map(plan_from_row).collect())
+}
+
+const RECURRING_PLAN_COLUMNS: &str = "id, plan_id, title, description, location, recurrence_type, interval_value, \
+     days_of_week, day_of_month, scheduled_time, end_time, estimated_duration, start_date, end_date, rrule, \
+     month_day_policy, is_active, created_at, updated_at";
+
+fn recurring_plan_from_row(row: &SqliteRow) -> RecurringPlan {
+    let recurrence_type = match row.get::<String, _>("recurrence_type").as_str() {
+        "weekly" => RecurrenceType::Weekly,
+        "monthly" => RecurrenceType::Monthly,
+        "yearly" => RecurrenceType::Yearly,
+        _ => RecurrenceType::Daily,
+    };
+    RecurringPlan {
+        id: row.get("id"),
+        plan_id: row.get("plan_id"),
+        title: row.get("title"),
+        description: row.get("description"),
+        location: row.get("location"),
+        recurrence_type,
+        interval_value: row.get::<Option<i32>, _>("interval_value").unwrap_or(1),
+        days_of_week: parse_json_list(row.get("days_of_week")),
+        day_of_month: row.get("day_of_month"),
+        scheduled_time: row.get("scheduled_time"),
+        end_time: row.get("end_time"),
+        estimated_duration: row.get("estimated_duration"),
+        start_date: row.get("start_date"),
+        end_date: row.get("end_date"),
+        rrule: row.get("rrule"),
+        month_day_policy: match row.get::<String, _>("month_day_policy").as_str() {
+            "skip" => MonthDayPolicy::Skip,
+            _ => MonthDayPolicy::Clamp,
+        },
+        exceptions: Vec::new(),
+        is_active: row.get::<Option<bool>, _>("is_active").unwrap_or(true),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+/// 활성화된 반복 일정 (예외 포함)
+pub async fn active_recurring_plans(pool: &SqlitePool) -> Result<Vec<RecurringPlan>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM recurring_plans WHERE is_active = 1",
+        RECURRING_PLAN_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await?;
+    let mut plans: Vec<RecurringPlan> = rows.iter().map(recurring_plan_from_row).collect();
+
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM recurrence_exceptions WHERE recurring_plan_id IN \
+         (SELECT id FROM recurring_plans WHERE is_active = 1) ORDER BY original_date",
+        EXCEPTION_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await?;
+    for exception in rows.iter().map(exception_from_row) {
+        if let Some(plan) = plans.iter_mut().find(|p| p.id == exception.recurring_plan_id) {
+            plan.exceptions.push(exception);
+        }
+    }
+    Ok(plans)
+}
+
+/// id로 반복 일정 조회 (비활성 포함, 예외 포함)
+pub async fn recurring_plan_by_id(pool: &SqlitePool, id: &str) -> Result<Option<RecurringPlan>, sqlx::Error> {
+    let row = sqlx::query(&format!("SELECT {} FROM recurring_plans WHERE id = ?", RECURRING_PLAN_COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    let Some(mut plan) = row.as_ref().map(recurring_plan_from_row) else {
+        return Ok(None);
+    };
+    plan.exceptions = recurrence_exceptions(pool, id).await?;
+    Ok(Some(plan))
+}
+
+const EXCEPTION_COLUMNS: &str = "id, recurring_plan_id, original_date, kind, date, scheduled_time, end_time, \
+     title, location, created_at, updated_at";
+
+fn exception_from_row(row: &SqliteRow) -> RecurrenceException {
+    RecurrenceException {
+        id: row.get("id"),
+        recurring_plan_id: row.get("recurring_plan_id"),
+        original_date: row.get("original_date"),
+        kind: match row.get::<String, _>("kind").as_str() {
+            "skip" => ExceptionKind::Skip,
+            _ => ExceptionKind::Override,
+        },
+        date: row.get("date"),
+        scheduled_time: row.get("scheduled_time"),
+        end_time: row.get("end_time"),
+        title: row.get("title"),
+        location: row.get("location"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+/// 반복 일정의 예외 (원래 날짜 순)
+pub async fn recurrence_exceptions(
+    pool: &SqlitePool,
+    recurring_plan_id: &str,
+) -> Result<Vec<RecurrenceException>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM recurrence_exceptions WHERE recurring_plan_id = ? ORDER BY original_date",
+        EXCEPTION_COLUMNS
+    ))
+    .bind(recurring_plan_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().map(exception_from_row).collect())
+}
+
+/// 해당 날짜에 이미 태스크로 생성된 반복 일정 id
+pub async fn generated_recurring_ids(pool: &SqlitePool, date: &str) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT recurring_plan_id FROM generated_tasks WHERE scheduled_date = ?")
+        .bind(date)
+        .fetch_all(pool)
+        .await
+}
+
+/// `from`~`to` (포함) 날짜에 이미 태스크로 생성된 (반복 일정 id, 날짜)
+pub async fn generated_recurring_between(
+    pool: &SqlitePool,
+    from: &str,
+    to: &str,
+) -> Result<Vec<(String, String)>, sqlx::Error> {
+    sqlx::query_as("SELECT recurring_plan_id, scheduled_date FROM generated_tasks WHERE scheduled_date BETWEEN ? AND ?")
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await
+}
+
+/// 활성화된 코어 타임
+pub async fn active_core_times(pool: &SqlitePool) -> Result<Vec<CoreTime>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, name, start_time, end_time, days_of_week, blocked_apps, created_at, updated_at \
+         FROM core_times WHERE is_active = 1 ORDER BY start_time",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| CoreTime {
+            id: row.get("id"),
+            name: row.get("name"),
+            start_time: row.get("start_time"),
+            end_time: row.get("end_time"),
+            days_of_week: parse_json_list(row.get("days_of_week")).unwrap_or_default(),
+            blocked_apps: parse_json_list(row.get("blocked_apps")),
+            is_active: true,
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+        .collect())
+}
+
+/// 태스크 저장
+pub async fn insert_task(conn: &mut SqliteConnection, task: &Task) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO tasks (id, plan_id, title, description, location, scheduled_date, scheduled_time, \
+         estimated_duration, actual_duration, priority, status, order_index, created_at, updated_at, completed_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&task.id)
+    .bind(&task.plan_id)
+    .bind(&task.title)
+    .bind(&task.description)
+    .bind(&task.location)
+    .bind(&task.scheduled_date)
+    .bind(&task.scheduled_time)
+    .bind(task.estimated_duration)
+    .bind(task.actual_duration)
+    .bind(task.priority)
+    .bind(task.status.to_string())
+    .bind(task.order_index)
+    .bind(&task.created_at)
+    .bind(&task.updated_at)
+    .bind(&task.completed_at)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// 반복 일정 저장
+pub async fn insert_recurring_plan(conn: &mut SqliteConnection, plan: &RecurringPlan) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO recurring_plans (id, plan_id, title, description, location, recurrence_type, interval_value, \
+         days_of_week, day_of_month, scheduled_time, end_time, estimated_duration, start_date, end_date, rrule, \
+         month_day_policy, is_active, created_at, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&plan.id)
+    .bind(&plan.plan_id)
+    .bind(&plan.title)
+    .bind(&plan.description)
+    .bind(&plan.location)
+    .bind(plan.recurrence_type.as_str())
+    .bind(plan.interval_value)
+    .bind(plan.days_of_week.as_ref().map(|days| serde_json::json!(days).to_string()))
+    .bind(plan.day_of_month)
+    .bind(&plan.scheduled_time)
+    .bind(&plan.end_time)
+    .bind(plan.estimated_duration)
+    .bind(&plan.start_date)
+    .bind(&plan.end_date)
+    .bind(&plan.rrule)
+    .bind(plan.month_day_policy.as_str())
+    .bind(plan.is_active)
+    .bind(&plan.created_at)
+    .bind(&plan.updated_at)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// 반복 일정 수정 (예외는 따로 저장)
+pub async fn update_recurring_plan(conn: &mut SqliteConnection, plan: &RecurringPlan) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE recurring_plans SET plan_id = ?, title = ?, description = ?, location = ?, recurrence_type = ?, \
+         interval_value = ?, days_of_week = ?, day_of_month = ?, scheduled_time = ?, end_time = ?, \
+         estimated_duration = ?, start_date = ?, end_date = ?, rrule = ?, month_day_policy = ?, is_active = ?, \
+         updated_at = ? WHERE id = ?",
+    )
+    .bind(&plan.plan_id)
+    .bind(&plan.title)
+    .bind(&plan.description)
+    .bind(&plan.location)
+    .bind(plan.recurrence_type.as_str())
+    .bind(plan.interval_value)
+    .bind(plan.days_of_week.as_ref().map(|days| serde_json::json!(days).to_string()))
+    .bind(plan.day_of_month)
+    .bind(&plan.scheduled_time)
+    .bind(&plan.end_time)
+    .bind(plan.estimated_duration)
+    .bind(&plan.start_date)
+    .bind(&plan.end_date)
+    .bind(&plan.rrule)
+    .bind(plan.month_day_policy.as_str())
+    .bind(plan.is_active)
+    .bind(&plan.updated_at)
+    .bind(&plan.id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// 발생 예외 저장 (같은 원래 날짜의 예외는 덮어씀)
+pub async fn upsert_recurrence_exception(
+    conn: &mut SqliteConnection,
+    exception: &RecurrenceException,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO recurrence_exceptions (id, recurring_plan_id, original_date, kind, date, scheduled_time, \
+         end_time, title, location, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(recurring_plan_id, original_date) DO UPDATE SET kind = excluded.kind, date = excluded.date, \
+           scheduled_time = excluded.scheduled_time, end_time = excluded.end_time, title = excluded.title, \
+           location = excluded.location, updated_at = excluded.updated_at",
+    )
+    .bind(&exception.id)
+    .bind(&exception.recurring_plan_id)
+    .bind(&exception.original_date)
+    .bind(exception.kind.as_str())
+    .bind(&exception.date)
+    .bind(&exception.scheduled_time)
+    .bind(&exception.end_time)
+    .bind(&exception.title)
+    .bind(&exception.location)
+    .bind(&exception.created_at)
+    .bind(&exception.updated_at)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// 반복 일정에서 생성한 태스크 기록 (같은 날짜는 한 번만)
+///
+/// `synced_at`은 태스크의 `updated_at`. 이후 태스크가 바뀌면 사용자가 고친 것으로 봅니다.
+pub async fn record_generated_task(
+    conn: &mut SqliteConnection,
+    recurring_plan_id: &str,
+    task_id: &str,
+    date: &str,
+    synced_at: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO generated_tasks (id, recurring_plan_id, task_id, scheduled_date, created_at, synced_at) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(generate_id())
+    .bind(recurring_plan_id)
+    .bind(task_id)
+    .bind(date)
+    .bind(now_iso())
+    .bind(synced_at)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// 반복 일정이 생성한 태스크
+#[derive(Debug, Clone)]
+pub struct GeneratedTask {
+    /// `generated_tasks`의 발생 날짜 (사용자가 태스크를 옮겼으면 태스크 날짜와 다름)
+    pub occurrence_date: String,
+    /// 반복 일정이 태스크를 마지막으로 맞춘 시각
+    pub synced_at: Option<String>,
+    pub task: Task,
+}
+
+impl GeneratedTask {
+    /// 시작하지 않았고 반복 일정이 맞춘 뒤 사용자가 고치지 않은 태스크
+    pub fn is_untouched(&self) -> bool {
+        matches!(self.task.status, TaskStatus::Pending) && self.synced_at.as_deref() == Some(self.task.updated_at.as_str())
+    }
+}
+
+/// 반복 일정이 `from` 이후 날짜에 생성한 태스크 (발생 날짜 순)
+pub async fn generated_tasks_from(
+    conn: &mut SqliteConnection,
+    recurring_plan_id: &str,
+    from: &str,
+) -> Result<Vec<GeneratedTask>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {}, g.occurrence_date, g.synced_at FROM tasks JOIN \
+           (SELECT task_id, scheduled_date AS occurrence_date, synced_at FROM generated_tasks \
+            WHERE recurring_plan_id = ? AND scheduled_date >= ?) g ON g.task_id = tasks.id \
+         ORDER BY g.occurrence_date",
+        TASK_COLUMNS
+    ))
+    .bind(recurring_plan_id)
+    .bind(from)
+    .fetch_all(conn)
+    .await?;
+    Ok(rows
+        .iter()
+        .map(|row| GeneratedTask {
+            occurrence_date: row.get("occurrence_date"),
+            synced_at: row.get("synced_at"),
+            task: task_from_row(row),
+        })
+        .collect())
+}
+
+/// 태스크가 사용자가 고치지 않은 상태였으면 `synced_at`을 새 `updated_at`으로 옮김 (태스크를 바꾸기 전에 호출)
+pub async fn resync_generated_task(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    updated_at: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE generated_tasks SET synced_at = ? \
+         WHERE task_id = ? AND synced_at = (SELECT updated_at FROM tasks WHERE id = ?)",
+    )
+    .bind(updated_at)
+    .bind(task_id)
+    .bind(task_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// 반복 일정이 `from`~`to` (포함) 날짜에 이미 생성한 날짜
+pub async fn generated_dates(
+    conn: &mut SqliteConnection,
+    recurring_plan_id: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT scheduled_date FROM generated_tasks WHERE recurring_plan_id = ? AND scheduled_date BETWEEN ? AND ?",
+    )
+    .bind(recurring_plan_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(conn)
+    .await
+}
+
+/// 반복 일정 태스크를 미리 만든 마지막 날짜
+pub async fn materialized_until(
+    conn: &mut SqliteConnection,
+    recurring_plan_id: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let until: Option<Option<String>> =
+        sqlx::query_scalar("SELECT materialized_until FROM recurring_plans WHERE id = ?")
+            .bind(recurring_plan_id)
+            .fetch_optional(conn)
+            .await?;
+    Ok(until.flatten())
+}
+
+pub async fn set_materialized_until(
+    conn: &mut SqliteConnection,
+    recurring_plan_id: &str,
+    date: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE recurring_plans SET materialized_until = ? WHERE id = ?")
+        .bind(date)
+        .bind(recurring_plan_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// 날짜의 `daily_progress` 캐시를 태스크 기준으로 다시 계산
+pub async fn refresh_daily_progress(conn: &mut SqliteConnection, date: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO daily_progress (date, total_tasks, completed_tasks, skipped_tasks, total_estimated_minutes, \
+           total_actual_minutes, completion_rate, streak_count, updated_at) \
+         SELECT ?1, COUNT(*), \
+           COALESCE(SUM(status = 'completed'), 0), COALESCE(SUM(status = 'skipped'), 0), \
+           COALESCE(SUM(estimated_duration), 0), COALESCE(SUM(actual_duration), 0), \
+           CASE WHEN COUNT(*) - COALESCE(SUM(status = 'skipped'), 0) > 0 \
+             THEN CAST(COALESCE(SUM(status = 'completed'), 0) AS REAL) / (COUNT(*) - COALESCE(SUM(status = 'skipped'), 0)) \
+             ELSE 0 END, \
+           0, ?2 \
+         FROM tasks WHERE scheduled_date = ?1 \
+         ON CONFLICT(date) DO UPDATE SET total_tasks = excluded.total_tasks, \
+           completed_tasks = excluded.completed_tasks, skipped_tasks = excluded.skipped_tasks, \
+           total_estimated_minutes = excluded.total_estimated_minutes, \
+           total_actual_minutes = excluded.total_actual_minutes, \
+           completion_rate = excluded.completion_rate, updated_at = excluded.updated_at",
+    )
+    .bind(date)
+    .bind(now_iso())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// 모든 migration을 적용한 in-memory DB (테스트용)
+#[cfg(test)]
+pub async fn test_pool() -> SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    for migration in crate::get_migrations() {
+        sqlx::raw_sql(migration.sql).execute(&pool).await.unwrap();
+    }
+    pool
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_schedule_rows() {
+        let pool = test_pool().await;
+        sqlx::raw_sql(
+            "INSERT INTO tasks (id, title, scheduled_date, scheduled_time, estimated_duration, status, created_at, updated_at) VALUES
+                ('a', 'A', '2026-03-02', '09:00', 30, 'completed', '', ''),
+                ('b', 'B', '2026-03-05', NULL, NULL, 'pending', '', '');
+             INSERT INTO recurring_plans (id, title, recurrence_type, days_of_week, scheduled_time, start_date, is_active, created_at, updated_at) VALUES
+                ('r1', '수업', 'weekly', '[1,3]', '13:00', '2026-03-01', 1, '', ''),
+                ('r2', '중단', 'daily', NULL, NULL, '2026-03-01', 0, '', '');
+             INSERT INTO core_times (id, name, start_time, end_time, days_of_week, created_at, updated_at) VALUES
+                ('c', 'Deep work', '10:00', '12:00', '[1,2,3,4,5]', '', '');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let tasks = tasks_between(&pool, "2026-03-01", "2026-03-03").await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert!(matches!(tasks[0].status, TaskStatus::Completed));
+
+        let plans = active_recurring_plans(&pool).await.unwrap();
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0].recurrence_type, RecurrenceType::Weekly);
+        assert_eq!(plans[0].days_of_week, Some(vec![1, 3]));
+
+        let core_times = active_core_times(&pool).await.unwrap();
+        assert_eq!(core_times[0].days_of_week, vec![1, 2, 3, 4, 5]);
+        assert!(generated_recurring_ids(&pool, "2026-03-02").await.unwrap().is_empty());
+
+        assert_eq!(task_by_id(&pool, "b").await.unwrap().unwrap().title, "B");
+        assert!(task_by_id(&pool, "x").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_plans_by_ids() {
+        let pool = test_pool().await;
+        sqlx::raw_sql(
+            r#"INSERT INTO plans (id, title, parsed_content, status, created_at, updated_at) VALUES
+                ('p1', '영어', '{"goals":["토익 900"],"milestones":[],"suggestedTasks":[]}', 'paused', '', ''),
+                ('p2', '운동', 'not json', 'active', '', '');"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let ids = ["p2".to_string(), "missing".to_string(), "p1".to_string()];
+        let plans = plans_by_ids(&pool, &ids).await.unwrap();
+        assert_eq!(plans.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(), vec!["p2", "p1"]);
+        assert!(plans[0].parsed_content.is_none());
+        assert!(matches!(plans[1].status, PlanStatus::Paused));
+        assert_eq!(plans[1].parsed_content.as_ref().unwrap().goals, vec!["토익 900"]);
+
+        let active = active_plans(&pool).await.unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, "p2");
+    }
+
+    #[tokio::test]
+    async fn test_rrule_migration_keeps_generated_tasks() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let migrations = crate::get_migrations();
+        for migration in migrations.iter().filter(|m| m.version < 9) {
+            sqlx::raw_sql(migration.sql).execute(&pool).await.unwrap();
+        }
+        sqlx::raw_sql(
+            "INSERT INTO tasks (id, title, scheduled_date, status, created_at, updated_at) VALUES
+                ('t', '수업', '2026-03-02', 'pending', '', '');
+             INSERT INTO recurring_plans (id, title, recurrence_type, days_of_week, start_date, is_active, created_at, updated_at) VALUES
+                ('r1', '수업', 'weekly', '[1]', '2026-03-01', 1, '', '');
+             INSERT INTO generated_tasks (id, recurring_plan_id, task_id, scheduled_date, created_at) VALUES
+                ('g', 'r1', 't', '2026-03-02', '');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for migration in migrations.iter().filter(|m| m.version >= 9) {
+            sqlx::raw_sql(migration.sql).execute(&pool).await.unwrap();
+        }
+
+        assert_eq!(generated_recurring_ids(&pool, "2026-03-02").await.unwrap(), vec!["r1"]);
+
+        let mut plan = active_recurring_plans(&pool).await.unwrap().remove(0);
+        assert_eq!(plan.rrule, None);
+        plan.id = "r2".to_string();
+        plan.recurrence_type = RecurrenceType::Yearly;
+        plan.rrule = Some("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH".to_string());
+        insert_recurring_plan(&mut pool.acquire().await.unwrap(), &plan).await.unwrap();
+
+        let plans = active_recurring_plans(&pool).await.unwrap();
+        let stored = plans.iter().find(|p| p.id == "r2").unwrap();
+        assert_eq!(stored.recurrence_type, RecurrenceType::Yearly);
+        assert_eq!(stored.rrule, plan.rrule);
+    }
+}
@@ -0,0 +1,348 @@
+//! 소요시간 예측
+//!
+//! 완료한 태스크의 예상/실제 소요시간 차이로 보정 계수를 학습합니다 (전체, 계획별, 제목 키워드별).
+//! 비율은 로그 공간에서 평균을 내고, 표본이 적은 그룹은 상위 그룹(키워드 → 계획 → 전체) 쪽으로 당겨서 씁니다.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+use crate::db;
+use crate::llm::prompts::Locale;
+use crate::models::{Task, TaskStatus};
+use crate::schedule::DEFAULT_DURATION;
+
+/// 학습에 쓰는 기간(일)
+pub const HISTORY_DAYS: i64 = 180;
+/// 그룹 평균을 상위 그룹 쪽으로 당기는 가상 표본 수
+const PRIOR_WEIGHT: f64 = 3.0;
+/// 표본이 없을 때의 로그 표준편차 (약 ±40%)
+const DEFAULT_SPREAD: f64 = 0.35;
+/// prompt에 넣는 키워드 보정 최대 개수
+const SUMMARY_KEYWORD_LIMIT: usize = 5;
+/// prompt에 넣을 키워드의 최소 표본 수
+const SUMMARY_MIN_SAMPLES: f64 = 3.0;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "at", "for", "from", "in", "of", "on", "the", "to", "with", "my",
+];
+
+/// 제목의 키워드 (소문자, 2글자 이상, 중복 제거)
+pub fn keywords(title: &str) -> Vec<String> {
+    let mut words: Vec<String> = title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 2 && !w.chars().all(|c| c.is_ascii_digit()))
+        .filter(|w| !STOP_WORDS.contains(w))
+        .map(String::from)
+        .collect();
+    words.sort();
+    words.dedup();
+    words
+}
+
+/// 로그 비율(실제/예상)과 로그 실제 시간의 합
+#[derive(Debug, Clone, Copy, Default)]
+struct Group {
+    count: f64,
+    ratio: f64,
+    ratio_sq: f64,
+    actual: f64,
+}
+
+impl Group {
+    fn add(&mut self, estimated: i32, actual: i32) {
+        let ratio = (actual as f64 / estimated as f64).ln();
+        self.count += 1.0;
+        self.ratio += ratio;
+        self.ratio_sq += ratio * ratio;
+        self.actual += (actual as f64).ln();
+    }
+
+    /// 여러 그룹의 평균 (한 태스크가 여러 키워드에 겹쳐 세어지지 않도록)
+    fn average(groups: &[&Group]) -> Group {
+        let k = groups.len().max(1) as f64;
+        groups.iter().fold(Group::default(), |sum, g| Group {
+            count: sum.count + g.count / k,
+            ratio: sum.ratio + g.ratio / k,
+            ratio_sq: sum.ratio_sq + g.ratio_sq / k,
+            actual: sum.actual + g.actual / k,
+        })
+    }
+}
+
+/// 상위 그룹 값을 `PRIOR_WEIGHT`개 표본으로 보고 섞은 값
+#[derive(Debug, Clone, Copy)]
+struct Blend {
+    ratio: f64,
+    variance: f64,
+    actual: f64,
+}
+
+impl Blend {
+    /// 기록이 없을 때: 보정 없음, 기본 소요시간
+    fn prior() -> Blend {
+        Blend {
+            ratio: 0.0,
+            variance: DEFAULT_SPREAD * DEFAULT_SPREAD,
+            actual: (DEFAULT_DURATION as f64).ln(),
+        }
+    }
+
+    fn with(self, group: &Group) -> Blend {
+        if group.count == 0.0 {
+            return self;
+        }
+        let n = group.count;
+        let weight = n + PRIOR_WEIGHT;
+        let mean = group.ratio / n;
+        let variance = (group.ratio_sq / n - mean * mean).max(0.0);
+        Blend {
+            ratio: (group.ratio + PRIOR_WEIGHT * self.ratio) / weight,
+            variance: (n * variance + PRIOR_WEIGHT * self.variance) / weight,
+            actual: (group.actual + PRIOR_WEIGHT * self.actual) / weight,
+        }
+    }
+}
+
+/// 예측에 가장 구체적으로 쓰인 기록
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Basis {
+    Keyword,
+    Plan,
+    User,
+    /// 완료 기록 없음
+    Default,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Prediction {
+    pub predicted_minutes: i32,
+    /// 예측 범위 (약 68%)
+    pub low_minutes: i32,
+    pub high_minutes: i32,
+    /// 실제 / 예상 보정 계수
+    pub correction_factor: f64,
+    /// 예측에 쓴 가장 구체적인 그룹의 완료 태스크 수
+    pub sample_count: i32,
+    /// 0-1
+    pub confidence: f64,
+    pub basis: Basis,
+}
+
+/// 그룹의 보정 계수
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Correction {
+    pub keyword: String,
+    pub factor: f64,
+    pub sample_count: i32,
+}
+
+/// prompt와 화면에 쓰는 보정 요약
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimationSummary {
+    /// 전체 보정 계수 (1.4면 예상보다 40% 더 걸림)
+    pub factor: f64,
+    pub sample_count: i32,
+    /// 전체와 많이 다른 키워드
+    pub keywords: Vec<Correction>,
+}
+
+/// 5분 단위 반올림 (최소 5분)
+fn round_minutes(minutes: f64) -> i32 {
+    ((minutes / 5.0).round() as i32 * 5).max(5)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Estimator {
+    user: Group,
+    plans: HashMap<String, Group>,
+    keywords: HashMap<String, Group>,
+}
+
+impl Estimator {
+    /// 예상/실제 시간이 모두 있는 완료 태스크로 학습
+    pub fn learn(tasks: &[Task]) -> Self {
+        let mut estimator = Self::default();
+        for task in tasks.iter().filter(|t| matches!(t.status, TaskStatus::Completed)) {
+            let (Some(estimated), Some(actual)) = (task.estimated_duration, task.actual_duration) else {
+                continue;
+            };
+            if estimated <= 0 || actual <= 0 {
+                continue;
+            }
+            estimator.user.add(estimated, actual);
+            if let Some(plan_id) = &task.plan_id {
+                estimator.plans.entry(plan_id.clone()).or_default().add(estimated, actual);
+            }
+            for keyword in keywords(&task.title) {
+                estimator.keywords.entry(keyword).or_default().add(estimated, actual);
+            }
+        }
+        estimator
+    }
+
+    pub fn sample_count(&self) -> i32 {
+        self.user.count as i32
+    }
+
+    /// 제목(과 계획, 사용자가 적은 예상 시간)으로 소요시간 예측
+    ///
+    /// `estimate`가 있으면 보정 계수를 곱하고, 없으면 비슷한 태스크의 실제 시간으로 예측합니다.
+    pub fn predict(&self, title: &str, plan_id: Option<&str>, estimate: Option<i32>) -> Prediction {
+        let plan = plan_id.and_then(|id| self.plans.get(id)).copied().unwrap_or_default();
+        let matched: Vec<&Group> = keywords(title).iter().filter_map(|k| self.keywords.get(k)).collect();
+        let matched = Group::average(&matched);
+
+        let blend = Blend::prior().with(&self.user).with(&plan).with(&matched);
+        let (basis, samples) = [
+            (Basis::Keyword, matched.count),
+            (Basis::Plan, plan.count),
+            (Basis::User, self.user.count),
+        ]
+        .into_iter()
+        .find(|(_, count)| *count > 0.0)
+        .unwrap_or((Basis::Default, 0.0));
+
+        let factor = blend.ratio.exp();
+        let log_predicted = match estimate.filter(|e| *e > 0) {
+            Some(estimate) => (estimate as f64).ln() + blend.ratio,
+            None if basis == Basis::Default => (DEFAULT_DURATION as f64).ln(),
+            None => blend.actual,
+        };
+        let spread = blend.variance.sqrt();
+
+        Prediction {
+            predicted_minutes: round_minutes(log_predicted.exp()),
+            low_minutes: round_minutes((log_predicted - spread).exp()),
+            high_minutes: round_minutes((log_predicted + spread).exp()),
+            correction_factor: (factor * 100.0).round() / 100.0,
+            sample_count: samples as i32,
+            confidence: (samples / (samples + PRIOR_WEIGHT) * 100.0).round() / 100.0,
+            basis,
+        }
+    }
+
+    /// 전체 보정 계수와 전체와 20% 이상 다른 키워드
+    pub fn summary(&self) -> EstimationSummary {
+        let user = Blend::prior().with(&self.user);
+        let mut keywords: Vec<Correction> = self
+            .keywords
+            .iter()
+            .filter(|(_, group)| group.count >= SUMMARY_MIN_SAMPLES)
+            .map(|(keyword, group)| Correction {
+                keyword: keyword.clone(),
+                factor: (user.with(group).ratio.exp() * 10.0).round() / 10.0,
+                sample_count: group.count as i32,
+            })
+            .filter(|c| (c.factor / user.ratio.exp() - 1.0).abs() >= 0.2)
+            .collect();
+        keywords.sort_by(|a, b| b.sample_count.cmp(&a.sample_count).then_with(|| a.keyword.cmp(&b.keyword)));
+        keywords.truncate(SUMMARY_KEYWORD_LIMIT);
+
+        EstimationSummary {
+            factor: (user.ratio.exp() * 10.0).round() / 10.0,
+            sample_count: self.sample_count(),
+            keywords,
+        }
+    }
+}
+
+impl EstimationSummary {
+    /// prompt용 보정 안내
+    pub fn describe(&self, locale: Locale) -> String {
+        if self.sample_count == 0 {
+            return locale.none_label().to_string();
+        }
+        let mut lines = vec![match locale {
+            Locale::En => format!(
+                "- Tasks usually take {:.1}x the estimated time ({} completed tasks)",
+                self.factor, self.sample_count
+            ),
+            Locale::Ko => format!(
+                "- 태스크는 보통 예상 시간의 {:.1}배가 걸림 (완료 태스크 {}개 기준)",
+                self.factor, self.sample_count
+            ),
+        }];
+        for correction in &self.keywords {
+            lines.push(match locale {
+                Locale::En => format!("- \"{}\" tasks take {:.1}x the estimate", correction.keyword, correction.factor),
+                Locale::Ko => format!("- \"{}\" 태스크는 예상의 {:.1}배", correction.keyword, correction.factor),
+            });
+        }
+        lines.push(match locale {
+            Locale::En => "- Apply these factors to estimatedDuration".to_string(),
+            Locale::Ko => "- estimatedDuration에 이 배율을 반영하세요".to_string(),
+        });
+        lines.join("\n")
+    }
+}
+
+/// `today`까지 `HISTORY_DAYS`일 동안의 완료 태스크로 학습
+pub async fn load(pool: &SqlitePool, today: NaiveDate) -> Result<Estimator, sqlx::Error> {
+    let since = (today - Duration::days(HISTORY_DAYS)).format("%Y-%m-%d").to_string();
+    let tasks = db::completed_tasks_with_durations(pool, &since).await?;
+    Ok(Estimator::learn(&tasks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn done(title: &str, plan_id: Option<&str>, estimated: i32, actual: i32) -> Task {
+        Task {
+            plan_id: plan_id.map(String::from),
+            estimated_duration: Some(estimated),
+            actual_duration: Some(actual),
+            priority: 1,
+            status: TaskStatus::Completed,
+            ..Task::test(title, "2026-03-01")
+        }
+    }
+
+    #[test]
+    fn test_keywords() {
+        assert_eq!(keywords("Write the weekly report (v2)"), vec!["report", "v2", "weekly", "write"]);
+        assert_eq!(keywords("영어 단어 외우기 30"), vec!["단어", "영어", "외우기"]);
+    }
+
+    #[test]
+    fn test_predict_learns_underestimation() {
+        let mut tasks: Vec<Task> = (0..10).map(|i| done(&format!("Email batch {}", i), None, 30, 42)).collect();
+        tasks.extend((0..6).map(|_| done("Write report", Some("work"), 60, 120)));
+        // 완료하지 않은 태스크는 학습하지 않음
+        let mut pending = done("Write report", Some("work"), 60, 600);
+        pending.status = TaskStatus::Pending;
+        tasks.push(pending);
+        let estimator = Estimator::learn(&tasks);
+
+        let email = estimator.predict("Email batch", None, Some(30));
+        assert_eq!(email.basis, Basis::Keyword);
+        assert_eq!(email.sample_count, 10);
+        assert_eq!(email.predicted_minutes, 45);
+        assert!(email.low_minutes <= email.predicted_minutes && email.predicted_minutes <= email.high_minutes);
+
+        // 보고서는 키워드와 계획 모두 2배 가까이 걸림
+        let report = estimator.predict("Quarterly report", Some("work"), Some(60));
+        assert!(report.correction_factor > 1.6, "{:?}", report);
+        assert_eq!((report.basis, report.sample_count), (Basis::Keyword, 6));
+        assert!(report.confidence < email.confidence);
+
+        // 예상 시간이 없으면 비슷한 태스크의 실제 시간
+        let unknown_estimate = estimator.predict("Email", None, None);
+        assert_eq!(unknown_estimate.predicted_minutes, 45);
+
+        let fresh = Estimator::default().predict("Anything", None, None);
+        assert_eq!((fresh.basis, fresh.predicted_minutes, fresh.confidence), (Basis::Default, 30, 0.0));
+
+        let summary = estimator.summary();
+        assert_eq!(summary.sample_count, 16);
+        assert!(summary.factor >= 1.5);
+        assert!(summary.describe(Locale::En).contains("completed tasks"));
+    }
+}
@@ -1,12 +1,24 @@
+mod agent;
+mod chat;
 mod commands;
+mod conflicts;
+mod dayplan;
+mod db;
+mod estimate;
 mod export;
 mod focus;
 mod import;
 mod ipc_server;
 mod llm;
 mod models;
+mod parser;
+mod plans;
 mod progress;
 mod recurring;
+mod reschedule;
+mod review;
+mod schedule;
+mod secrets;
 
 use std::sync::{Arc, Mutex};
 use tauri::{
@@ -20,7 +32,9 @@ use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut,
 use tauri_plugin_sql::{Migration, MigrationKind};
 use tauri_plugin_store::StoreExt;
 
-use llm::{ClaudeProvider, LLMProvider, LLMRequest, LLMMessage};
+use llm::stream::StreamRegistry;
+use llm::prompts::{Locale, PromptId, PromptOverrides, PromptRegistry};
+use llm::{LLMError, LLMRequest, LLMMessage, LLMService, StructuredOutput};
 
 fn get_migrations() -> Vec<Migration> {
     vec![
@@ -54,11 +68,62 @@ fn get_migrations() -> Vec<Migration> {
             sql: include_str!("db/migrations/005_focus_block_stats.sql"),
             kind: MigrationKind::Up,
         },
+        Migration {
+            version: 6,
+            description: "add llm usage ledger table",
+            sql: include_str!("db/migrations/006_llm_usage.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 7,
+            description: "add chat sessions tables",
+            sql: include_str!("db/migrations/007_chat_sessions.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 8,
+            description: "add weekly reviews table",
+            sql: include_str!("db/migrations/008_weekly_reviews.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 9,
+            description: "add rrule to recurring plans",
+            sql: include_str!("db/migrations/009_recurring_rrule.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 10,
+            description: "add month day policy to recurring plans",
+            sql: include_str!("db/migrations/010_month_day_policy.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 11,
+            description: "create recurrence exceptions table",
+            sql: include_str!("db/migrations/011_recurrence_exceptions.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 12,
+            description: "track recurring task materialization window",
+            sql: include_str!("db/migrations/012_recurring_materialization.sql"),
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 13,
+            description: "track generated task sync",
+            sql: include_str!("db/migrations/013_generated_task_sync.sql"),
+            kind: MigrationKind::Up,
+        },
     ]
 }
 
 struct CurrentShortcut(Mutex<Shortcut>);
 struct ApiKeyState(Mutex<Option<String>>);
+/// 메모리에 올려둔 provider API 키 (settings.json에는 저장하지 않음)
+struct ProviderKeyState(Mutex<Option<String>>);
+struct SecretsState(Arc<secrets::Secrets>);
 struct IpcState(Arc<IpcServerState>);
 
 fn toggle_window(app: &AppHandle) {
@@ -205,34 +270,299 @@ fn set_shortcut(app: AppHandle, state: State<CurrentShortcut>, shortcut_str: Str
     Ok(shortcut_str)
 }
 
-// API Key management commands
-#[tauri::command]
-fn get_api_key(app: AppHandle, state: State<ApiKeyState>) -> String {
-    // First check memory state
-    if let Some(key) = state.0.lock().unwrap().clone() {
-        return key;
+/// 메모리에 올려둔 Claude API 키 (시작할 때 secrets에서 로드)
+fn load_api_key(state: &ApiKeyState) -> Option<String> {
+    state.0.lock().unwrap().clone()
+}
+
+/// secrets 저장소를 열고 키를 메모리에 로드
+fn setup_secrets(app: &AppHandle) -> Arc<secrets::Secrets> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| std::env::temp_dir().join("schedule-ai"));
+    let secrets = secrets::Secrets::open(&data_dir);
+    load_secrets(app, &secrets);
+    Arc::new(secrets)
+}
+
+/// 예전 버전이 store에 평문으로 저장한 키를 옮긴 뒤 키를 메모리에 로드
+///
+/// 저장소가 잠겨 있으면 평문 키를 그대로 두고 이번 실행에서만 사용합니다 (잠금 해제 후 다시 호출).
+fn load_secrets(app: &AppHandle, secrets: &secrets::Secrets) {
+    let state = app.state::<ApiKeyState>();
+
+    if let Ok(store) = app.store("settings.json") {
+        let plain = store.get("api_key").and_then(|value| value.as_str().map(str::to_string));
+        if let Some(plain) = plain {
+            match secrets.migrate(secrets::CLAUDE_API_KEY, &plain) {
+                Ok(()) => {
+                    store.delete("api_key");
+                    if let Err(e) = store.save() {
+                        eprintln!("Failed to remove plain API key from store: {}", e);
+                    }
+                }
+                Err(e) => {
+                    // 옮기지 못하면 평문을 남겨두고 이번 실행에서는 그대로 사용
+                    eprintln!("Failed to migrate API key to {}: {}", secrets.backend(), e);
+                    *state.0.lock().unwrap() = Some(plain);
+                }
+            }
+        }
+    }
+
+    match secrets.get(secrets::CLAUDE_API_KEY) {
+        Ok(Some(key)) => *state.0.lock().unwrap() = Some(key),
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to load API key from {}: {}", secrets.backend(), e),
     }
 
-    // Try to load from store
+    migrate_provider_key(app, secrets);
+}
+
+/// 예전 버전이 `llm_provider.apiKey`에 평문으로 저장한 키를 secrets로 옮기고 메모리에 로드
+fn migrate_provider_key(app: &AppHandle, secrets: &secrets::Secrets) {
+    let state = app.state::<ProviderKeyState>();
+
     if let Ok(store) = app.store("settings.json") {
-        if let Some(key) = store.get("api_key") {
-            if let Some(key_str) = key.as_str() {
-                let key_string = key_str.to_string();
-                *state.0.lock().unwrap() = Some(key_string.clone());
-                return key_string;
+        let mut config = load_provider_config(app);
+        if let Some(plain) = config.api_key.take().filter(|key| !key.is_empty()) {
+            match secrets.migrate(secrets::PROVIDER_API_KEY, &plain) {
+                Ok(()) => match serde_json::to_value(&config) {
+                    Ok(value) => {
+                        store.set("llm_provider", value);
+                        if let Err(e) = store.save() {
+                            eprintln!("Failed to remove plain provider API key from store: {}", e);
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to serialize provider config: {}", e),
+                },
+                Err(e) => {
+                    // 옮기지 못하면 평문을 남겨두고 이번 실행에서는 그대로 사용
+                    eprintln!("Failed to migrate provider API key to {}: {}", secrets.backend(), e);
+                    *state.0.lock().unwrap() = Some(plain);
+                    return;
+                }
             }
         }
     }
 
-    String::new()
+    match secrets.get(secrets::PROVIDER_API_KEY) {
+        Ok(Some(key)) => *state.0.lock().unwrap() = Some(key),
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to load provider API key from {}: {}", secrets.backend(), e),
+    }
+}
+
+/// 저장된 LLM provider 설정 로드 (없으면 Claude 기본값)
+fn load_provider_config(app: &AppHandle) -> llm::ProviderConfig {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("llm_provider"))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// 저장된 월간 사용량 예산 로드 (없으면 무제한)
+fn load_usage_budget(app: &AppHandle) -> llm::usage::UsageBudget {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("llm_budget"))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// 저장된 하루 자동 배치 설정 로드 (없으면 기본값)
+fn load_planner_preferences(app: &AppHandle) -> dayplan::PlannerPreferences {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("planner_preferences"))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// 반복 일정 태스크를 미리 만들어 두는 기간(일) 로드 (없으면 4주)
+fn load_recurring_horizon(app: &AppHandle) -> i64 {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("recurring_horizon_days"))
+        .and_then(|value| value.as_i64())
+        .filter(|days| recurring::materialize::HORIZON_RANGE.contains(days))
+        .unwrap_or(recurring::materialize::DEFAULT_HORIZON_DAYS)
+}
+
+/// 현재 provider 설정으로 LLMService 생성
+///
+/// `operation` 이름으로 모든 호출을 사용량 ledger에 기록하고,
+/// provider를 호출할 때마다 이번 달 사용량이 예산을 넘었는지 확인해 `BudgetExceeded`로 거부합니다.
+/// `SCHEDULE_AI_LLM_CACHE`가 설정되어 있으면 응답 캐시(녹화/재생)를 거칩니다.
+async fn create_llm_service(
+    app: &AppHandle,
+    state: &ApiKeyState,
+    operation: &str,
+) -> Result<LLMService, LLMError> {
+    let mut config = load_provider_config(app);
+    config.api_key = app.state::<ProviderKeyState>().0.lock().unwrap().clone();
+    let cache_dir = app
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join("llm_cache"))
+        .unwrap_or_else(|_| std::env::temp_dir().join("schedule-ai-llm-cache"));
+    let provider = llm::cache::CacheConfig::from_env().build(&config, load_api_key(state), &cache_dir)?;
+    let service = LLMService::new(provider);
+
+    let pool = match db::pool(app).await {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("LLM usage ledger unavailable: {}", e);
+            return Ok(service);
+        }
+    };
+
+    let ledger = llm::usage::UsageLedger::new(pool, operation).with_budget(load_usage_budget(app));
+    Ok(service.with_ledger(ledger))
+}
+
+/// 날짜의 기존 일정, 빈 시간, 최근 완료 통계, 소요시간 보정을 DB에서 모은 AI context
+///
+/// DB를 읽을 수 없으면 일정이 비어 있는 것으로 보고 활동 시간대 전체를 빈 시간으로 둡니다.
+async fn load_day_context(app: &AppHandle, date: &str, window: &schedule::TimeSlot) -> commands::llm::LLMContext {
+    let days_before = |days: i64| {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| (d - chrono::Duration::days(days)).format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|_| date.to_string())
+    };
+    let history_start = days_before(schedule::HISTORY_DAYS);
+    let estimate_start = days_before(estimate::HISTORY_DAYS);
+
+    let loaded = async {
+        let pool = db::pool(app).await?;
+        let tasks = db::tasks_between(&pool, &history_start, date).await.map_err(|e| e.to_string())?;
+        let generated = db::generated_recurring_ids(&pool, date).await.map_err(|e| e.to_string())?;
+        let recurring: Vec<_> = db::active_recurring_plans(&pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter(|plan| !generated.contains(&plan.id))
+            .collect();
+        let core_times = db::active_core_times(&pool).await.map_err(|e| e.to_string())?;
+        let completed = db::completed_tasks_with_durations(&pool, &estimate_start)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok::<_, String>((tasks, recurring, core_times, completed))
+    }
+    .await;
+
+    let (tasks, recurring, core_times, completed) = loaded.unwrap_or_else(|e| {
+        eprintln!("Failed to load schedule context: {}", e);
+        Default::default()
+    });
+
+    commands::llm::LLMContext {
+        plans: None,
+        schedule: Some(schedule::build_day_schedule(date, &tasks, &recurring, &core_times, window)),
+        recent_stats: Some(schedule::recent_stats(&tasks, date)),
+        estimation: Some(estimate::Estimator::learn(&completed).summary()),
+        recent_tasks: Some(tasks),
+        current_date: Some(date.to_string()),
+    }
+}
+
+/// 응답 타입 `T`로 structured output AI completion 실행
+///
+/// `request_id`가 있으면 생성 중인 JSON을 `llm-delta` 이벤트로 스트리밍하고
+/// `cancel_llm_request`로 취소할 수 있습니다.
+async fn complete_typed_with_events<T: StructuredOutput>(
+    app: &AppHandle,
+    service: &LLMService,
+    request: LLMRequest,
+    request_id: Option<&str>,
+) -> Result<T, LLMError> {
+    match request_id {
+        Some(id) => {
+            let streams = app.state::<StreamRegistry>();
+            let on_delta = llm::stream::delta_emitter(app, id);
+            let future = service.complete_typed::<T>(request, Some(&on_delta));
+            llm::stream::run_with_events(app, &streams, id, future).await
+        }
+        None => service.complete_typed::<T>(request, None).await,
+    }
+    .map(|structured| structured.value)
 }
 
+/// 진행 중인 스트리밍 AI 요청 취소
 #[tauri::command]
-fn set_api_key(app: AppHandle, state: State<ApiKeyState>, api_key: String) -> Result<(), String> {
-    // Save to store
-    let store = app.store("settings.json").map_err(|e| e.to_string())?;
-    store.set("api_key", serde_json::json!(api_key.clone()));
-    store.save().map_err(|e| e.to_string())?;
+fn cancel_llm_request(streams: State<StreamRegistry>, request_id: String) -> bool {
+    streams.cancel(&request_id)
+}
+
+// API Key management commands
+
+/// 저장된 키의 가린 값 ("sk-ant-...abcd"). 키가 없으면 빈 문자열
+#[tauri::command]
+fn get_api_key(state: State<ApiKeyState>) -> String {
+    load_api_key(&state)
+        .map(|key| secrets::mask(&key))
+        .unwrap_or_default()
+}
+
+/// 비밀값 저장소 상태 (설정 화면에서 passphrase 입력이 필요한지)
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretsStatus {
+    pub backend: &'static str,
+    pub locked: bool,
+    /// 암호화 파일이 이미 있으면 기존 passphrase, 없으면 새 passphrase를 입력받음
+    pub has_file: bool,
+}
+
+fn secrets_status(secrets: &secrets::Secrets) -> SecretsStatus {
+    SecretsStatus {
+        backend: secrets.backend(),
+        locked: secrets.is_locked(),
+        has_file: secrets.has_file(),
+    }
+}
+
+#[tauri::command]
+fn get_secrets_status(secrets: State<SecretsState>) -> SecretsStatus {
+    secrets_status(&secrets.0)
+}
+
+/// OS 키체인이 없을 때 사용자가 입력한 passphrase로 암호화 파일을 열고 저장된 키를 로드
+#[tauri::command]
+async fn unlock_secrets(
+    app: AppHandle,
+    secrets: State<'_, SecretsState>,
+    passphrase: String,
+) -> Result<SecretsStatus, String> {
+    if passphrase.is_empty() {
+        return Err("Passphrase must not be empty".to_string());
+    }
+    let store = secrets.0.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        store.unlock(zeroize::Zeroizing::new(passphrase))?;
+        load_secrets(&app, &store);
+        Ok::<_, secrets::SecretError>(secrets_status(&store))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// OS 키체인(없으면 passphrase로 암호화한 파일)에 저장
+#[tauri::command]
+async fn set_api_key(
+    state: State<'_, ApiKeyState>,
+    secrets: State<'_, SecretsState>,
+    api_key: String,
+) -> Result<(), String> {
+    let store = secrets.0.clone();
+    let key = api_key.clone();
+    tauri::async_runtime::spawn_blocking(move || store.set(secrets::CLAUDE_API_KEY, &key))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
 
     // Update memory state
     *state.0.lock().unwrap() = Some(api_key);
@@ -241,11 +571,12 @@ fn set_api_key(app: AppHandle, state: State<ApiKeyState>, api_key: String) -> Re
 }
 
 #[tauri::command]
-fn delete_api_key(app: AppHandle, state: State<ApiKeyState>) -> Result<(), String> {
-    // Remove from store
-    let store = app.store("settings.json").map_err(|e| e.to_string())?;
-    store.delete("api_key");
-    store.save().map_err(|e| e.to_string())?;
+async fn delete_api_key(state: State<'_, ApiKeyState>, secrets: State<'_, SecretsState>) -> Result<(), String> {
+    let store = secrets.0.clone();
+    tauri::async_runtime::spawn_blocking(move || store.delete(secrets::CLAUDE_API_KEY))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
 
     // Clear memory state
     *state.0.lock().unwrap() = None;
@@ -253,6 +584,135 @@ fn delete_api_key(app: AppHandle, state: State<ApiKeyState>) -> Result<(), Strin
     Ok(())
 }
 
+// LLM provider selection commands
+
+/// 저장된 provider 설정. API 키는 가린 값만 돌려줌
+#[tauri::command]
+fn get_llm_provider(app: AppHandle, state: State<ProviderKeyState>) -> llm::ProviderConfig {
+    llm::ProviderConfig {
+        api_key: state.0.lock().unwrap().as_deref().map(secrets::mask),
+        ..load_provider_config(&app)
+    }
+}
+
+/// provider 설정은 settings.json에, API 키는 secrets에 저장
+///
+/// `apiKey`가 없거나 가린 값 그대로면 기존 키를 유지하고, 빈 문자열이면 삭제합니다.
+#[tauri::command]
+async fn set_llm_provider(
+    app: AppHandle,
+    state: State<'_, ProviderKeyState>,
+    secrets: State<'_, SecretsState>,
+    mut config: llm::ProviderConfig,
+) -> Result<(), String> {
+    let current = state.0.lock().unwrap().clone();
+    let submitted = config
+        .api_key
+        .take()
+        .filter(|key| current.as_deref().map(secrets::mask).as_ref() != Some(key));
+
+    if let Some(api_key) = submitted {
+        let store = secrets.0.clone();
+        let key = api_key.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if key.is_empty() {
+                store.delete(secrets::PROVIDER_API_KEY)
+            } else {
+                store.set(secrets::PROVIDER_API_KEY, &key)
+            }
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+        *state.0.lock().unwrap() = Some(api_key).filter(|key| !key.is_empty());
+    }
+
+    let value = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    let store = app.store("settings.json").map_err(|e| e.to_string())?;
+    store.set("llm_provider", value);
+    store.save().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// LLM usage ledger commands
+#[tauri::command]
+async fn get_llm_usage(
+    app: AppHandle,
+    from: String,
+    to: String,
+) -> Result<Vec<llm::usage::DailyUsage>, String> {
+    let pool = db::pool(&app).await?;
+    llm::usage::daily_usage(&pool, &from, &to)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 이번 달 누적 사용량과 예산
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmUsageSummary {
+    pub month: String,
+    pub totals: llm::usage::UsageTotals,
+    pub budget: llm::usage::UsageBudget,
+}
+
+#[tauri::command]
+async fn get_llm_usage_summary(app: AppHandle, month: Option<String>) -> Result<LlmUsageSummary, String> {
+    let pool = db::pool(&app).await?;
+    let month = month.unwrap_or_else(llm::usage::current_month);
+    let totals = llm::usage::monthly_totals(&pool, &month)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(LlmUsageSummary {
+        month,
+        totals,
+        budget: load_usage_budget(&app),
+    })
+}
+
+#[tauri::command]
+fn set_llm_budget(app: AppHandle, budget: llm::usage::UsageBudget) -> Result<(), String> {
+    let value = serde_json::to_value(&budget).map_err(|e| e.to_string())?;
+    let store = app.store("settings.json").map_err(|e| e.to_string())?;
+    store.set("llm_budget", value);
+    store.save().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Day planner preference commands
+#[tauri::command]
+fn get_planner_preferences(app: AppHandle) -> dayplan::PlannerPreferences {
+    load_planner_preferences(&app)
+}
+
+#[tauri::command]
+fn set_planner_preferences(app: AppHandle, preferences: dayplan::PlannerPreferences) -> Result<(), String> {
+    let value = serde_json::to_value(&preferences).map_err(|e| e.to_string())?;
+    let store = app.store("settings.json").map_err(|e| e.to_string())?;
+    store.set("planner_preferences", value);
+    store.save().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Recurring task horizon commands
+#[tauri::command]
+fn get_recurring_horizon(app: AppHandle) -> i64 {
+    load_recurring_horizon(&app)
+}
+
+#[tauri::command]
+async fn set_recurring_horizon(app: AppHandle, days: i64) -> Result<recurring::materialize::MaterializeSummary, String> {
+    if !recurring::materialize::HORIZON_RANGE.contains(&days) {
+        return Err(format!("Recurring horizon must be between 1 and 365 days: {}", days));
+    }
+    let store = app.store("settings.json").map_err(|e| e.to_string())?;
+    store.set("recurring_horizon_days", serde_json::json!(days));
+    store.save().map_err(|e| e.to_string())?;
+    // 늘렸으면 바로 채움
+    commands::recurring::top_up(&app).await
+}
+
 // Plan rules management commands
 #[tauri::command]
 fn get_plan_rules(app: AppHandle) -> String {
@@ -433,308 +893,302 @@ fn set_language(app: AppHandle, language: String) -> Result<(), String> {
     Ok(())
 }
 
+/// 언어 설정(없으면 시스템 언어)과 사용자 override로 prompt registry 구성
+fn load_prompt_registry(app: &AppHandle) -> PromptRegistry {
+    let language = get_language(app.clone()).unwrap_or_else(get_system_locale);
+    PromptRegistry::new(Locale::from_tag(&language), load_prompt_overrides(app))
+}
+
+fn load_prompt_overrides(app: &AppHandle) -> PromptOverrides {
+    app.store("settings.json")
+        .ok()
+        .and_then(|store| store.get("prompt_overrides"))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+fn save_prompt_overrides(app: &AppHandle, overrides: &PromptOverrides) -> Result<(), String> {
+    let value = serde_json::to_value(overrides).map_err(|e| e.to_string())?;
+    let store = app.store("settings.json").map_err(|e| e.to_string())?;
+    store.set("prompt_overrides", value);
+    store.save().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Prompt template commands
 #[tauri::command]
-async fn validate_api_key(state: State<'_, ApiKeyState>) -> Result<bool, String> {
-    let api_key = state.0.lock().unwrap().clone();
-
-    match api_key {
-        Some(key) if !key.is_empty() => {
-            let provider = ClaudeProvider::new(key);
-            let request = LLMRequest {
-                messages: vec![LLMMessage {
-                    role: "user".to_string(),
-                    content: "Hi".to_string(),
-                }],
-                max_tokens: Some(10),
-                temperature: Some(0.0),
-            };
-
-            match provider.complete(request).await {
-                Ok(_) => Ok(true),
-                Err(_) => Ok(false),
-            }
+fn get_prompt_templates(app: AppHandle) -> Vec<llm::prompts::PromptTemplateInfo> {
+    load_prompt_registry(&app).templates()
+}
+
+#[tauri::command]
+fn set_prompt_override(
+    app: AppHandle,
+    id: PromptId,
+    locale: Locale,
+    template: String,
+) -> Result<(), String> {
+    llm::prompts::validate_template(id, &template).map_err(|e| e.to_string())?;
+    let mut overrides = load_prompt_overrides(&app);
+    overrides.entry(id).or_default().insert(locale, template);
+    save_prompt_overrides(&app, &overrides)
+}
+
+#[tauri::command]
+fn reset_prompt_override(app: AppHandle, id: PromptId, locale: Locale) -> Result<(), String> {
+    let mut overrides = load_prompt_overrides(&app);
+    if let Some(by_locale) = overrides.get_mut(&id) {
+        by_locale.remove(&locale);
+        if by_locale.is_empty() {
+            overrides.remove(&id);
         }
-        _ => Ok(false),
     }
+    save_prompt_overrides(&app, &overrides)
 }
 
 #[tauri::command]
-async fn split_task_with_ai(
-    state: State<'_, ApiKeyState>,
-    task_title: String,
-) -> Result<commands::llm::SplitTaskResponse, String> {
-    let api_key = state.0.lock().unwrap().clone()
-        .ok_or("API key not set")?;
-
-    let provider = ClaudeProvider::new(api_key);
+async fn validate_api_key(app: AppHandle, state: State<'_, ApiKeyState>) -> Result<bool, String> {
+    let service = match create_llm_service(&app, &state, "validate_api_key").await {
+        Ok(service) => service,
+        Err(_) => return Ok(false),
+    };
 
-    let system_prompt = r#"당신은 ADHD 환자를 돕는 일정 관리 AI입니다.
-주어진 태스크를 ADHD 친화적인 작은 단위(5-15분)로 분해해주세요.
+    let request = LLMRequest {
+        messages: vec![LLMMessage {
+            role: "user".to_string(),
+            content: "Hi".to_string(),
+        }],
+        max_tokens: Some(10),
+        temperature: Some(0.0),
+    };
 
-원칙:
-- 각 서브태스크는 명확하고 구체적으로
-- 시작하기 쉬운 작은 첫 단계
-- 완료 기준이 명확해야 함
-- 3-5개의 서브태스크로 분해
+    match service.complete(request).await {
+        Ok(_) => Ok(true),
+        Err(_) => Ok(false),
+    }
+}
 
-JSON 형식으로만 응답하세요:
-{"subtasks": [{"title": "서브태스크 제목", "estimated_minutes": 10}]}"#;
+#[tauri::command]
+async fn split_task_with_ai(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    task_title: String,
+    request_id: Option<String>,
+) -> Result<commands::llm::SplitTaskResponse, LLMError> {
+    let service = create_llm_service(&app, &state, "split_task").await?;
+    let prompt = load_prompt_registry(&app)
+        .render(PromptId::SplitTask, &[("task_title", &task_title)])?;
 
     let request = LLMRequest {
         messages: vec![
             LLMMessage {
                 role: "user".to_string(),
-                content: format!("{}\n\n태스크: {}", system_prompt, task_title),
+                content: prompt,
             },
         ],
         max_tokens: Some(1024),
         temperature: Some(0.7),
     };
 
-    let response = provider.complete(request).await.map_err(|e| e.to_string())?;
-
-    // Parse the JSON response - handle markdown code blocks
-    let content = response.content.trim();
-    let json_str = if content.starts_with("```") {
-        content
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim()
-    } else {
-        content
-    };
-
-    let parsed: commands::llm::SplitTaskResponse = serde_json::from_str(json_str)
-        .map_err(|e| format!("Failed to parse LLM response: {}. Response: {}", e, json_str))?;
-
-    Ok(parsed)
+    complete_typed_with_events(&app, &service, request, request_id.as_deref()).await
 }
 
 #[tauri::command]
 async fn parse_task_with_ai(
+    app: AppHandle,
     state: State<'_, ApiKeyState>,
     input: String,
     current_date: String,
-) -> Result<commands::llm::ParseTaskResponse, String> {
-    let api_key = state.0.lock().unwrap().clone()
-        .ok_or("API key not set")?;
-
-    let provider = ClaudeProvider::new(api_key);
-
-    let system_prompt = format!(r#"당신은 ADHD 환자를 돕는 일정 관리 AI입니다.
-사용자의 자연어 입력을 분석하여 구조화된 태스크 정보로 변환해주세요.
-
-오늘 날짜: {}
-
-입력에서 다음 정보를 추출하세요:
-- title: 태스크 제목 (필수)
-- scheduled_date: 날짜 "YYYY-MM-DD" (없으면 오늘)
-- scheduled_time: 시작 시간 "HH:MM" (있으면)
-- end_time: 종료 시간 "HH:MM" (있으면)
-- location: 장소 (있으면)
-- subtasks: 서브태스크 배열 (있으면)
-- priority: 우선순위 0-3 (기본값 1)
-- estimated_duration: 예상 소요시간(분) (시작/종료 시간으로 계산하거나 추정)
-
-날짜 키워드:
-- "오늘" = 오늘 날짜
-- "내일" = 오늘 + 1일
-- "모레" = 오늘 + 2일
-- "다음주" = 오늘 + 7일
+    request_id: Option<String>,
+) -> Result<commands::llm::ParseTaskResponse, LLMError> {
+    // API 키가 없거나 API에 닿지 못하면 규칙 기반 파서 사용
+    // (취소, 예산 초과, 인증 실패, replay 누락 등은 그대로 반환)
+    let offline = |error: LLMError| {
+        eprintln!("AI task parsing unavailable, using offline parser: {}", error);
+        let today = chrono::NaiveDate::parse_from_str(&current_date, "%Y-%m-%d")
+            .unwrap_or_else(|_| chrono::Local::now().date_naive());
+        Ok(parser::parse_task(&input, today))
+    };
 
-JSON 형식으로만 응답하세요 (마크다운 코드블록 없이):
-{{"title": "태스크 제목", "scheduled_date": "2026-01-15", "scheduled_time": "14:00", "location": "카페", "subtasks": ["준비물 챙기기"], "priority": 1, "estimated_duration": 60}}"#, current_date);
+    let service = match create_llm_service(&app, &state, "parse_task").await {
+        Ok(service) => service,
+        // 프로바이더를 만들 수 없음 = API 키/프로바이더 미설정
+        Err(error @ LLMError::ConfigError(_)) => return offline(error),
+        Err(error) => return Err(error),
+    };
+    let prompt = load_prompt_registry(&app)
+        .render(
+            PromptId::ParseTask,
+            &[("current_date", &current_date), ("input", &input)],
+        )?;
 
     let request = LLMRequest {
         messages: vec![
             LLMMessage {
                 role: "user".to_string(),
-                content: format!("{}\n\n입력: {}", system_prompt, input),
+                content: prompt,
             },
         ],
         max_tokens: Some(1024),
         temperature: Some(0.3),
     };
 
-    let response = provider.complete(request).await.map_err(|e| e.to_string())?;
-
-    // Parse the JSON response - handle markdown code blocks
-    let content = response.content.trim();
-    let json_str = if content.starts_with("```") {
-        content
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim()
-    } else {
-        content
-    };
+    match complete_typed_with_events(&app, &service, request, request_id.as_deref()).await {
+        Ok(parsed) => Ok(parsed),
+        Err(error) if error.is_unavailable() => offline(error),
+        Err(error) => Err(error),
+    }
+}
 
-    let parsed: commands::llm::ParseTaskResponse = serde_json::from_str(json_str)
-        .map_err(|e| format!("Failed to parse LLM response: {}. Response: {}", e, json_str))?;
+/// 여러 할 일이 섞인 입력을 한 번에 태스크/반복 일정 목록으로 변환
+///
+/// 모호한 날짜나 확신도가 낮은 항목은 `needsConfirmation`으로 표시합니다.
+#[tauri::command]
+async fn capture_tasks_with_ai(
+    app: AppHandle,
+    state: State<'_, ApiKeyState>,
+    input: String,
+    current_date: String,
+    request_id: Option<String>,
+) -> Result<commands::llm::CaptureTasksResponse, LLMError> {
+    if input.trim().is_empty() {
+        return Err(LLMError::InvalidInput("input must not be empty".to_string()));
+    }
+    let service = create_llm_service(&app, &state, "capture_tasks").await?;
+    let prompt = load_prompt_registry(&app)
+        .render(
+            PromptId::CaptureTasks,
+            &[("current_date", &current_date), ("input", &input)],
+        )?;
 
-    Ok(parsed)
+    let request = LLMRequest {
+        messages: vec![
+            LLMMessage {
+                role: "user".to_string(),
+                content: prompt,
+            },
+        ],
+        max_tokens: Some(2048),
+        temperature: Some(0.3),
+    };
+
+    let output: commands::llm::CaptureTasksOutput =
+        complete_typed_with_events(&app, &service, request, request_id.as_deref()).await?;
+    Ok(output.finish(&current_date))
 }
 
 #[tauri::command]
 async fn parse_plan_with_ai(
+    app: AppHandle,
     state: State<'_, ApiKeyState>,
     plan_input: String,
     plan_rules: Option<String>,
-) -> Result<commands::llm::ParsePlanResponse, String> {
-    let api_key = state.0.lock().unwrap().clone()
-        .ok_or("API key not set")?;
-
-    let provider = ClaudeProvider::new(api_key);
-
-    let rules_section = plan_rules
-        .filter(|r| !r.is_empty())
-        .map(|r| format!("\n\n사용자의 개인 규칙:\n{}", r))
-        .unwrap_or_default();
-
-    let system_prompt = format!(r#"당신은 ADHD 환자를 돕는 일정 관리 AI입니다.
-사용자의 계획을 분석하여 구조화된 JSON으로 변환해주세요.
-
-원칙:
-- 목표는 구체적이고 측정 가능하게
-- 마일스톤은 중간 목표로 설정
-- 일일 태스크는 15-45분 단위로{}
-
-JSON 형식으로만 응답하세요:
-{{
-  "goals": ["목표1", "목표2"],
-  "milestones": [
-    {{"title": "마일스톤", "target_date": "2025-01-15"}}
-  ],
-  "suggested_tasks": [
-    {{"title": "일일 태스크", "estimated_duration": 30, "priority": 1}}
-  ]
-}}"#, rules_section);
+    request_id: Option<String>,
+) -> Result<commands::llm::ParsePlanResponse, LLMError> {
+    let service = create_llm_service(&app, &state, "parse_plan").await?;
+    let prompts = load_prompt_registry(&app);
+
+    let plan_rules = plan_rules
+        .filter(|r| !r.trim().is_empty())
+        .unwrap_or_else(|| prompts.locale().none_label().to_string());
+    let today = llm::cache::today();
+    let prompt = prompts
+        .render(
+            PromptId::ParsePlan,
+            &[("current_date", &today), ("plan_rules", &plan_rules), ("plan_input", &plan_input)],
+        )?;
 
     let request = LLMRequest {
         messages: vec![
             LLMMessage {
                 role: "user".to_string(),
-                content: format!("{}\n\n계획:\n{}", system_prompt, plan_input),
+                content: prompt,
             },
         ],
         max_tokens: Some(2048),
         temperature: Some(0.7),
     };
 
-    let response = provider.complete(request).await.map_err(|e| e.to_string())?;
-
-    // Parse the JSON response - handle markdown code blocks
-    let content = response.content.trim();
-    let json_str = if content.starts_with("```") {
-        content
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim()
-    } else {
-        content
-    };
-
-    // Parse into intermediate structure
-    #[derive(serde::Deserialize)]
-    struct ParsedContent {
-        goals: Vec<String>,
-        milestones: Option<Vec<serde_json::Value>>,
-        suggested_tasks: Vec<serde_json::Value>,
-    }
-
-    let parsed: ParsedContent = serde_json::from_str(json_str)
-        .map_err(|e| format!("Failed to parse LLM response: {}. Response: {}", e, json_str))?;
-
-    // Convert to our response type
-    let suggested_tasks: Vec<models::SuggestedTask> = parsed.suggested_tasks
-        .into_iter()
-        .map(|t| models::SuggestedTask {
-            title: t.get("title").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-            estimated_duration: t.get("estimated_duration").and_then(|v| v.as_i64()).map(|v| v as i32),
-            priority: t.get("priority").and_then(|v| v.as_i64()).unwrap_or(1) as i32,
-            frequency: None,
-        })
-        .collect();
+    let parsed: commands::llm::PlanParseOutput =
+        complete_typed_with_events(&app, &service, request, request_id.as_deref()).await?;
 
     Ok(commands::llm::ParsePlanResponse {
-        parsed_content: models::ParsedPlanContent {
-            goals: parsed.goals,
-            milestones: vec![],
-            suggested_tasks,
-        },
+        parsed_content: parsed.into_content(),
     })
 }
 
+/// 계획을 바탕으로 하루 태스크 생성
+///
+/// 그날의 기존 태스크, 반복 일정, 코어 타임, 최근 완료 통계를 함께 보내고,
+/// 생성된 태스크는 빈 시간에 겹치지 않게 배치합니다. 오늘이면 현재 시각 이후만 사용합니다.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn generate_daily_tasks_with_ai(
+    app: AppHandle,
     state: State<'_, ApiKeyState>,
     plan_title: String,
     plan_description: String,
     date: String,
     plan_rules: Option<String>,
-) -> Result<commands::llm::GenerateDailyTasksResponse, String> {
-    let api_key = state.0.lock().unwrap().clone()
-        .ok_or("API key not set")?;
-
-    let provider = ClaudeProvider::new(api_key);
-
-    let rules_section = plan_rules
-        .filter(|r| !r.is_empty())
-        .map(|r| format!("\n\n사용자의 개인 규칙:\n{}", r))
-        .unwrap_or_default();
-
-    let system_prompt = format!(r#"당신은 ADHD 환자를 돕는 일정 관리 AI입니다.
-주어진 계획을 기반으로 오늘 할 태스크를 생성해주세요.
-
-원칙:
-- ADHD 친화적인 작은 단위(15-45분)
-- 시작하기 쉬운 간단한 태스크부터
-- 3-5개의 태스크 생성
-- 구체적이고 실행 가능한 태스크{}
-
-JSON 형식으로만 응답하세요:
-{{
-  "tasks": [
-    {{"title": "태스크", "description": "설명", "estimated_duration": 25, "priority": 1, "scheduled_time": "09:00"}}
-  ],
-  "summary": "오늘의 요약"
-}}"#, rules_section);
+    day_start: Option<String>,
+    day_end: Option<String>,
+    request_id: Option<String>,
+) -> Result<commands::llm::GenerateDailyTasksResponse, LLMError> {
+    let service = create_llm_service(&app, &state, "generate_daily_tasks").await?;
+    let prompts = load_prompt_registry(&app);
+    let locale = prompts.locale();
+
+    let mut window = schedule::TimeSlot {
+        start: day_start.unwrap_or_else(|| schedule::DAY_START.to_string()),
+        end: day_end.unwrap_or_else(|| schedule::DAY_END.to_string()),
+    };
+    if date == chrono::Local::now().format("%Y-%m-%d").to_string() {
+        window.start = window.start.max(schedule::now_rounded());
+    }
+    let context = load_day_context(&app, &date, &window).await;
+    let day = context.schedule.unwrap_or_else(|| schedule::build_day_schedule(&date, &[], &[], &[], &window));
+    let mut recent_stats = context.recent_stats.unwrap_or_default().describe(locale);
+    if let Some(estimation) = context.estimation.filter(|e| e.sample_count > 0) {
+        recent_stats = if recent_stats == locale.none_label() {
+            estimation.describe(locale)
+        } else {
+            format!("{}\n{}", recent_stats, estimation.describe(locale))
+        };
+    }
+
+    let plan_rules = plan_rules
+        .filter(|r| !r.trim().is_empty())
+        .unwrap_or_else(|| locale.none_label().to_string());
+    let prompt = prompts
+        .render(
+            PromptId::GenerateDailyTasks,
+            &[
+                ("plan_rules", &plan_rules),
+                ("plan_title", &plan_title),
+                ("plan_description", &plan_description),
+                ("date", &date),
+                ("existing_schedule", &day.describe_busy(locale)),
+                ("free_slots", &day.describe_free_slots(locale)),
+                ("focus_windows", &day.describe_focus_windows(locale)),
+                ("recent_stats", &recent_stats),
+            ],
+        )?;
 
     let request = LLMRequest {
         messages: vec![
             LLMMessage {
                 role: "user".to_string(),
-                content: format!(
-                    "{}\n\n계획: {}\n설명: {}\n날짜: {}",
-                    system_prompt, plan_title, plan_description, date
-                ),
+                content: prompt,
             },
         ],
         max_tokens: Some(2048),
         temperature: Some(0.7),
     };
 
-    let response = provider.complete(request).await.map_err(|e| e.to_string())?;
-
-    // Parse the JSON response - handle markdown code blocks
-    let content = response.content.trim();
-    let json_str = if content.starts_with("```") {
-        content
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim()
-    } else {
-        content
-    };
-
-    let parsed: commands::llm::GenerateDailyTasksResponse = serde_json::from_str(json_str)
-        .map_err(|e| format!("Failed to parse LLM response: {}. Response: {}", e, json_str))?;
-
-    Ok(parsed)
+    let mut response: commands::llm::GenerateDailyTasksResponse =
+        complete_typed_with_events(&app, &service, request, request_id.as_deref()).await?;
+    response.fit_to(&day);
+    Ok(response)
 }
 
 // Export/Import commands
@@ -879,122 +1333,40 @@ async fn parse_recurrence_pattern(
 
 #[tauri::command]
 async fn parse_recurrence_pattern_with_ai(
+    app: AppHandle,
     state: State<'_, ApiKeyState>,
     input: String,
-) -> Result<recurring::ParsedRecurrencePattern, String> {
-    let api_key = state.0.lock().unwrap().clone()
-        .ok_or("API key not set")?;
-
-    let provider = ClaudeProvider::new(api_key);
-
-    let system_prompt = r#"당신은 자연어를 구조화된 반복 일정으로 변환하는 AI입니다.
-사용자의 입력을 분석하여 반복 일정 패턴을 JSON으로 추출해주세요.
-
-추출할 정보:
-- recurrence_type: "daily", "weekly", "monthly" 중 하나
-- interval_value: 반복 간격 (기본값 1, 격주면 2)
-- days_of_week: 요일 배열 [0=일, 1=월, 2=화, 3=수, 4=목, 5=금, 6=토] (주간 반복 시)
-- day_of_month: 월간 반복 시 날짜 (1-31)
-- scheduled_time: 시작 시간 "HH:MM" 형식
-- end_time: 종료 시간 "HH:MM" 형식 (있으면)
-- estimated_duration: 소요 시간 (분 단위, start_time과 end_time으로 계산)
-- start_date: 시작 날짜 "YYYY-MM-DD" 형식
-- end_date: 종료 날짜 "YYYY-MM-DD" 형식 (있으면)
-- title: 일정 제목
-- location: 장소 (있으면)
-
-요일 키워드:
-- "평일" = [1,2,3,4,5]
-- "주말" = [0,6]
-- "월수금" = [1,3,5]
-
-오늘 날짜: "#.to_string() + &chrono::Local::now().format("%Y-%m-%d").to_string() + r#"
-
-JSON 형식으로만 응답하세요 (마크다운 코드블록 없이):
-{
-  "recurrence_type": "weekly",
-  "interval_value": 1,
-  "days_of_week": [6],
-  "scheduled_time": "12:00",
-  "end_time": "16:00",
-  "estimated_duration": 240,
-  "start_date": "2026-01-01",
-  "end_date": "2026-02-28",
-  "title": "토플 학원",
-  "location": "강남역 근처"
-}"#;
+    request_id: Option<String>,
+) -> Result<recurring::ParsedRecurrencePattern, LLMError> {
+    let service = create_llm_service(&app, &state, "parse_recurrence").await?;
+    let today = llm::cache::today();
+    let prompt = load_prompt_registry(&app)
+        .render(PromptId::ParseRecurrence, &[("today", &today), ("input", &input)])?;
 
     let request = LLMRequest {
         messages: vec![
             LLMMessage {
                 role: "user".to_string(),
-                content: format!("{}\n\n입력: {}", system_prompt, input),
+                content: prompt,
             },
         ],
         max_tokens: Some(1024),
         temperature: Some(0.3), // 결정론적인 결과를 위해 낮은 온도
     };
 
-    let response = provider.complete(request).await.map_err(|e| e.to_string())?;
-
-    // Parse the JSON response - handle markdown code blocks
-    let content = response.content.trim();
-    let json_str = if content.starts_with("```") {
-        content
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim()
-    } else {
-        content
-    };
-
-    // LLM 응답을 중간 구조로 파싱
-    #[derive(serde::Deserialize)]
-    struct LLMParsedPattern {
-        recurrence_type: Option<String>,
-        interval_value: Option<i32>,
-        days_of_week: Option<Vec<i32>>,
-        day_of_month: Option<i32>,
-        scheduled_time: Option<String>,
-        end_time: Option<String>,
-        estimated_duration: Option<i32>,
-        start_date: Option<String>,
-        end_date: Option<String>,
-        title: Option<String>,
-        location: Option<String>,
-    }
-
-    let parsed: LLMParsedPattern = serde_json::from_str(json_str)
-        .map_err(|e| format!("Failed to parse LLM response: {}. Response: {}", e, json_str))?;
-
-    // 변환
-    let recurrence_type = match parsed.recurrence_type.as_deref() {
-        Some("daily") => recurring::RecurrenceType::Daily,
-        Some("monthly") => recurring::RecurrenceType::Monthly,
-        _ => recurring::RecurrenceType::Weekly,
-    };
-
-    Ok(recurring::ParsedRecurrencePattern {
-        recurrence_type,
-        interval_value: parsed.interval_value.unwrap_or(1),
-        days_of_week: parsed.days_of_week,
-        day_of_month: parsed.day_of_month,
-        scheduled_time: parsed.scheduled_time,
-        end_time: parsed.end_time,
-        estimated_duration: parsed.estimated_duration,
-        start_date: parsed.start_date,
-        end_date: parsed.end_date,
-        title: parsed.title,
-        location: parsed.location,
-    })
+    complete_typed_with_events(&app, &service, request, request_id.as_deref()).await
 }
 
+/// 반복 일정의 첫 horizon 동안 생성될 태스크 미리보기
 #[tauri::command]
 async fn generate_tasks_preview(
+    app: AppHandle,
     recurring_plan: recurring::RecurringPlan,
 ) -> Result<Vec<recurring::GeneratedTaskInput>, String> {
-    Ok(recurring::generate_tasks_from_recurring_plan(&recurring_plan))
+    recurring_plan.rule()?;
+    let start = chrono::NaiveDate::parse_from_str(&recurring_plan.start_date, "%Y-%m-%d").map_err(|e| e.to_string())?;
+    let end = start + chrono::Duration::days(load_recurring_horizon(&app));
+    Ok(recurring::generate_tasks_from_recurring_plan(&recurring_plan, start, end))
 }
 
 fn sanitize_filename(name: &str) -> String {
@@ -1039,69 +1411,58 @@ async fn poll_extension_command(
 }
 
 /// Focus Mode AI 인사이트 응답 구조
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 pub struct FocusInsightResponse {
+    /// 앱 차단 원인 분석
     pub insight: String,
+    /// 구체적인 조언 2가지
     pub advice: Vec<String>,
+    /// 격려의 말
     pub encouragement: String,
 }
 
-/// Claude Tool Use 기반 Focus Mode AI 인사이트 생성
+impl StructuredOutput for FocusInsightResponse {
+    const TOOL_NAME: &'static str = "focus_insight";
+
+    fn validate(&self) -> Result<(), String> {
+        if self.insight.trim().is_empty() || self.encouragement.trim().is_empty() {
+            return Err("insight and encouragement must not be empty".to_string());
+        }
+        if self.advice.is_empty() {
+            return Err("advice must contain at least one item".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// structured output 기반 Focus Mode AI 인사이트 생성
+///
+/// `request_id`를 넘기면 생성 중인 JSON이 `llm-delta` 이벤트로 스트리밍됩니다.
 #[tauri::command]
 async fn stream_focus_insight(
+    app: AppHandle,
     state: State<'_, ApiKeyState>,
     stats_summary: String,
-) -> Result<FocusInsightResponse, String> {
-    // 사용자가 입력한 Claude API 키 사용
-    let api_key = state.0.lock().unwrap().clone()
-        .ok_or("API key not set. Please set your Claude API key in Settings.")?;
-
-    let provider = ClaudeProvider::new(api_key);
-
-    let system_prompt = r#"# Role
-당신은 ADHD 사용자를 위한 '행동 심리학 기반 집중력 코치'입니다.
-
-# Task
-제공된 앱 차단 데이터를 분석하여 다음 필드를 채우세요:
-1. insight: 가장 많이 차단된 앱이 주는 심리적 보상 분석
-2. advice: 인지 부하를 줄이는 구체적 행동 지침 (배열 형태, 2가지)
-3. encouragement: 죄책감을 덜어주는 따뜻한 격려
-
-# Context
-사용자는 차단된 앱을 자주 열어보려 했습니다. 비판보다는 공감과 해결책에 집중하세요."#;
+    request_id: Option<String>,
+) -> Result<FocusInsightResponse, LLMError> {
+    let service = create_llm_service(&app, &state, "focus_insight").await?;
+    let prompt = load_prompt_registry(&app)
+        .render(PromptId::FocusInsight, &[("stats_summary", &stats_summary)])?;
 
     let messages = vec![
         LLMMessage {
             role: "user".to_string(),
-            content: format!("{}\n\n다음은 제 집중 모드 앱 차단 통계입니다:\n{}", system_prompt, stats_summary),
+            content: prompt,
         },
     ];
 
-    // JSON Schema 정의 (Claude tool use 형식)
-    let schema = serde_json::json!({
-        "type": "object",
-        "properties": {
-            "insight": { "type": "string", "description": "앱 차단 원인 분석" },
-            "advice": {
-                "type": "array",
-                "items": { "type": "string" },
-                "description": "구체적인 조언 2가지"
-            },
-            "encouragement": { "type": "string", "description": "격려의 말" }
-        },
-        "required": ["insight", "advice", "encouragement"]
-    });
-
-    let content = provider
-        .complete_structured(messages, schema, "focus_insight")
-        .await
-        .map_err(|e| e.to_string())?;
-
-    // JSON 파싱
-    let response: FocusInsightResponse = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    let request = LLMRequest {
+        messages,
+        max_tokens: Some(1024),
+        temperature: None,
+    };
 
-    Ok(response)
+    complete_typed_with_events(&app, &service, request, request_id.as_deref()).await
 }
 
 fn format_shortcut(shortcut: &Shortcut) -> String {
@@ -1182,11 +1543,18 @@ pub fn run() {
         )
         .manage(CurrentShortcut(Mutex::new(default_shortcut.clone())))
         .manage(ApiKeyState(Mutex::new(None)))
+        .manage(ProviderKeyState(Mutex::new(None)))
+        .manage(StreamRegistry::default())
+        .manage(commands::agent::AgentProposals::default())
         .manage(IpcState(Arc::new(IpcServerState::new())))
         .setup(move |app| {
             // Register default shortcut: Alt+Shift+Space
             app.global_shortcut().register(default_shortcut)?;
 
+            // API 키 저장소 (OS 키체인, 없으면 암호화 파일)
+            let secrets = setup_secrets(app.handle());
+            app.manage(SecretsState(secrets));
+
             // 닫기 동작 변경 (종료 → 숨김)
             setup_close_behavior(app.handle());
 
@@ -1210,19 +1578,69 @@ pub fn run() {
                 }
             });
 
+            // 반복 일정 태스크 롤링 윈도우: 시작할 때 채우고, 이후 날짜가 바뀌면 다시 채움
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut ticker = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+                let mut last_run = None;
+                loop {
+                    ticker.tick().await;
+                    let today = chrono::Local::now().date_naive();
+                    if last_run == Some(today) {
+                        continue;
+                    }
+                    match commands::recurring::top_up(&app_handle).await {
+                        Ok(_) => last_run = Some(today),
+                        Err(e) => eprintln!("Failed to materialize recurring tasks: {}", e),
+                    }
+                }
+            });
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::llm::process_with_llm,
             commands::llm::parse_plan,
+            commands::llm::materialize_plan,
             commands::llm::generate_daily_tasks,
             commands::llm::split_task,
+            commands::chat::create_chat_session,
+            commands::chat::list_chat_sessions,
+            commands::chat::get_chat_messages,
+            commands::chat::send_chat_message,
+            commands::chat::delete_chat_session,
+            commands::agent::run_planning_agent,
+            commands::agent::apply_agent_changes,
+            commands::agent::discard_agent_changes,
+            commands::reschedule::propose_reschedule,
+            commands::estimate::predict_task_duration,
+            commands::estimate::get_estimation_summary,
+            commands::dayplan::plan_day,
+            commands::conflicts::find_schedule_conflicts,
+            commands::conflicts::check_task_conflicts,
+            commands::review::get_weekly_stats,
+            commands::review::generate_weekly_review,
+            commands::review::list_weekly_reviews,
+            commands::review::get_weekly_review,
+            commands::review::delete_weekly_review,
             get_current_shortcut,
             set_shortcut,
             get_api_key,
             set_api_key,
             delete_api_key,
+            get_secrets_status,
+            unlock_secrets,
             validate_api_key,
+            get_llm_provider,
+            set_llm_provider,
+            // LLM usage ledger
+            get_llm_usage,
+            get_llm_usage_summary,
+            set_llm_budget,
+            get_planner_preferences,
+            set_planner_preferences,
+            get_recurring_horizon,
+            set_recurring_horizon,
             get_plan_rules,
             set_plan_rules,
             // Tab shortcuts
@@ -1241,8 +1659,14 @@ pub fn run() {
             get_system_locale,
             get_language,
             set_language,
+            // Prompt templates
+            get_prompt_templates,
+            set_prompt_override,
+            reset_prompt_override,
+            cancel_llm_request,
             split_task_with_ai,
             parse_task_with_ai,
+            capture_tasks_with_ai,
             parse_plan_with_ai,
             generate_daily_tasks_with_ai,
             // Export/Import
@@ -1261,6 +1685,11 @@ pub fn run() {
             parse_recurrence_pattern,
             parse_recurrence_pattern_with_ai,
             generate_tasks_preview,
+            commands::recurring::list_recurrence_exceptions,
+            commands::recurring::edit_recurring_occurrence,
+            commands::recurring::skip_recurring_occurrence,
+            commands::recurring::materialize_recurring_tasks,
+            commands::recurring::refresh_recurring_plan_tasks,
             // Focus mode
             focus::get_running_apps_command,
             focus::get_installed_apps_command,
@@ -0,0 +1,310 @@
+//! tool use 에이전트 루프
+//!
+//! 모델이 도구 호출(`tool_use`)을 멈출 때까지 도구를 실행하고 결과(`tool_result`)를 돌려주는
+//! 과정을 반복합니다. 도구의 실제 동작은 `ToolExecutor`가 정합니다.
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::stream::UsageReport;
+use super::structured::{add_usage, schema_for};
+use super::{LLMError, LLMService, TokenUsage};
+
+/// 에이전트가 한 요청에서 모델을 호출하는 최대 횟수
+pub const MAX_STEPS: usize = 8;
+
+/// 모델에게 제공하는 도구
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolSpec {
+    pub name: String,
+    pub description: String,
+    pub input_schema: serde_json::Value,
+}
+
+impl ToolSpec {
+    /// 입력 타입의 JSON schema로 도구 정의
+    pub fn new<T: JsonSchema>(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            input_schema: schema_for::<T>(),
+        }
+    }
+}
+
+/// 에이전트 대화의 content block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        content: String,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        is_error: bool,
+    },
+    /// 에이전트가 다루지 않는 block (thinking 등)
+    #[serde(other, skip_serializing)]
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentMessage {
+    pub role: String,
+    pub content: Vec<ContentBlock>,
+}
+
+impl AgentMessage {
+    pub fn user(text: &str) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentRequest {
+    pub system: Option<String>,
+    pub messages: Vec<AgentMessage>,
+    pub tools: Vec<ToolSpec>,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+}
+
+/// 모델 응답 한 번
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentTurn {
+    pub content: Vec<ContentBlock>,
+    pub stop_reason: Option<String>,
+    pub usage: Option<TokenUsage>,
+}
+
+impl UsageReport for AgentTurn {
+    fn usage(&self) -> Option<TokenUsage> {
+        self.usage.clone()
+    }
+}
+
+impl AgentTurn {
+    fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(text.trim()),
+                _ => None,
+            })
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// 도구 호출 실행
+///
+/// `Err`는 모델에게 `is_error` 결과로 전달되어 모델이 입력을 고쳐 다시 호출할 수 있습니다.
+#[async_trait]
+pub trait ToolExecutor: Send {
+    async fn call(&mut self, name: &str, input: serde_json::Value) -> Result<String, String>;
+}
+
+/// 에이전트 실행 결과
+#[derive(Debug, Clone)]
+pub struct AgentRun {
+    /// 모델의 마지막 답변
+    pub message: String,
+    pub tool_calls: usize,
+    pub usage: Option<TokenUsage>,
+}
+
+impl UsageReport for AgentRun {
+    fn usage(&self) -> Option<TokenUsage> {
+        self.usage.clone()
+    }
+}
+
+/// 모델이 도구 호출을 멈출 때까지 반복
+pub async fn run<E: ToolExecutor>(
+    service: &LLMService,
+    mut request: AgentRequest,
+    executor: &mut E,
+) -> Result<AgentRun, LLMError> {
+    let mut usage = None;
+    let mut tool_calls = 0;
+
+    for _ in 0..MAX_STEPS {
+        let turn = service.complete_with_tools(&request).await?;
+        add_usage(&mut usage, turn.usage.clone());
+
+        let calls: Vec<(String, String, serde_json::Value)> = turn
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse { id, name, input } => Some((id.clone(), name.clone(), input.clone())),
+                _ => None,
+            })
+            .collect();
+
+        if calls.is_empty() || turn.stop_reason.as_deref() != Some("tool_use") {
+            return Ok(AgentRun {
+                message: turn.text(),
+                tool_calls,
+                usage,
+            });
+        }
+
+        let mut results = Vec::with_capacity(calls.len());
+        for (id, name, input) in calls {
+            tool_calls += 1;
+            let (content, is_error) = match executor.call(&name, input).await {
+                Ok(content) => (content, false),
+                Err(error) => (error, true),
+            };
+            results.push(ContentBlock::ToolResult {
+                tool_use_id: id,
+                content,
+                is_error,
+            });
+        }
+
+        request.messages.push(AgentMessage {
+            role: "assistant".to_string(),
+            content: turn
+                .content
+                .into_iter()
+                .filter(|block| !matches!(block, ContentBlock::Other))
+                .collect(),
+        });
+        request.messages.push(AgentMessage {
+            role: "user".to_string(),
+            content: results,
+        });
+    }
+
+    Err(LLMError::ApiError(format!(
+        "Agent did not finish within {} steps",
+        MAX_STEPS
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::ClaudeProvider;
+    use mockito::Matcher;
+    use std::sync::Arc;
+
+    struct Recorder(Vec<String>);
+
+    #[async_trait]
+    impl ToolExecutor for Recorder {
+        async fn call(&mut self, name: &str, input: serde_json::Value) -> Result<String, String> {
+            self.0.push(format!("{} {}", name, input));
+            match input["date"].as_str() {
+                Some("2026-03-02") => Ok("[]".to_string()),
+                _ => Err("date must be YYYY-MM-DD".to_string()),
+            }
+        }
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct DateInput {
+        date: String,
+    }
+
+    #[tokio::test]
+    async fn test_agent_loop_returns_tool_results_until_done() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("POST", "/v1/messages")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "system": "plan",
+                "tools": [{"name": "list_tasks_for_date"}]
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"content":[{"type":"thinking","thinking":"..."},
+                    {"type":"tool_use","id":"t1","name":"list_tasks_for_date","input":{"date":"tomorrow"}}],
+                    "stop_reason":"tool_use","usage":{"input_tokens":10,"output_tokens":5}}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let second = server
+            .mock("POST", "/v1/messages")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "messages": [{}, {}, {"role": "user", "content": [{
+                    "type": "tool_result", "tool_use_id": "t1", "is_error": true
+                }]}]
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"content":[{"type":"tool_use","id":"t2","name":"list_tasks_for_date","input":{"date":"2026-03-02"}}],
+                    "stop_reason":"tool_use","usage":{"input_tokens":20,"output_tokens":5}}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let last = server
+            .mock("POST", "/v1/messages")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "messages": [{}, {}, {}, {}, {"content": [{"tool_use_id": "t2", "content": "[]"}]}]
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"content":[{"type":"text","text":"Nothing scheduled."}],
+                    "stop_reason":"end_turn","usage":{"input_tokens":30,"output_tokens":5}}"#,
+            )
+            .create_async()
+            .await;
+
+        let service = LLMService::new(Arc::new(
+            ClaudeProvider::new("sk-test".to_string()).with_base_url(server.url()),
+        ));
+        let request = AgentRequest {
+            system: Some("plan".to_string()),
+            messages: vec![AgentMessage::user("what is tomorrow?")],
+            tools: vec![ToolSpec::new::<DateInput>("list_tasks_for_date", "List tasks")],
+            max_tokens: None,
+            temperature: None,
+        };
+        let mut recorder = Recorder(Vec::new());
+        let run = run(&service, request, &mut recorder).await.unwrap();
+
+        first.assert_async().await;
+        second.assert_async().await;
+        last.assert_async().await;
+        assert_eq!(run.message, "Nothing scheduled.");
+        assert_eq!(run.tool_calls, 2);
+        assert_eq!(run.usage.unwrap().input_tokens, 60);
+        assert_eq!(recorder.0.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tool_use_unsupported_by_other_providers() {
+        let service = LLMService::new(Arc::new(crate::llm::OllamaProvider::new()));
+        let request = AgentRequest {
+            system: None,
+            messages: vec![AgentMessage::user("hi")],
+            tools: vec![],
+            max_tokens: None,
+            temperature: None,
+        };
+        let mut recorder = Recorder(Vec::new());
+        assert!(matches!(
+            run(&service, request, &mut recorder).await,
+            Err(LLMError::ConfigError(_))
+        ));
+    }
+}
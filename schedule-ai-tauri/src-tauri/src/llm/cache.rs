@@ -0,0 +1,515 @@
+//! LLM 응답 캐시와 녹화/재생
+//!
+//! `CachedProvider`는 provider, 모델, 메시지와 파라미터의 hash를 키로 응답을 디스크에 저장합니다.
+//! 저장 파일(fixture)은 요청 내용도 함께 담은 JSON이라 QA나 테스트용으로 커밋해 두고
+//! 재생 모드에서 네트워크와 API 키 없이 같은 응답을 돌려받을 수 있습니다.
+//!
+//! 모드는 환경 변수 `SCHEDULE_AI_LLM_CACHE`(`off`, `cache`, `record`, `replay`)로,
+//! 저장 위치는 `SCHEDULE_AI_LLM_CACHE_DIR`로 정합니다.
+//! 프롬프트에 들어가는 오늘 날짜는 캐시를 쓸 때 `SCHEDULE_AI_TODAY`(YYYY-MM-DD)로 고정할 수 있어
+//! 녹화한 날과 다른 날에도 같은 fixture가 재생됩니다.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::agent::{AgentRequest, AgentTurn, ContentBlock};
+use super::{DeltaCallback, LLMError, LLMProvider, LLMRequest, LLMResponse, ProviderConfig};
+
+pub const MODE_ENV: &str = "SCHEDULE_AI_LLM_CACHE";
+pub const DIR_ENV: &str = "SCHEDULE_AI_LLM_CACHE_DIR";
+pub const TODAY_ENV: &str = "SCHEDULE_AI_TODAY";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheMode {
+    /// 캐시 사용 안 함
+    #[default]
+    Off,
+    /// 저장된 응답이 있으면 재사용하고 없으면 호출 후 저장
+    Cache,
+    /// 항상 호출하고 응답을 저장 (fixture 갱신)
+    Record,
+    /// 저장된 응답만 사용. 없으면 에러 (네트워크 사용 안 함)
+    Replay,
+}
+
+impl CacheMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "off" => Some(CacheMode::Off),
+            "cache" => Some(CacheMode::Cache),
+            "record" => Some(CacheMode::Record),
+            "replay" => Some(CacheMode::Replay),
+            _ => None,
+        }
+    }
+}
+
+/// 캐시 설정 (환경 변수)
+#[derive(Debug, Clone, Default)]
+pub struct CacheConfig {
+    pub mode: CacheMode,
+    pub dir: Option<PathBuf>,
+    /// 프롬프트에 넣을 오늘 날짜 고정값 (캐시를 쓸 때만 적용)
+    pub today: Option<NaiveDate>,
+}
+
+impl CacheConfig {
+    pub fn from_env() -> Self {
+        let mode = std::env::var(MODE_ENV).ok().map(|value| {
+            CacheMode::parse(&value).unwrap_or_else(|| {
+                eprintln!("Unknown {} value \"{}\", cache disabled", MODE_ENV, value);
+                CacheMode::Off
+            })
+        });
+        let today = std::env::var(TODAY_ENV).ok().filter(|d| !d.is_empty()).and_then(|value| {
+            let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok();
+            if date.is_none() {
+                eprintln!("Invalid {} value \"{}\", using the current date", TODAY_ENV, value);
+            }
+            date
+        });
+        Self {
+            mode: mode.unwrap_or_default(),
+            dir: std::env::var_os(DIR_ENV).filter(|d| !d.is_empty()).map(PathBuf::from),
+            today,
+        }
+    }
+
+    /// 프롬프트에 넣을 오늘 날짜 (YYYY-MM-DD)
+    ///
+    /// 캐시가 꺼져 있으면 고정값을 무시하고 항상 실제 날짜를 씁니다.
+    pub fn today(&self) -> String {
+        let today = match self.today {
+            Some(date) if self.mode != CacheMode::Off => date,
+            _ => chrono::Local::now().date_naive(),
+        };
+        today.format("%Y-%m-%d").to_string()
+    }
+
+    /// 설정에 맞게 provider 생성 (캐시를 쓰면 `CachedProvider`로 감쌈)
+    ///
+    /// 재생 모드는 provider를 호출하지 않으므로 API 키가 없어도 됩니다.
+    pub fn build(
+        &self,
+        config: &ProviderConfig,
+        api_key: Option<String>,
+        default_dir: &Path,
+    ) -> Result<Arc<dyn LLMProvider>, LLMError> {
+        let api_key = match self.mode {
+            CacheMode::Replay => api_key.filter(|k| !k.is_empty()).or_else(|| Some("replay".to_string())),
+            _ => api_key,
+        };
+        let provider = config.build(api_key)?;
+        if self.mode == CacheMode::Off {
+            return Ok(provider);
+        }
+
+        let dir = self.dir.clone().unwrap_or_else(|| default_dir.to_path_buf());
+        Ok(Arc::new(CachedProvider::new(provider, dir, self.mode)))
+    }
+}
+
+/// 환경 변수 설정을 반영한 오늘 날짜 (프롬프트용)
+pub fn today() -> String {
+    CacheConfig::from_env().today()
+}
+
+/// 캐시 키를 만드는 요청 정보 (fixture에도 그대로 저장)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FixtureRequest {
+    provider: String,
+    model: String,
+    /// "complete", "structured", "tools"
+    call: String,
+    request: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool_name: Option<String>,
+}
+
+impl FixtureRequest {
+    fn key(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        format!("{:x}", Sha256::digest(bytes))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    #[serde(flatten)]
+    request: FixtureRequest,
+    response: serde_json::Value,
+}
+
+/// 응답을 디스크에 캐시하는 provider wrapper
+pub struct CachedProvider {
+    inner: Arc<dyn LLMProvider>,
+    dir: PathBuf,
+    mode: CacheMode,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Arc<dyn LLMProvider>, dir: PathBuf, mode: CacheMode) -> Self {
+        Self { inner, dir, mode }
+    }
+
+    fn fixture_request<R: Serialize>(
+        &self,
+        call: &str,
+        request: &R,
+        schema: Option<&serde_json::Value>,
+        tool_name: Option<&str>,
+    ) -> FixtureRequest {
+        FixtureRequest {
+            provider: self.inner.name().to_string(),
+            model: self.inner.model().to_string(),
+            call: call.to_string(),
+            request: serde_json::to_value(request).unwrap_or_default(),
+            schema: schema.cloned(),
+            tool_name: tool_name.map(str::to_string),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// 저장된 응답 (재생 모드에서 없으면 에러)
+    fn lookup<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Result<Option<T>, LLMError> {
+        if self.mode == CacheMode::Record {
+            return Ok(None);
+        }
+
+        let path = self.path(key);
+        let fixture = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Fixture>(&text).ok())
+            .and_then(|fixture| serde_json::from_value(fixture.response).ok());
+        match fixture {
+            Some(response) => Ok(Some(response)),
+            None if self.mode == CacheMode::Replay => Err(LLMError::ConfigError(format!(
+                "No recorded LLM response at {}",
+                path.display()
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// 응답 저장. 실패해도 호출 결과는 그대로 반환
+    fn store<T: Serialize>(&self, request: FixtureRequest, key: &str, response: &T) {
+        let fixture = Fixture {
+            request,
+            response: serde_json::to_value(response).unwrap_or_default(),
+        };
+        let written = std::fs::create_dir_all(&self.dir).and_then(|_| {
+            let text = serde_json::to_string_pretty(&fixture).map_err(std::io::Error::other)?;
+            // 동시에 같은 키를 쓰더라도 반쯤 쓴 파일이 읽히지 않게 rename
+            let temp = self.dir.join(format!("{}.{}.tmp", key, uuid::Uuid::new_v4()));
+            std::fs::write(&temp, text)?;
+            std::fs::rename(&temp, self.path(key))
+        });
+        if let Err(e) = written {
+            eprintln!("Failed to store LLM response {}: {}", key, e);
+        }
+    }
+
+    /// 캐시된 응답은 새로 쓴 토큰이 없으므로 재생 모드가 아니면 usage를 비움
+    fn hit(&self, mut response: LLMResponse) -> LLMResponse {
+        if self.mode != CacheMode::Replay {
+            response.usage = None;
+        }
+        response
+    }
+
+    async fn cached<F>(&self, request: FixtureRequest, call: F) -> Result<LLMResponse, LLMError>
+    where
+        F: std::future::Future<Output = Result<LLMResponse, LLMError>>,
+    {
+        let key = request.key();
+        if let Some(response) = self.lookup::<LLMResponse>(&key)? {
+            return Ok(self.hit(response));
+        }
+        let response = call.await?;
+        self.store(request, &key, &response);
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for CachedProvider {
+    async fn complete(&self, request: LLMRequest) -> Result<LLMResponse, LLMError> {
+        let fixture = self.fixture_request("complete", &request, None, None);
+        self.cached(fixture, self.inner.complete(request)).await
+    }
+
+    async fn complete_structured(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+    ) -> Result<LLMResponse, LLMError> {
+        let fixture = self.fixture_request("structured", &request, Some(&schema), Some(tool_name));
+        self.cached(fixture, self.inner.complete_structured(request, schema, tool_name))
+            .await
+    }
+
+    /// 캐시된 응답은 한 번에 전달
+    async fn complete_stream(
+        &self,
+        request: LLMRequest,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        let fixture = self.fixture_request("complete", &request, None, None);
+        let key = fixture.key();
+        if let Some(response) = self.lookup::<LLMResponse>(&key)? {
+            on_delta(&response.content);
+            return Ok(self.hit(response));
+        }
+        let response = self.inner.complete_stream(request, on_delta).await?;
+        self.store(fixture, &key, &response);
+        Ok(response)
+    }
+
+    async fn complete_structured_stream(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        let fixture = self.fixture_request("structured", &request, Some(&schema), Some(tool_name));
+        let key = fixture.key();
+        if let Some(response) = self.lookup::<LLMResponse>(&key)? {
+            on_delta(&response.content);
+            return Ok(self.hit(response));
+        }
+        let response = self
+            .inner
+            .complete_structured_stream(request, schema, tool_name, on_delta)
+            .await?;
+        self.store(fixture, &key, &response);
+        Ok(response)
+    }
+
+    async fn complete_with_tools(&self, request: &AgentRequest) -> Result<AgentTurn, LLMError> {
+        let fixture = self.fixture_request("tools", request, None, None);
+        let key = fixture.key();
+        if let Some(mut turn) = self.lookup::<AgentTurn>(&key)? {
+            if self.mode != CacheMode::Replay {
+                turn.usage = None;
+            }
+            return Ok(turn);
+        }
+        let mut turn = self.inner.complete_with_tools(request).await?;
+        turn.content.retain(|block| !matches!(block, ContentBlock::Other));
+        self.store(fixture, &key, &turn);
+        Ok(turn)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{ClaudeProvider, LLMMessage, LLMService};
+
+    fn request(text: &str) -> LLMRequest {
+        LLMRequest {
+            messages: vec![LLMMessage {
+                role: "user".to_string(),
+                content: text.to_string(),
+            }],
+            max_tokens: Some(100),
+            temperature: Some(0.3),
+        }
+    }
+
+    fn claude(url: String) -> Arc<dyn LLMProvider> {
+        Arc::new(
+            ClaudeProvider::new("sk-test".to_string())
+                .with_base_url(url)
+                .with_retry_policy(crate::llm::RetryPolicy {
+                    max_retries: 0,
+                    ..Default::default()
+                }),
+        )
+    }
+
+    #[test]
+    fn test_cache_mode_parse() {
+        assert_eq!(CacheMode::parse("Replay"), Some(CacheMode::Replay));
+        assert_eq!(CacheMode::parse(""), Some(CacheMode::Off));
+        assert_eq!(CacheMode::parse("sometimes"), None);
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_offline() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .with_status(200)
+            .with_body(
+                r#"{"content":[{"type":"tool_use","id":"t","name":"parse_task","input":{"title":"Gym"}}],
+                    "usage":{"input_tokens":10,"output_tokens":5}}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let schema = serde_json::json!({"type": "object"});
+        let recorder = CachedProvider::new(claude(server.url()), dir.path().to_path_buf(), CacheMode::Record);
+        let recorded = recorder
+            .complete_structured(request("gym at 6"), schema.clone(), "parse_task")
+            .await
+            .unwrap();
+        assert_eq!(recorded.content, r#"{"title":"Gym"}"#);
+        mock.assert_async().await;
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        // 재생은 서버 없이 같은 응답
+        drop(server);
+        let replay = CachedProvider::new(
+            claude("http://127.0.0.1:9".to_string()),
+            dir.path().to_path_buf(),
+            CacheMode::Replay,
+        );
+        let service = LLMService::new(Arc::new(replay));
+        let replayed = service
+            .complete_structured(request("gym at 6"), schema.clone(), "parse_task")
+            .await
+            .unwrap();
+        assert_eq!(replayed.content, recorded.content);
+        assert_eq!(replayed.usage.unwrap().input_tokens, 10);
+
+        // 요청이 다르면 키가 달라 재생할 응답이 없음
+        assert!(matches!(
+            service.complete_structured(request("gym at 7"), schema, "parse_task").await,
+            Err(LLMError::ConfigError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cache_mode_calls_provider_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .with_status(200)
+            .with_body(r#"{"content":[{"type":"text","text":"hello"}],"usage":{"input_tokens":3,"output_tokens":1}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let cached = CachedProvider::new(claude(server.url()), dir.path().to_path_buf(), CacheMode::Cache);
+        let first = cached.complete(request("hi")).await.unwrap();
+        assert!(first.usage.is_some());
+
+        // 스트리밍 요청도 같은 키를 사용
+        let deltas = std::sync::Mutex::new(Vec::new());
+        let on_delta = |delta: &str| deltas.lock().unwrap().push(delta.to_string());
+        let second = cached.complete_stream(request("hi"), &on_delta).await.unwrap();
+        assert_eq!(second.content, "hello");
+        assert!(second.usage.is_none());
+        assert_eq!(deltas.into_inner().unwrap(), vec!["hello"]);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_replay_with_pinned_today_across_days() {
+        use crate::llm::prompts::{Locale, PromptId, PromptOverrides, PromptRegistry};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .with_status(200)
+            .with_body(r#"{"content":[{"type":"text","text":"plan"}],"usage":{"input_tokens":3,"output_tokens":1}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        // parse_plan_with_ai와 같은 방식으로 오늘 날짜를 넣은 프롬프트
+        let prompts = PromptRegistry::new(Locale::En, PromptOverrides::new());
+        let plan_request = |config: &CacheConfig| {
+            let today = config.today();
+            let prompt = prompts
+                .render(
+                    PromptId::ParsePlan,
+                    &[("current_date", &today), ("plan_rules", "None"), ("plan_input", "Run a 10k")],
+                )
+                .unwrap();
+            request(&prompt)
+        };
+        let pinned = NaiveDate::from_ymd_opt(2024, 3, 1);
+
+        let record = CacheConfig {
+            mode: CacheMode::Record,
+            today: pinned,
+            ..Default::default()
+        };
+        let recorder = CachedProvider::new(claude(server.url()), dir.path().to_path_buf(), CacheMode::Record);
+        recorder.complete(plan_request(&record)).await.unwrap();
+        mock.assert_async().await;
+
+        // 녹화한 날이 지나도 같은 날짜로 고정하면 재생됨
+        drop(server);
+        let replay = CacheConfig {
+            mode: CacheMode::Replay,
+            today: pinned,
+            ..Default::default()
+        };
+        let replayer = CachedProvider::new(
+            claude("http://127.0.0.1:9".to_string()),
+            dir.path().to_path_buf(),
+            CacheMode::Replay,
+        );
+        assert_eq!(replayer.complete(plan_request(&replay)).await.unwrap().content, "plan");
+
+        // 고정하지 않으면 실제 날짜가 들어가 키가 달라짐
+        let unpinned = CacheConfig {
+            mode: CacheMode::Replay,
+            ..Default::default()
+        };
+        assert!(matches!(
+            replayer.complete(plan_request(&unpinned)).await,
+            Err(LLMError::ConfigError(_))
+        ));
+
+        // 캐시를 끄면 고정값은 무시
+        let off = CacheConfig {
+            today: pinned,
+            ..Default::default()
+        };
+        assert_eq!(off.today(), chrono::Local::now().format("%Y-%m-%d").to_string());
+    }
+
+    #[test]
+    fn test_replay_needs_no_api_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let replay = CacheConfig {
+            mode: CacheMode::Replay,
+            ..Default::default()
+        };
+        let provider = replay.build(&ProviderConfig::default(), None, dir.path()).unwrap();
+        assert_eq!(provider.name(), "claude");
+
+        assert!(CacheConfig::default()
+            .build(&ProviderConfig::default(), None, dir.path())
+            .is_err());
+    }
+}
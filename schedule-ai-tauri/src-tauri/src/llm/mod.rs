@@ -1,6 +1,27 @@
 use async_trait::async_trait;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+pub mod agent;
+pub mod cache;
+pub mod ollama;
+pub mod openai;
+pub mod prompts;
+pub mod retry;
+pub mod stream;
+pub mod structured;
+pub mod usage;
+
+pub use ollama::OllamaProvider;
+pub use openai::OpenAICompatibleProvider;
+pub use retry::RetryPolicy;
+pub use structured::{Structured, StructuredOutput};
+
+const CLAUDE_BASE_URL: &str = "https://api.anthropic.com";
+const CLAUDE_DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMMessage {
@@ -37,18 +58,248 @@ pub enum LLMError {
     ParseError(String),
     #[error("Configuration error: {0}")]
     ConfigError(String),
+    #[error("Request cancelled")]
+    Cancelled,
+    #[error("Invalid structured output: {0}")]
+    ValidationError(String),
+    #[error("Authentication failed: {0}")]
+    AuthError(String),
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        retry_after: Option<u64>,
+    },
+    #[error("API overloaded: {0}")]
+    Overloaded(String),
+    #[error("Server error ({status}): {message}")]
+    ServerError { status: u16, message: String },
+    #[error("Context too long: {0}")]
+    ContextTooLong(String),
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+    #[error("Usage budget exceeded: {0}")]
+    BudgetExceeded(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+}
+
+/// 프론트엔드가 분기할 수 있는 에러 종류
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LLMErrorKind {
+    Api,
+    Request,
+    Parse,
+    Config,
+    Cancelled,
+    Validation,
+    Auth,
+    RateLimited,
+    Overloaded,
+    Server,
+    ContextTooLong,
+    Timeout,
+    BudgetExceeded,
+    InvalidInput,
+    Database,
+}
+
+/// 커맨드 에러와 `llm-done` 이벤트로 전달되는 구조화된 에러
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LLMErrorInfo {
+    pub kind: LLMErrorKind,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+}
+
+impl LLMError {
+    pub fn kind(&self) -> LLMErrorKind {
+        match self {
+            LLMError::ApiError(_) => LLMErrorKind::Api,
+            LLMError::RequestError(_) => LLMErrorKind::Request,
+            LLMError::ParseError(_) => LLMErrorKind::Parse,
+            LLMError::ConfigError(_) => LLMErrorKind::Config,
+            LLMError::Cancelled => LLMErrorKind::Cancelled,
+            LLMError::ValidationError(_) => LLMErrorKind::Validation,
+            LLMError::AuthError(_) => LLMErrorKind::Auth,
+            LLMError::RateLimited { .. } => LLMErrorKind::RateLimited,
+            LLMError::Overloaded(_) => LLMErrorKind::Overloaded,
+            LLMError::ServerError { .. } => LLMErrorKind::Server,
+            LLMError::ContextTooLong(_) => LLMErrorKind::ContextTooLong,
+            LLMError::Timeout(_) => LLMErrorKind::Timeout,
+            LLMError::BudgetExceeded(_) => LLMErrorKind::BudgetExceeded,
+            LLMError::InvalidInput(_) => LLMErrorKind::InvalidInput,
+            LLMError::DatabaseError(_) => LLMErrorKind::Database,
+        }
+    }
+
+    /// 네트워크/API 쪽 장애로 응답을 못 받은 경우 (오프라인 처리로 대신할 수 있음)
+    ///
+    /// 예산 초과, 인증 실패, 설정 오류(replay 누락 포함)는 대신 처리하지 않고 그대로 알립니다.
+    pub fn is_unavailable(&self) -> bool {
+        matches!(
+            self,
+            LLMError::ApiError(_)
+                | LLMError::RequestError(_)
+                | LLMError::RateLimited { .. }
+                | LLMError::Overloaded(_)
+                | LLMError::ServerError { .. }
+                | LLMError::Timeout(_)
+        )
+    }
+
+    pub fn info(&self) -> LLMErrorInfo {
+        LLMErrorInfo {
+            kind: self.kind(),
+            message: self.to_string(),
+            retry_after: match self {
+                LLMError::RateLimited { retry_after, .. } => *retry_after,
+                _ => None,
+            },
+        }
+    }
 }
 
+/// Tauri 커맨드 에러로 반환할 때 `{ kind, message, retryAfter? }` 형태로 직렬화
+impl Serialize for LLMError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.info().serialize(serializer)
+    }
+}
+
+impl From<prompts::PromptError> for LLMError {
+    fn from(error: prompts::PromptError) -> Self {
+        LLMError::ConfigError(error.to_string())
+    }
+}
+
+/// 스트리밍 중 도착한 텍스트 조각을 받는 콜백
+pub type DeltaCallback<'a> = &'a (dyn Fn(&str) + Send + Sync);
+
 #[async_trait]
 pub trait LLMProvider: Send + Sync {
     async fn complete(&self, request: LLMRequest) -> Result<LLMResponse, LLMError>;
+
+    /// JSON schema를 따르는 structured output completion
+    ///
+    /// 응답의 `content`는 schema에 맞는 JSON 문자열입니다.
+    async fn complete_structured(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+    ) -> Result<LLMResponse, LLMError>;
+
+    /// 토큰 단위 스트리밍 completion
+    ///
+    /// 스트리밍을 지원하지 않는 provider는 완성된 응답을 한 번에 전달합니다.
+    async fn complete_stream(
+        &self,
+        request: LLMRequest,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        let response = self.complete(request).await?;
+        on_delta(&response.content);
+        Ok(response)
+    }
+
+    /// structured output 스트리밍 (delta는 부분 JSON 문자열)
+    async fn complete_structured_stream(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        let response = self.complete_structured(request, schema, tool_name).await?;
+        on_delta(&response.content);
+        Ok(response)
+    }
+
+    /// 도구를 제공한 completion (모델이 도구 호출 여부를 결정)
+    async fn complete_with_tools(&self, _request: &agent::AgentRequest) -> Result<agent::AgentTurn, LLMError> {
+        Err(LLMError::ConfigError(format!(
+            "Tool use is not supported by the {} provider",
+            self.name()
+        )))
+    }
+
     fn name(&self) -> &str;
+    fn model(&self) -> &str;
 }
 
 pub struct ClaudeProvider {
     client: reqwest::Client,
     api_key: String,
     model: String,
+    base_url: String,
+    retry: RetryPolicy,
+}
+
+/// Anthropic Messages API 요청 본문
+#[derive(Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<&'a LLMMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<Tool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<ToolChoice>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+/// 에이전트(tool use) 요청 본문
+#[derive(Serialize)]
+struct ToolsRequest<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<&'a str>,
+    messages: &'a [agent::AgentMessage],
+    tools: &'a [agent::ToolSpec],
+}
+
+#[derive(Serialize)]
+struct Tool {
+    name: String,
+    description: String,
+    input_schema: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct ToolChoice {
+    #[serde(rename = "type")]
+    choice_type: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+    usage: Option<TokenUsage>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum ContentBlock {
+    #[serde(rename = "text")]
+    Text { text: String },
+    #[serde(rename = "tool_use")]
+    ToolUse { input: serde_json::Value },
+    #[serde(other)]
+    Other,
 }
 
 impl ClaudeProvider {
@@ -56,222 +307,853 @@ impl ClaudeProvider {
         Self {
             client: reqwest::Client::new(),
             api_key,
-            model: "claude-sonnet-4-20250514".to_string(),
+            model: CLAUDE_DEFAULT_MODEL.to_string(),
+            base_url: CLAUDE_BASE_URL.to_string(),
+            retry: RetryPolicy::default(),
         }
     }
 
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     pub fn with_model(mut self, model: String) -> Self {
         self.model = model;
         self
     }
 
-    /// Tool use 기반 structured output completion
-    pub async fn complete_structured(
-        &self,
-        messages: Vec<LLMMessage>,
-        schema: serde_json::Value,
-        tool_name: &str,
-    ) -> Result<String, LLMError> {
-        #[derive(Serialize)]
-        struct AnthropicRequest {
-            model: String,
-            max_tokens: u32,
-            messages: Vec<AnthropicMessage>,
-            tools: Vec<Tool>,
-            tool_choice: ToolChoice,
-        }
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
 
-        #[derive(Serialize)]
-        struct AnthropicMessage {
-            role: String,
-            content: String,
-        }
+    fn messages_request<'a>(
+        &'a self,
+        request: &'a LLMRequest,
+        default_max_tokens: u32,
+        tool: Option<(serde_json::Value, &str)>,
+        stream: bool,
+    ) -> MessagesRequest<'a> {
+        let (tools, tool_choice) = match tool {
+            Some((schema, tool_name)) => (
+                Some(vec![Tool {
+                    name: tool_name.to_string(),
+                    description: format!("Generate structured {} response", tool_name),
+                    input_schema: schema,
+                }]),
+                Some(ToolChoice {
+                    choice_type: "tool".to_string(),
+                    name: tool_name.to_string(),
+                }),
+            ),
+            None => (None, None),
+        };
 
-        #[derive(Serialize)]
-        struct Tool {
-            name: String,
-            description: String,
-            input_schema: serde_json::Value,
-        }
+        // Messages API는 system prompt를 메시지가 아닌 최상위 필드로 받음
+        let (system, messages): (Vec<&LLMMessage>, Vec<&LLMMessage>) =
+            request.messages.iter().partition(|m| m.role == "system");
+        let system = (!system.is_empty()).then(|| {
+            system
+                .iter()
+                .map(|m| m.content.as_str())
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        });
 
-        #[derive(Serialize)]
-        struct ToolChoice {
-            #[serde(rename = "type")]
-            choice_type: String,
-            name: String,
+        MessagesRequest {
+            model: &self.model,
+            max_tokens: request.max_tokens.unwrap_or(default_max_tokens),
+            temperature: request.temperature,
+            system,
+            messages,
+            tools,
+            tool_choice,
+            stream,
         }
+    }
 
-        let anthropic_messages: Vec<AnthropicMessage> = messages
-            .into_iter()
-            .map(|m| AnthropicMessage {
-                role: m.role,
-                content: m.content,
-            })
-            .collect();
-
-        let tool = Tool {
-            name: tool_name.to_string(),
-            description: format!("Generate structured {} response", tool_name),
-            input_schema: schema,
-        };
+    /// 요청을 보내고 응답 본문까지 JSON으로 읽음 (timeout은 본문 읽기까지 포함)
+    async fn send_json<B, T>(&self, body: &B) -> Result<T, LLMError>
+    where
+        B: Serialize + Sync,
+        T: serde::de::DeserializeOwned,
+    {
+        retry::with_retry(&self.retry, move || async move {
+            let read = async {
+                self.send_once(body)
+                    .await?
+                    .json::<T>()
+                    .await
+                    .map_err(|e| LLMError::ParseError(e.to_string()))
+            };
+            tokio::time::timeout(self.retry.timeout, read)
+                .await
+                .map_err(|_| LLMError::Timeout(self.retry.timeout))?
+        })
+        .await
+    }
 
-        let anthropic_request = AnthropicRequest {
-            model: self.model.clone(),
-            max_tokens: 1024,
-            messages: anthropic_messages,
-            tools: vec![tool],
-            tool_choice: ToolChoice {
-                choice_type: "tool".to_string(),
-                name: tool_name.to_string(),
-            },
-        };
+    /// 스트리밍 요청 전송. timeout은 응답 헤더를 받을 때까지 (본문은 `send_stream`에서 청크마다)
+    async fn send_stream_request<B: Serialize + Sync>(&self, body: &B) -> Result<reqwest::Response, LLMError> {
+        retry::with_retry(&self.retry, move || async move {
+            tokio::time::timeout(self.retry.timeout, self.send_once(body))
+                .await
+                .map_err(|_| LLMError::Timeout(self.retry.timeout))?
+        })
+        .await
+    }
 
+    async fn send_once<B: Serialize + Sync>(&self, body: &B) -> Result<reqwest::Response, LLMError> {
         let response = self
             .client
-            .post("https://api.anthropic.com/v1/messages")
+            .post(format!("{}/v1/messages", self.base_url))
             .header("x-api-key", &self.api_key)
             .header("anthropic-version", "2023-06-01")
             .header("content-type", "application/json")
-            .json(&anthropic_request)
+            .json(body)
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(LLMError::ApiError(error_text));
-        }
+        retry::check_status(response).await
+    }
 
-        // Parse response with tool_use content block
-        #[derive(Deserialize)]
-        struct AnthropicResponse {
-            content: Vec<ContentBlock>,
-        }
+    /// SSE 응답을 읽으며 delta를 전달하고 최종 응답을 조립
+    async fn send_stream(
+        &self,
+        body: &MessagesRequest<'_>,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        let response = self.send_stream_request(body).await?;
 
-        #[derive(Deserialize)]
-        #[serde(tag = "type")]
-        enum ContentBlock {
-            #[serde(rename = "tool_use")]
-            ToolUse { input: serde_json::Value },
-            #[serde(rename = "text")]
-            Text { text: String },
-        }
+        let mut parser = stream::SseParser::default();
+        let mut state = stream::AnthropicStreamState::default();
+        let mut chunks = response.bytes_stream();
 
-        let anthropic_response: AnthropicResponse = response
-            .json()
+        // 청크 사이가 timeout보다 길면 연결이 멈춘 것으로 봄
+        while let Some(chunk) = tokio::time::timeout(self.retry.timeout, chunks.next())
             .await
-            .map_err(|e| LLMError::ParseError(e.to_string()))?;
-
-        // Extract tool_use input
-        for block in anthropic_response.content {
-            if let ContentBlock::ToolUse { input } = block {
-                return Ok(input.to_string());
+            .map_err(|_| LLMError::Timeout(self.retry.timeout))?
+        {
+            for event in parser.push(&chunk?) {
+                if let Some(delta) = state.apply(&event)? {
+                    on_delta(&delta);
+                }
+            }
+            if state.is_finished() {
+                break;
             }
         }
 
-        Err(LLMError::ParseError("No tool_use block in response".to_string()))
+        state.into_response(body.tool_choice.is_some())
     }
 }
 
 #[async_trait]
 impl LLMProvider for ClaudeProvider {
     async fn complete(&self, request: LLMRequest) -> Result<LLMResponse, LLMError> {
-        #[derive(Serialize)]
-        struct AnthropicRequest {
-            model: String,
-            max_tokens: u32,
-            messages: Vec<AnthropicMessage>,
-        }
+        let body = self.messages_request(&request, 4096, None, false);
+        let response: MessagesResponse = self.send_json(&body).await?;
 
-        #[derive(Serialize)]
-        struct AnthropicMessage {
-            role: String,
-            content: String,
-        }
+        let content = response
+            .content
+            .into_iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("");
 
-        #[derive(Deserialize)]
-        struct AnthropicResponse {
-            content: Vec<ContentBlock>,
-            usage: Usage,
-        }
+        Ok(LLMResponse {
+            content,
+            usage: response.usage,
+        })
+    }
 
-        #[derive(Deserialize)]
-        struct ContentBlock {
-            text: String,
-        }
+    /// Tool use 기반 structured output completion
+    async fn complete_structured(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+    ) -> Result<LLMResponse, LLMError> {
+        let body = self.messages_request(&request, 1024, Some((schema, tool_name)), false);
+        let response: MessagesResponse = self.send_json(&body).await?;
 
-        #[derive(Deserialize)]
-        struct Usage {
-            input_tokens: u32,
-            output_tokens: u32,
+        // Extract tool_use input
+        for block in response.content {
+            if let ContentBlock::ToolUse { input } = block {
+                return Ok(LLMResponse {
+                    content: input.to_string(),
+                    usage: response.usage,
+                });
+            }
         }
 
-        let anthropic_messages: Vec<AnthropicMessage> = request
-            .messages
-            .into_iter()
-            .map(|m| AnthropicMessage {
-                role: m.role,
-                content: m.content,
-            })
-            .collect();
+        Err(LLMError::ParseError("No tool_use block in response".to_string()))
+    }
+
+    async fn complete_stream(
+        &self,
+        request: LLMRequest,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        let body = self.messages_request(&request, 4096, None, true);
+        self.send_stream(&body, on_delta).await
+    }
 
-        let anthropic_request = AnthropicRequest {
-            model: self.model.clone(),
+    async fn complete_structured_stream(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        let body = self.messages_request(&request, 1024, Some((schema, tool_name)), true);
+        self.send_stream(&body, on_delta).await
+    }
+
+    async fn complete_with_tools(&self, request: &agent::AgentRequest) -> Result<agent::AgentTurn, LLMError> {
+        let body = ToolsRequest {
+            model: &self.model,
             max_tokens: request.max_tokens.unwrap_or(4096),
-            messages: anthropic_messages,
+            temperature: request.temperature,
+            system: request.system.as_deref(),
+            messages: &request.messages,
+            tools: &request.tools,
         };
+        self.send_json(&body).await
+    }
 
-        let response = self
-            .client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&anthropic_request)
-            .send()
-            .await?;
+    fn name(&self) -> &str {
+        "claude"
+    }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(LLMError::ApiError(error_text));
-        }
+    fn model(&self) -> &str {
+        &self.model
+    }
+}
 
-        let anthropic_response: AnthropicResponse = response
-            .json()
-            .await
-            .map_err(|e| LLMError::ParseError(e.to_string()))?;
+/// 사용할 LLM 백엔드 종류
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    #[default]
+    Claude,
+    #[serde(rename = "openai")]
+    OpenAI,
+    Ollama,
+}
 
-        let content = anthropic_response
-            .content
-            .into_iter()
-            .map(|c| c.text)
-            .collect::<Vec<_>>()
-            .join("");
+/// 저장되는 provider 선택 (settings.json의 `llm_provider`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderConfig {
+    pub kind: ProviderKind,
+    pub base_url: Option<String>,
+    pub model: Option<String>,
+    /// secrets(`llm_provider_api_key`)에 저장되고 settings.json에는 남기지 않음
+    pub api_key: Option<String>,
+    /// 요청 timeout (초)
+    pub timeout_secs: Option<u64>,
+    /// 재시도 횟수
+    pub max_retries: Option<u32>,
+}
 
-        Ok(LLMResponse {
-            content,
-            usage: Some(TokenUsage {
-                input_tokens: anthropic_response.usage.input_tokens,
-                output_tokens: anthropic_response.usage.output_tokens,
-            }),
-        })
+impl ProviderConfig {
+    /// 설정값을 반영한 재시도 정책
+    pub fn retry_policy(&self) -> RetryPolicy {
+        let mut policy = RetryPolicy::default();
+        if let Some(seconds) = self.timeout_secs.filter(|s| *s > 0) {
+            policy.timeout = Duration::from_secs(seconds);
+        }
+        if let Some(max_retries) = self.max_retries {
+            policy.max_retries = max_retries;
+        }
+        policy
     }
 
-    fn name(&self) -> &str {
-        "claude"
+    /// 설정으로 provider 생성
+    ///
+    /// Claude는 설정에 키가 없으면 기존 API 키(`claude_api_key`)를 사용합니다.
+    pub fn build(&self, claude_api_key: Option<String>) -> Result<Arc<dyn LLMProvider>, LLMError> {
+        let base_url = self.base_url.clone().filter(|u| !u.trim().is_empty());
+        let model = self.model.clone().filter(|m| !m.trim().is_empty());
+        let api_key = self.api_key.clone().filter(|k| !k.is_empty());
+
+        match self.kind {
+            ProviderKind::Claude => {
+                let key = api_key
+                    .or(claude_api_key.filter(|k| !k.is_empty()))
+                    .ok_or_else(|| LLMError::ConfigError("API key not set".to_string()))?;
+                let mut provider = ClaudeProvider::new(key).with_retry_policy(self.retry_policy());
+                if let Some(url) = base_url {
+                    provider = provider.with_base_url(url);
+                }
+                if let Some(model) = model {
+                    provider = provider.with_model(model);
+                }
+                Ok(Arc::new(provider))
+            }
+            ProviderKind::OpenAI => {
+                let mut provider = OpenAICompatibleProvider::new(api_key).with_retry_policy(self.retry_policy());
+                if let Some(url) = base_url {
+                    provider = provider.with_base_url(url);
+                }
+                if let Some(model) = model {
+                    provider = provider.with_model(model);
+                }
+                Ok(Arc::new(provider))
+            }
+            ProviderKind::Ollama => {
+                let mut provider = OllamaProvider::new().with_retry_policy(self.retry_policy());
+                if let Some(url) = base_url {
+                    provider = provider.with_base_url(url);
+                }
+                if let Some(model) = model {
+                    provider = provider.with_model(model);
+                }
+                Ok(Arc::new(provider))
+            }
+        }
     }
 }
 
 pub struct LLMService {
     provider: Arc<dyn LLMProvider>,
+    ledger: Option<usage::UsageLedger>,
 }
 
 impl LLMService {
     pub fn new(provider: Arc<dyn LLMProvider>) -> Self {
-        Self { provider }
+        Self {
+            provider,
+            ledger: None,
+        }
+    }
+
+    /// 모든 provider 호출을 사용량 ledger에 기록
+    pub fn with_ledger(mut self, ledger: usage::UsageLedger) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// 호출 전에 월간 예산을 확인하고, provider 호출의 지연시간과 결과를 ledger에 남김
+    async fn tracked<T, F>(&self, call: F) -> Result<T, LLMError>
+    where
+        T: stream::UsageReport,
+        F: Future<Output = Result<T, LLMError>>,
+    {
+        if let Some(ledger) = &self.ledger {
+            ledger.check_budget().await?;
+        }
+        let started = Instant::now();
+        let result = call.await;
+        if let Some(ledger) = &self.ledger {
+            ledger
+                .record(self.provider.name(), self.provider.model(), &result, started.elapsed())
+                .await;
+        }
+        result
     }
 
     pub async fn complete(&self, request: LLMRequest) -> Result<LLMResponse, LLMError> {
-        self.provider.complete(request).await
+        self.tracked(self.provider.complete(request)).await
+    }
+
+    pub async fn complete_structured(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+    ) -> Result<LLMResponse, LLMError> {
+        self.tracked(self.provider.complete_structured(request, schema, tool_name))
+            .await
+    }
+
+    pub async fn complete_stream(
+        &self,
+        request: LLMRequest,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        self.tracked(self.provider.complete_stream(request, on_delta)).await
+    }
+
+    pub async fn complete_with_tools(&self, request: &agent::AgentRequest) -> Result<agent::AgentTurn, LLMError> {
+        self.tracked(self.provider.complete_with_tools(request)).await
+    }
+
+    pub async fn complete_structured_stream(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+        on_delta: DeltaCallback<'_>,
+    ) -> Result<LLMResponse, LLMError> {
+        self.tracked(
+            self.provider
+                .complete_structured_stream(request, schema, tool_name, on_delta),
+        )
+        .await
+    }
+
+    /// 응답 타입 `T`의 schema로 structured output을 요청하고 검증된 값을 반환
+    ///
+    /// 파싱/검증에 실패하면 오류를 모델에게 알려주고 `structured::MAX_RETRIES`번 재시도합니다.
+    /// `on_delta`가 있으면 스트리밍으로 요청합니다.
+    pub async fn complete_typed<T: StructuredOutput>(
+        &self,
+        mut request: LLMRequest,
+        on_delta: Option<DeltaCallback<'_>>,
+    ) -> Result<Structured<T>, LLMError> {
+        let schema = structured::schema_for::<T>();
+        let mut usage = None;
+        let mut attempt = 0;
+
+        loop {
+            let response = match on_delta {
+                Some(on_delta) => {
+                    self.complete_structured_stream(request.clone(), schema.clone(), T::TOOL_NAME, on_delta)
+                        .await?
+                }
+                None => {
+                    self.complete_structured(request.clone(), schema.clone(), T::TOOL_NAME)
+                        .await?
+                }
+            };
+            structured::add_usage(&mut usage, response.usage);
+
+            match structured::parse::<T>(&response.content) {
+                Ok(value) => return Ok(Structured { value, usage }),
+                Err(error) if attempt < structured::MAX_RETRIES => {
+                    attempt += 1;
+                    request = structured::retry_request(request, response.content, &error, T::TOOL_NAME);
+                }
+                Err(error) => return Err(LLMError::ValidationError(error)),
+            }
+        }
     }
 }
 
+/// 모델 응답에서 마크다운 코드블록(```json ... ```)을 제거
+pub(crate) fn strip_code_fence(content: &str) -> &str {
+    let content = content.trim();
+    if content.starts_with("```") {
+        content
+            .trim_start_matches("```json")
+            .trim_start_matches("```")
+            .trim_end_matches("```")
+            .trim()
+    } else {
+        content
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn request(content: &str) -> LLMRequest {
+        LLMRequest {
+            messages: vec![LLMMessage {
+                role: "user".to_string(),
+                content: content.to_string(),
+            }],
+            max_tokens: Some(256),
+            temperature: Some(0.2),
+        }
+    }
+
+    #[test]
+    fn test_strip_code_fence() {
+        assert_eq!(strip_code_fence("```json\n{\"a\":1}\n```"), r#"{"a":1}"#);
+        assert_eq!(strip_code_fence("  {\"a\":1} "), r#"{"a":1}"#);
+    }
+
+    #[test]
+    fn test_provider_config_build() {
+        let claude = ProviderConfig::default();
+        assert!(matches!(claude.build(None), Err(LLMError::ConfigError(_))));
+        let provider = claude.build(Some("sk-ant".to_string())).unwrap();
+        assert_eq!(provider.name(), "claude");
+        assert_eq!(provider.model(), CLAUDE_DEFAULT_MODEL);
+
+        let ollama = ProviderConfig {
+            kind: ProviderKind::Ollama,
+            base_url: Some("http://127.0.0.1:11434".to_string()),
+            model: Some("qwen2.5".to_string()),
+            api_key: None,
+            ..ProviderConfig::default()
+        };
+        let provider = ollama.build(None).unwrap();
+        assert_eq!(provider.name(), "ollama");
+        assert_eq!(provider.model(), "qwen2.5");
+
+        let config: ProviderConfig =
+            serde_json::from_str(r#"{"kind":"openai","baseUrl":"http://localhost:8080/v1","model":"","apiKey":null}"#)
+                .unwrap();
+        assert_eq!(config.kind, ProviderKind::OpenAI);
+        assert_eq!(config.build(None).unwrap().model(), "gpt-4o-mini");
+    }
+
+    #[tokio::test]
+    async fn test_claude_structured_uses_forced_tool_choice() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .match_header("x-api-key", "sk-test")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "max_tokens": 256,
+                "tool_choice": {"type": "tool", "name": "focus_insight"}
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"content":[{"type":"text","text":"ok"},
+                    {"type":"tool_use","id":"t1","name":"focus_insight","input":{"insight":"x"}}],
+                    "usage":{"input_tokens":10,"output_tokens":5}}"#,
+            )
+            .create_async()
+            .await;
+
+        let service = LLMService::new(Arc::new(
+            ClaudeProvider::new("sk-test".to_string()).with_base_url(server.url()),
+        ));
+        let response = service
+            .complete_structured(request("stats"), serde_json::json!({"type": "object"}), "focus_insight")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.content, r#"{"insight":"x"}"#);
+        assert_eq!(response.usage.unwrap().output_tokens, 5);
+    }
+
+    #[tokio::test]
+    async fn test_claude_moves_system_messages_to_top_level() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "system": "You plan schedules.",
+                "messages": [
+                    {"role": "user", "content": "Hi"},
+                    {"role": "assistant", "content": "Hello"},
+                    {"role": "user", "content": "Move gym"}
+                ]
+            })))
+            .with_status(200)
+            .with_body(r#"{"content":[{"type":"text","text":"Done"}]}"#)
+            .create_async()
+            .await;
+
+        let message = |role: &str, content: &str| LLMMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+        };
+        let request = LLMRequest {
+            messages: vec![
+                message("system", "You plan schedules."),
+                message("user", "Hi"),
+                message("assistant", "Hello"),
+                message("user", "Move gym"),
+            ],
+            max_tokens: None,
+            temperature: None,
+        };
+        let provider = ClaudeProvider::new("sk-test".to_string()).with_base_url(server.url());
+        assert_eq!(provider.complete(request).await.unwrap().content, "Done");
+        mock.assert_async().await;
+    }
+
+    #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+    struct Minutes {
+        minutes: i32,
+    }
+
+    impl StructuredOutput for Minutes {
+        const TOOL_NAME: &'static str = "minutes";
+
+        fn validate(&self) -> Result<(), String> {
+            structured::check_range("minutes", Some(self.minutes), 1, 120)
+        }
+    }
+
+    fn tool_use_body(input: &str, output_tokens: u32) -> String {
+        format!(
+            r#"{{"content":[{{"type":"tool_use","id":"t1","name":"minutes","input":{}}}],
+                "usage":{{"input_tokens":10,"output_tokens":{}}}}}"#,
+            input, output_tokens
+        )
+    }
+
+    #[tokio::test]
+    async fn test_complete_typed_retries_with_validation_error() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("POST", "/v1/messages")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "tools": [{"name": "minutes", "input_schema": {"required": ["minutes"]}}]
+            })))
+            .with_status(200)
+            .with_body(tool_use_body(r#"{"minutes":0}"#, 3))
+            .expect(1)
+            .create_async()
+            .await;
+        let retry = server
+            .mock("POST", "/v1/messages")
+            .match_body(Matcher::Regex("minutes must be between 1 and 120".to_string()))
+            .with_status(200)
+            .with_body(tool_use_body(r#"{"minutes":15}"#, 4))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let service = LLMService::new(Arc::new(
+            ClaudeProvider::new("sk-test".to_string()).with_base_url(server.url()),
+        ));
+        let result = service
+            .complete_typed::<Minutes>(request("15분"), None)
+            .await
+            .unwrap();
+
+        first.assert_async().await;
+        retry.assert_async().await;
+        assert_eq!(result.value.minutes, 15);
+        let usage = result.usage.unwrap();
+        assert_eq!(usage.input_tokens, 20);
+        assert_eq!(usage.output_tokens, 7);
+    }
+
+    #[tokio::test]
+    async fn test_complete_typed_gives_up_after_retry() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .with_status(200)
+            .with_body(tool_use_body(r#"{"minutes":"ten"}"#, 3))
+            .expect(2)
+            .create_async()
+            .await;
+
+        let service = LLMService::new(Arc::new(
+            ClaudeProvider::new("sk-test".to_string()).with_base_url(server.url()),
+        ));
+        let err = service
+            .complete_typed::<Minutes>(request("10분"), None)
+            .await
+            .unwrap_err();
+
+        mock.assert_async().await;
+        assert!(matches!(err, LLMError::ValidationError(_)));
+    }
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy {
+            timeout: Duration::from_secs(5),
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claude_retries_rate_limit_honoring_retry_after() {
+        let mut server = mockito::Server::new_async().await;
+        let limited = server
+            .mock("POST", "/v1/messages")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .with_body(r#"{"type":"error","error":{"type":"rate_limit_error","message":"slow down"}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("POST", "/v1/messages")
+            .with_status(200)
+            .with_body(r#"{"content":[{"type":"text","text":"hi"}]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let provider = ClaudeProvider::new("sk-test".to_string())
+            .with_base_url(server.url())
+            .with_retry_policy(fast_retry());
+        let response = provider.complete(request("hi")).await.unwrap();
+
+        limited.assert_async().await;
+        ok.assert_async().await;
+        assert_eq!(response.content, "hi");
+    }
+
+    #[tokio::test]
+    async fn test_claude_gives_up_when_overloaded() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .with_status(529)
+            .with_body(r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let provider = ClaudeProvider::new("sk-test".to_string())
+            .with_base_url(server.url())
+            .with_retry_policy(fast_retry());
+        let err = provider.complete(request("hi")).await.unwrap_err();
+
+        mock.assert_async().await;
+        assert!(matches!(err, LLMError::Overloaded(_)));
+    }
+
+    #[tokio::test]
+    async fn test_claude_auth_error_is_not_retried() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .with_status(401)
+            .with_body(r#"{"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let provider = ClaudeProvider::new("sk-bad".to_string())
+            .with_base_url(server.url())
+            .with_retry_policy(fast_retry());
+        let err = provider.complete(request("hi")).await.unwrap_err();
+
+        mock.assert_async().await;
+        assert_eq!(err.kind(), LLMErrorKind::Auth);
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["kind"], "auth");
+        assert_eq!(json["message"], "Authentication failed: invalid x-api-key");
+        assert!(json.get("retryAfter").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_claude_request_timeout() {
+        // 연결은 받지만 응답하지 않는 서버
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hold = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+
+        let provider = ClaudeProvider::new("sk-test".to_string())
+            .with_base_url(url)
+            .with_retry_policy(RetryPolicy {
+                timeout: Duration::from_millis(100),
+                max_retries: 1,
+                ..fast_retry()
+            });
+        let err = provider.complete(request("hi")).await.unwrap_err();
+        hold.abort();
+
+        assert!(matches!(err, LLMError::Timeout(d) if d == Duration::from_millis(100)));
+        assert_eq!(err.to_string(), "Request timed out after 100ms");
+    }
+
+    #[tokio::test]
+    async fn test_claude_body_read_timeout() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 헤더만 보내고 본문을 끝내지 않는 서버
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hold = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 100\r\n\r\n{")
+                    .await;
+                sockets.push(socket);
+            }
+        });
+
+        let provider = ClaudeProvider::new("sk-test".to_string())
+            .with_base_url(url)
+            .with_retry_policy(RetryPolicy {
+                timeout: Duration::from_millis(100),
+                max_retries: 0,
+                ..fast_retry()
+            });
+        let err = provider.complete(request("hi")).await.unwrap_err();
+        hold.abort();
+
+        assert!(matches!(err, LLMError::Timeout(_)));
+    }
+
+    #[tokio::test]
+    async fn test_claude_stream_idle_timeout() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 첫 이벤트만 보내고 스트림을 멈추는 서버
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hold = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\n\
+                          event: message_start\ndata: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":3}}}\n\n",
+                    )
+                    .await;
+                sockets.push(socket);
+            }
+        });
+
+        let provider = ClaudeProvider::new("sk-test".to_string())
+            .with_base_url(url)
+            .with_retry_policy(RetryPolicy {
+                timeout: Duration::from_millis(100),
+                max_retries: 0,
+                ..fast_retry()
+            });
+        let err = provider
+            .complete_stream(request("hi"), &|_| {})
+            .await
+            .unwrap_err();
+        hold.abort();
+
+        assert!(matches!(err, LLMError::Timeout(_)));
+    }
+
+    #[test]
+    fn test_rate_limited_error_serialization() {
+        let err = LLMError::RateLimited {
+            message: "slow down".to_string(),
+            retry_after: Some(30),
+        };
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            serde_json::json!({"kind": "rateLimited", "message": "Rate limited: slow down", "retryAfter": 30})
+        );
+
+        let config: ProviderConfig =
+            serde_json::from_str(r#"{"kind":"claude","timeoutSecs":10,"maxRetries":0}"#).unwrap();
+        let policy = config.retry_policy();
+        assert_eq!(policy.timeout, Duration::from_secs(10));
+        assert_eq!(policy.max_retries, 0);
+    }
+
+    #[test]
+    fn test_unavailable_errors() {
+        assert!(LLMError::Timeout(Duration::from_secs(1)).is_unavailable());
+        assert!(LLMError::Overloaded("busy".to_string()).is_unavailable());
+        assert!(LLMError::ServerError { status: 502, message: String::new() }.is_unavailable());
+        assert!(!LLMError::BudgetExceeded("over".to_string()).is_unavailable());
+        assert!(!LLMError::AuthError("bad key".to_string()).is_unavailable());
+        assert!(!LLMError::ConfigError("replay miss".to_string()).is_unavailable());
+        assert!(!LLMError::Cancelled.is_unavailable());
+    }
+}
@@ -0,0 +1,262 @@
+//! 로컬 Ollama 서버 provider (`/api/chat`)
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::retry::{self, RetryPolicy};
+use super::{strip_code_fence, LLMError, LLMMessage, LLMProvider, LLMRequest, LLMResponse, TokenUsage};
+
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+const DEFAULT_MODEL: &str = "llama3.1";
+
+pub struct OllamaProvider {
+    client: reqwest::Client,
+    model: String,
+    base_url: String,
+    retry: RetryPolicy,
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [LLMMessage],
+    stream: bool,
+    options: ChatOptions,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<&'a serde_json::Value>,
+}
+
+#[derive(Serialize)]
+struct ChatOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    message: ResponseMessage,
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+impl OllamaProvider {
+    pub fn new() -> Self {
+        let retry = RetryPolicy::default();
+        Self {
+            client: retry::http_client(&retry),
+            model: DEFAULT_MODEL.to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            retry,
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.client = retry::http_client(&retry);
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_model(mut self, model: String) -> Self {
+        self.model = model;
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    async fn chat(
+        &self,
+        request: &LLMRequest,
+        format: Option<serde_json::Value>,
+    ) -> Result<LLMResponse, LLMError> {
+        let body = ChatRequest {
+            model: &self.model,
+            messages: &request.messages,
+            stream: false,
+            options: ChatOptions {
+                temperature: request.temperature,
+                num_predict: request.max_tokens,
+            },
+            format: format.as_ref(),
+        };
+        let body = &body;
+
+        retry::with_retry(&self.retry, move || async move { self.chat_once(body).await }).await
+    }
+
+    async fn chat_once(&self, body: &ChatRequest<'_>) -> Result<LLMResponse, LLMError> {
+        let response = self
+            .client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| retry::from_reqwest(e, &self.retry))?;
+        let chat_response: ChatResponse = retry::check_status(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| retry::from_reqwest(e, &self.retry))?;
+
+        let usage = match (chat_response.prompt_eval_count, chat_response.eval_count) {
+            (Some(input_tokens), Some(output_tokens)) => Some(TokenUsage {
+                input_tokens,
+                output_tokens,
+            }),
+            _ => None,
+        };
+
+        Ok(LLMResponse {
+            content: chat_response.message.content,
+            usage,
+        })
+    }
+}
+
+impl Default for OllamaProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OllamaProvider {
+    async fn complete(&self, request: LLMRequest) -> Result<LLMResponse, LLMError> {
+        self.chat(&request, None).await
+    }
+
+    /// Ollama의 `format` 필드에 JSON schema를 전달하는 structured output
+    async fn complete_structured(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        _tool_name: &str,
+    ) -> Result<LLMResponse, LLMError> {
+        let mut response = self.chat(&request, Some(schema)).await?;
+        response.content = strip_code_fence(&response.content).to_string();
+        Ok(response)
+    }
+
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn request(content: &str) -> LLMRequest {
+        LLMRequest {
+            messages: vec![LLMMessage {
+                role: "user".to_string(),
+                content: content.to_string(),
+            }],
+            max_tokens: Some(128),
+            temperature: Some(0.3),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_against_stub_server() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/chat")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "model": "qwen2.5",
+                "stream": false,
+                "options": {"num_predict": 128}
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"model":"qwen2.5","message":{"role":"assistant","content":"안녕하세요"},
+                    "done":true,"prompt_eval_count":12,"eval_count":4}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = OllamaProvider::new()
+            .with_base_url(server.url())
+            .with_model("qwen2.5".to_string());
+
+        let response = provider.complete(request("안녕")).await.unwrap();
+        mock.assert_async().await;
+        assert_eq!(response.content, "안녕하세요");
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.input_tokens, 12);
+        assert_eq!(usage.output_tokens, 4);
+    }
+
+    #[tokio::test]
+    async fn test_complete_structured_passes_schema_as_format() {
+        let mut server = mockito::Server::new_async().await;
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {"title": {"type": "string"}},
+            "required": ["title"]
+        });
+        let mock = server
+            .mock("POST", "/api/chat")
+            .match_body(Matcher::PartialJson(serde_json::json!({ "format": schema })))
+            .with_status(200)
+            .with_body(r#"{"message":{"content":"{\"title\":\"운동\"}"},"done":true}"#)
+            .create_async()
+            .await;
+
+        let provider = OllamaProvider::new().with_base_url(server.url());
+        let response = provider
+            .complete_structured(request("운동"), schema, "parse_task")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.content, r#"{"title":"운동"}"#);
+        assert!(response.usage.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stalled_server_times_out() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // 헤더만 보내고 본문을 끝내지 않는 서버
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hold = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 100\r\n\r\n{")
+                    .await;
+                sockets.push(socket);
+            }
+        });
+
+        let provider = OllamaProvider::new()
+            .with_base_url(url)
+            .with_retry_policy(RetryPolicy {
+                timeout: std::time::Duration::from_millis(100),
+                max_retries: 0,
+                ..RetryPolicy::default()
+            });
+        let err = provider.complete(request("안녕")).await.unwrap_err();
+        hold.abort();
+
+        assert!(matches!(err, LLMError::Timeout(_)));
+    }
+}
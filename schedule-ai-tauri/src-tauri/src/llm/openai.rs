@@ -0,0 +1,286 @@
+//! OpenAI 호환 chat completions provider
+//!
+//! OpenAI 뿐 아니라 vLLM, LM Studio, llama.cpp server 등
+//! `/chat/completions` 엔드포인트를 제공하는 서버에서 사용할 수 있습니다.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::retry::{self, RetryPolicy};
+use super::{strip_code_fence, LLMError, LLMMessage, LLMProvider, LLMRequest, LLMResponse, TokenUsage};
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+pub struct OpenAICompatibleProvider {
+    client: reqwest::Client,
+    api_key: Option<String>,
+    model: String,
+    base_url: String,
+    retry: RetryPolicy,
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [LLMMessage],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<&'a serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+#[derive(Deserialize)]
+struct ChoiceMessage {
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Usage {
+    prompt_tokens: u32,
+    completion_tokens: u32,
+}
+
+impl OpenAICompatibleProvider {
+    pub fn new(api_key: Option<String>) -> Self {
+        let retry = RetryPolicy::default();
+        Self {
+            client: retry::http_client(&retry),
+            api_key,
+            model: DEFAULT_MODEL.to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            retry,
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.client = retry::http_client(&retry);
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_model(mut self, model: String) -> Self {
+        self.model = model;
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    async fn chat(
+        &self,
+        request: &LLMRequest,
+        response_format: Option<serde_json::Value>,
+    ) -> Result<LLMResponse, LLMError> {
+        let body = ChatRequest {
+            model: &self.model,
+            messages: &request.messages,
+            max_tokens: request.max_tokens,
+            temperature: request.temperature,
+            response_format: response_format.as_ref(),
+        };
+        let body = &body;
+
+        retry::with_retry(&self.retry, move || async move { self.chat_once(body).await }).await
+    }
+
+    async fn chat_once(&self, body: &ChatRequest<'_>) -> Result<LLMResponse, LLMError> {
+        let mut builder = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .json(body);
+        if let Some(key) = &self.api_key {
+            builder = builder.bearer_auth(key);
+        }
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| retry::from_reqwest(e, &self.retry))?;
+        let chat_response: ChatResponse = retry::check_status(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| retry::from_reqwest(e, &self.retry))?;
+
+        let content = chat_response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.message.content)
+            .ok_or_else(|| LLMError::ParseError("No choices in response".to_string()))?;
+
+        Ok(LLMResponse {
+            content,
+            usage: chat_response.usage.map(|u| TokenUsage {
+                input_tokens: u.prompt_tokens,
+                output_tokens: u.completion_tokens,
+            }),
+        })
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OpenAICompatibleProvider {
+    async fn complete(&self, request: LLMRequest) -> Result<LLMResponse, LLMError> {
+        self.chat(&request, None).await
+    }
+
+    /// `response_format: json_schema` 기반 structured output
+    async fn complete_structured(
+        &self,
+        request: LLMRequest,
+        schema: serde_json::Value,
+        tool_name: &str,
+    ) -> Result<LLMResponse, LLMError> {
+        let response_format = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": tool_name,
+                "schema": schema,
+            }
+        });
+
+        let mut response = self.chat(&request, Some(response_format)).await?;
+        response.content = strip_code_fence(&response.content).to_string();
+        Ok(response)
+    }
+
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn request(content: &str) -> LLMRequest {
+        LLMRequest {
+            messages: vec![LLMMessage {
+                role: "user".to_string(),
+                content: content.to_string(),
+            }],
+            max_tokens: Some(64),
+            temperature: Some(0.0),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_against_stub_server() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer test-key")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "model": "local-model",
+                "messages": [{"role": "user", "content": "Hi"}],
+                "max_tokens": 64
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"choices":[{"message":{"role":"assistant","content":"Hello!"}}],
+                    "usage":{"prompt_tokens":3,"completion_tokens":2}}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = OpenAICompatibleProvider::new(Some("test-key".to_string()))
+            .with_base_url(format!("{}/v1/", server.url()))
+            .with_model("local-model".to_string());
+
+        let response = provider.complete(request("Hi")).await.unwrap();
+        mock.assert_async().await;
+        assert_eq!(response.content, "Hello!");
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.input_tokens, 3);
+        assert_eq!(usage.output_tokens, 2);
+    }
+
+    #[tokio::test]
+    async fn test_complete_structured_sends_json_schema() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_header("authorization", Matcher::Missing)
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": {"name": "split_task"}
+                }
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"choices":[{"message":{"content":"```json\n{\"subtasks\":[]}\n```"}}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = OpenAICompatibleProvider::new(None).with_base_url(server.url());
+        let response = provider
+            .complete_structured(
+                request("split"),
+                serde_json::json!({"type": "object"}),
+                "split_task",
+            )
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.content, r#"{"subtasks":[]}"#);
+        assert!(response.usage.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_error_status_is_classified_and_retried() {
+        let mut server = mockito::Server::new_async().await;
+        let limited = server
+            .mock("POST", "/chat/completions")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .with_body(r#"{"error":{"type":"requests","message":"Rate limit reached"}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let failing = server
+            .mock("POST", "/chat/completions")
+            .with_status(500)
+            .with_body("boom")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let provider = OpenAICompatibleProvider::new(None)
+            .with_base_url(server.url())
+            .with_retry_policy(RetryPolicy {
+                max_retries: 1,
+                initial_backoff: std::time::Duration::from_millis(1),
+                ..RetryPolicy::default()
+            });
+        let err = provider.complete(request("Hi")).await.unwrap_err();
+
+        limited.assert_async().await;
+        failing.assert_async().await;
+        assert!(matches!(err, LLMError::ServerError { status: 500, ref message } if message == "boom"));
+    }
+}
@@ -0,0 +1,313 @@
+//! AI 작업별 prompt 템플릿 registry
+//!
+//! 템플릿은 작업(`PromptId`)과 언어(`Locale`)별로 `templates/{locale}/{id}.txt`에 있고,
+//! 사용자가 설정에서 덮어쓸 수 있습니다 (settings.json의 `prompt_overrides`).
+//! 변수는 `{{name}}` 형식이며 한 번만 치환되므로 입력값 안의 `{{...}}`는 그대로 남습니다.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// prompt를 사용하는 AI 작업
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptId {
+    SplitTask,
+    ParseTask,
+    ParsePlan,
+    GenerateDailyTasks,
+    ParseRecurrence,
+    FocusInsight,
+    Assistant,
+    Chat,
+    Agent,
+    CaptureTasks,
+    WeeklyReview,
+    Reschedule,
+}
+
+impl PromptId {
+    pub const ALL: [PromptId; 12] = [
+        PromptId::SplitTask,
+        PromptId::ParseTask,
+        PromptId::ParsePlan,
+        PromptId::GenerateDailyTasks,
+        PromptId::ParseRecurrence,
+        PromptId::FocusInsight,
+        PromptId::Assistant,
+        PromptId::Chat,
+        PromptId::Agent,
+        PromptId::CaptureTasks,
+        PromptId::WeeklyReview,
+        PromptId::Reschedule,
+    ];
+
+    /// 템플릿에서 사용할 수 있는 변수
+    pub fn variables(&self) -> &'static [&'static str] {
+        match self {
+            PromptId::SplitTask => &["task_title"],
+            PromptId::ParseTask => &["current_date", "input"],
+            PromptId::ParsePlan => &["current_date", "plan_rules", "plan_input"],
+            PromptId::GenerateDailyTasks => &[
+                "plan_rules",
+                "plan_title",
+                "plan_description",
+                "date",
+                "existing_schedule",
+                "free_slots",
+                "focus_windows",
+                "recent_stats",
+            ],
+            PromptId::ParseRecurrence => &["today", "input"],
+            PromptId::FocusInsight => &["stats_summary"],
+            PromptId::Assistant => &["current_date", "context", "input"],
+            PromptId::Chat => &["current_date", "context"],
+            PromptId::Agent => &["current_date", "context"],
+            PromptId::CaptureTasks => &["current_date", "input"],
+            PromptId::WeeklyReview => &["week_start", "week_end", "weekly_stats"],
+            PromptId::Reschedule => &["current_date", "overdue_tasks", "available_days"],
+        }
+    }
+}
+
+/// prompt 언어
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ko,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Ko];
+
+    /// 언어 태그로 Locale 결정 ("ko", "ko_KR", "ko-KR" → Ko, 그 외 → En)
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag.split(['_', '-']).next().unwrap_or("").to_lowercase();
+        match language.as_str() {
+            "ko" => Locale::Ko,
+            _ => Locale::En,
+        }
+    }
+
+    /// 비어 있는 선택 입력(개인 규칙 등)을 대신할 문구
+    pub fn none_label(&self) -> &'static str {
+        match self {
+            Locale::En => "None",
+            Locale::Ko => "없음",
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum PromptError {
+    #[error("Unknown template variable: {{{{{0}}}}}")]
+    UnknownVariable(String),
+    #[error("Missing value for template variable: {{{{{0}}}}}")]
+    MissingVariable(String),
+}
+
+/// 기본 템플릿
+fn default_template(id: PromptId, locale: Locale) -> &'static str {
+    let template = match (locale, id) {
+        (Locale::En, PromptId::SplitTask) => include_str!("templates/en/split_task.txt"),
+        (Locale::En, PromptId::ParseTask) => include_str!("templates/en/parse_task.txt"),
+        (Locale::En, PromptId::ParsePlan) => include_str!("templates/en/parse_plan.txt"),
+        (Locale::En, PromptId::GenerateDailyTasks) => include_str!("templates/en/generate_daily_tasks.txt"),
+        (Locale::En, PromptId::ParseRecurrence) => include_str!("templates/en/parse_recurrence.txt"),
+        (Locale::En, PromptId::FocusInsight) => include_str!("templates/en/focus_insight.txt"),
+        (Locale::En, PromptId::Assistant) => include_str!("templates/en/assistant.txt"),
+        (Locale::En, PromptId::Chat) => include_str!("templates/en/chat.txt"),
+        (Locale::En, PromptId::Agent) => include_str!("templates/en/agent.txt"),
+        (Locale::En, PromptId::CaptureTasks) => include_str!("templates/en/capture_tasks.txt"),
+        (Locale::En, PromptId::WeeklyReview) => include_str!("templates/en/weekly_review.txt"),
+        (Locale::En, PromptId::Reschedule) => include_str!("templates/en/reschedule.txt"),
+        (Locale::Ko, PromptId::SplitTask) => include_str!("templates/ko/split_task.txt"),
+        (Locale::Ko, PromptId::ParseTask) => include_str!("templates/ko/parse_task.txt"),
+        (Locale::Ko, PromptId::ParsePlan) => include_str!("templates/ko/parse_plan.txt"),
+        (Locale::Ko, PromptId::GenerateDailyTasks) => include_str!("templates/ko/generate_daily_tasks.txt"),
+        (Locale::Ko, PromptId::ParseRecurrence) => include_str!("templates/ko/parse_recurrence.txt"),
+        (Locale::Ko, PromptId::FocusInsight) => include_str!("templates/ko/focus_insight.txt"),
+        (Locale::Ko, PromptId::Assistant) => include_str!("templates/ko/assistant.txt"),
+        (Locale::Ko, PromptId::Chat) => include_str!("templates/ko/chat.txt"),
+        (Locale::Ko, PromptId::Agent) => include_str!("templates/ko/agent.txt"),
+        (Locale::Ko, PromptId::CaptureTasks) => include_str!("templates/ko/capture_tasks.txt"),
+        (Locale::Ko, PromptId::WeeklyReview) => include_str!("templates/ko/weekly_review.txt"),
+        (Locale::Ko, PromptId::Reschedule) => include_str!("templates/ko/reschedule.txt"),
+    };
+    template.trim_end()
+}
+
+/// `{{name}}` 변수를 한 번에 치환
+///
+/// 치환된 값은 다시 해석하지 않고, 닫히지 않은 `{{`는 그대로 둡니다.
+pub fn render(template: &str, vars: &[(&str, &str)]) -> Result<String, PromptError> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            output.push_str(&rest[start..]);
+            return Ok(output);
+        };
+
+        let name = after[..end].trim();
+        let value = vars
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| PromptError::MissingVariable(name.to_string()))?;
+        output.push_str(value);
+        rest = &after[end + 2..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// 템플릿이 작업에서 제공하지 않는 변수를 쓰는지 검사
+pub fn validate_template(id: PromptId, template: &str) -> Result<(), PromptError> {
+    let known = id.variables();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if !known.contains(&name) {
+            return Err(PromptError::UnknownVariable(name.to_string()));
+        }
+        rest = &after[end + 2..];
+    }
+    Ok(())
+}
+
+/// 사용자가 덮어쓴 템플릿 (작업 → 언어 → 템플릿)
+pub type PromptOverrides = HashMap<PromptId, HashMap<Locale, String>>;
+
+/// 설정 화면에 보여줄 템플릿 정보
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptTemplateInfo {
+    pub id: PromptId,
+    pub locale: Locale,
+    pub template: String,
+    pub default_template: String,
+    pub variables: Vec<String>,
+    pub is_overridden: bool,
+}
+
+/// 현재 언어와 사용자 override를 반영한 prompt 조회
+pub struct PromptRegistry {
+    locale: Locale,
+    overrides: PromptOverrides,
+}
+
+impl PromptRegistry {
+    pub fn new(locale: Locale, overrides: PromptOverrides) -> Self {
+        Self { locale, overrides }
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    fn template_for(&self, id: PromptId, locale: Locale) -> &str {
+        self.overrides
+            .get(&id)
+            .and_then(|by_locale| by_locale.get(&locale))
+            .map(String::as_str)
+            .unwrap_or_else(|| default_template(id, locale))
+    }
+
+    /// 현재 언어의 템플릿에 변수를 채운 prompt
+    pub fn render(&self, id: PromptId, vars: &[(&str, &str)]) -> Result<String, PromptError> {
+        render(self.template_for(id, self.locale), vars)
+    }
+
+    /// 모든 작업 × 언어의 템플릿 목록
+    pub fn templates(&self) -> Vec<PromptTemplateInfo> {
+        PromptId::ALL
+            .iter()
+            .flat_map(|&id| Locale::ALL.iter().map(move |&locale| (id, locale)))
+            .map(|(id, locale)| PromptTemplateInfo {
+                id,
+                locale,
+                template: self.template_for(id, locale).to_string(),
+                default_template: default_template(id, locale).to_string(),
+                variables: id.variables().iter().map(|v| v.to_string()).collect(),
+                is_overridden: self
+                    .overrides
+                    .get(&id)
+                    .is_some_and(|by_locale| by_locale.contains_key(&locale)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_is_single_pass() {
+        let rendered = render(
+            "Task: {{task_title}} / {{ task_title }}",
+            &[("task_title", "{{input}} 보고서")],
+        )
+        .unwrap();
+        assert_eq!(rendered, "Task: {{input}} 보고서 / {{input}} 보고서");
+
+        assert_eq!(render("open {{ only", &[]).unwrap(), "open {{ only");
+        assert_eq!(
+            render("{{missing}}", &[]),
+            Err(PromptError::MissingVariable("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_default_templates_use_known_variables() {
+        let registry = PromptRegistry::new(Locale::En, PromptOverrides::new());
+        for info in registry.templates() {
+            validate_template(info.id, &info.template).unwrap();
+            let values: Vec<(&str, &str)> = info.id.variables().iter().map(|v| (*v, "x")).collect();
+            let rendered = render(&info.template, &values).unwrap();
+            assert!(!rendered.contains("{{"), "{:?}/{:?}", info.id, info.locale);
+        }
+    }
+
+    #[test]
+    fn test_locale_and_overrides() {
+        assert_eq!(Locale::from_tag("ko_KR"), Locale::Ko);
+        assert_eq!(Locale::from_tag("ko-KR"), Locale::Ko);
+        assert_eq!(Locale::from_tag("en"), Locale::En);
+        assert_eq!(Locale::from_tag(""), Locale::En);
+
+        let mut overrides = PromptOverrides::new();
+        overrides
+            .entry(PromptId::SplitTask)
+            .or_default()
+            .insert(Locale::Ko, "쪼개기: {{task_title}}".to_string());
+
+        let ko = PromptRegistry::new(Locale::Ko, overrides.clone());
+        assert_eq!(ko.render(PromptId::SplitTask, &[("task_title", "청소")]).unwrap(), "쪼개기: 청소");
+
+        let en = PromptRegistry::new(Locale::En, overrides);
+        assert!(en
+            .render(PromptId::SplitTask, &[("task_title", "Clean")])
+            .unwrap()
+            .ends_with("Task: Clean"));
+
+        assert_eq!(
+            validate_template(PromptId::SplitTask, "{{input}}"),
+            Err(PromptError::UnknownVariable("input".to_string()))
+        );
+
+        let stored: PromptOverrides =
+            serde_json::from_value(serde_json::json!({"split_task": {"ko": "x"}})).unwrap();
+        assert_eq!(stored[&PromptId::SplitTask][&Locale::Ko], "x");
+    }
+}
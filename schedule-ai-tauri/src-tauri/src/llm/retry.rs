@@ -0,0 +1,250 @@
+//! 요청 timeout / 재시도 정책과 API 에러 분류
+//!
+//! 429(rate limit), 529(overloaded), 5xx, timeout, 연결 실패는 지수 backoff로 재시도하며
+//! 서버가 `retry-after`를 주면 그 값을 우선합니다.
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::future::Future;
+use std::time::Duration;
+
+use super::LLMError;
+
+/// 요청 timeout과 재시도 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// 시도마다의 timeout (응답 본문 읽기 포함, 스트리밍은 응답 헤더와 청크 사이 간격에 각각 적용)
+    pub timeout: Duration,
+    /// 첫 요청 이후 최대 재시도 횟수
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+            max_retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `attempt`번째 재시도 전 대기 시간 (0부터 시작)
+    pub fn backoff(&self, attempt: u32, retry_after: Option<u64>) -> Duration {
+        if let Some(seconds) = retry_after {
+            return Duration::from_secs(seconds);
+        }
+        let factor = 2u32.saturating_pow(attempt);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// `timeout`을 요청 전체(본문 읽기 포함)에 적용한 HTTP client
+///
+/// 스트리밍하지 않는 provider(OpenAI 호환, Ollama)용입니다.
+pub fn http_client(policy: &RetryPolicy) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(policy.timeout)
+        .build()
+        .unwrap_or_default()
+}
+
+/// 재시도 가능한 실패는 `policy`에 따라 backoff 후 `attempt_once`를 다시 실행
+pub async fn with_retry<T, F, Fut>(policy: &RetryPolicy, mut attempt_once: F) -> Result<T, LLMError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, LLMError>>,
+{
+    let mut attempt = 0;
+    loop {
+        let error = match attempt_once().await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        if attempt >= policy.max_retries || !is_retryable(&error) {
+            return Err(error);
+        }
+
+        let retry_after = match &error {
+            LLMError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        };
+        tokio::time::sleep(policy.backoff(attempt, retry_after)).await;
+        attempt += 1;
+    }
+}
+
+/// 실패한 응답이면 본문을 읽어 LLMError로 분류
+pub async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, LLMError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let retry_after = retry_after(response.headers());
+    let error_text = response.text().await.unwrap_or_default();
+    Err(error_from_response(status, retry_after, error_text))
+}
+
+/// reqwest 에러 분류 (client timeout은 `Timeout`, 응답 JSON 디코딩 실패는 `ParseError`)
+pub fn from_reqwest(error: reqwest::Error, policy: &RetryPolicy) -> LLMError {
+    if error.is_timeout() {
+        LLMError::Timeout(policy.timeout)
+    } else if error.is_decode() {
+        LLMError::ParseError(error.to_string())
+    } else {
+        LLMError::RequestError(error)
+    }
+}
+
+/// 재시도하면 성공할 수 있는 에러인지
+pub fn is_retryable(error: &LLMError) -> bool {
+    match error {
+        LLMError::RateLimited { .. } | LLMError::Overloaded(_) | LLMError::Timeout(_) => true,
+        LLMError::ServerError { .. } => true,
+        LLMError::RequestError(e) => e.is_connect() || e.is_timeout(),
+        _ => false,
+    }
+}
+
+/// `retry-after` 헤더 (초 단위)
+pub fn retry_after(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|seconds| seconds.max(0.0).ceil() as u64)
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    #[serde(rename = "type")]
+    error_type: String,
+    message: String,
+}
+
+/// Anthropic 에러 타입(`rate_limit_error` 등)을 LLMError로 변환
+pub fn error_from_type(error_type: &str, message: String, retry_after: Option<u64>) -> LLMError {
+    match error_type {
+        "authentication_error" | "permission_error" => LLMError::AuthError(message),
+        "rate_limit_error" => LLMError::RateLimited {
+            message,
+            retry_after,
+        },
+        "overloaded_error" => LLMError::Overloaded(message),
+        "request_too_large" => LLMError::ContextTooLong(message),
+        "invalid_request_error" if is_context_message(&message) => LLMError::ContextTooLong(message),
+        _ => LLMError::ApiError(message),
+    }
+}
+
+/// 실패한 HTTP 응답을 LLMError로 분류
+pub fn error_from_response(status: StatusCode, retry_after: Option<u64>, body: String) -> LLMError {
+    let body = match serde_json::from_str::<ErrorResponse>(&body) {
+        Ok(parsed) => match error_from_type(&parsed.error.error_type, parsed.error.message, retry_after) {
+            LLMError::ApiError(message) => message,
+            error => return error,
+        },
+        Err(_) => body,
+    };
+
+    match status.as_u16() {
+        401 | 403 => LLMError::AuthError(body),
+        413 => LLMError::ContextTooLong(body),
+        429 => LLMError::RateLimited {
+            message: body,
+            retry_after,
+        },
+        529 => LLMError::Overloaded(body),
+        code if status.is_server_error() => LLMError::ServerError {
+            status: code,
+            message: body,
+        },
+        _ => LLMError::ApiError(body),
+    }
+}
+
+fn is_context_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("prompt is too long") || message.contains("context window")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(3),
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.backoff(0, None), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2, None), Duration::from_secs(2));
+        assert_eq!(policy.backoff(5, None), Duration::from_secs(3));
+        assert_eq!(policy.backoff(0, Some(7)), Duration::from_secs(7));
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, "12".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(12));
+        headers.insert(RETRY_AFTER, "1.5".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(2));
+        headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_error_from_response() {
+        let body = |kind: &str, message: &str| {
+            format!(r#"{{"type":"error","error":{{"type":"{}","message":"{}"}}}}"#, kind, message)
+        };
+
+        assert!(matches!(
+            error_from_response(StatusCode::UNAUTHORIZED, None, body("authentication_error", "invalid x-api-key")),
+            LLMError::AuthError(m) if m == "invalid x-api-key"
+        ));
+        assert!(matches!(
+            error_from_response(StatusCode::TOO_MANY_REQUESTS, Some(3), body("rate_limit_error", "slow down")),
+            LLMError::RateLimited { retry_after: Some(3), .. }
+        ));
+        assert!(matches!(
+            error_from_response(StatusCode::from_u16(529).unwrap(), None, body("overloaded_error", "Overloaded")),
+            LLMError::Overloaded(_)
+        ));
+        assert!(matches!(
+            error_from_response(
+                StatusCode::BAD_REQUEST,
+                None,
+                body("invalid_request_error", "prompt is too long: 210000 tokens > 200000 maximum")
+            ),
+            LLMError::ContextTooLong(_)
+        ));
+        assert!(matches!(
+            error_from_response(StatusCode::BAD_REQUEST, None, body("invalid_request_error", "bad field")),
+            LLMError::ApiError(m) if m == "bad field"
+        ));
+        assert!(matches!(
+            error_from_response(StatusCode::BAD_GATEWAY, None, "<html>".to_string()),
+            LLMError::ServerError { status: 502, .. }
+        ));
+    }
+}
@@ -0,0 +1,491 @@
+//! 스트리밍 completion 지원
+//!
+//! - Anthropic server-sent events 파싱 (`content_block_delta`, `message_stop`, usage)
+//! - 프론트엔드로 `llm-delta` / `llm-done` 이벤트 전달
+//! - request id 기반 취소
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Notify;
+
+use super::{retry, LLMError, LLMErrorInfo, LLMResponse, TokenUsage};
+
+/// 텍스트 조각 이벤트 이름
+pub const DELTA_EVENT: &str = "llm-delta";
+/// 요청 종료(완료/취소/실패) 이벤트 이름
+pub const DONE_EVENT: &str = "llm-done";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmDeltaEvent {
+    pub request_id: String,
+    pub delta: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmDoneEvent {
+    pub request_id: String,
+    pub status: StreamStatus,
+    pub usage: Option<TokenUsage>,
+    pub error: Option<LLMErrorInfo>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamStatus {
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+/// SSE 이벤트 하나 (`event:` + `data:`)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+}
+
+/// 바이트 청크를 SSE 이벤트로 분리하는 파서
+///
+/// 청크 경계가 이벤트나 UTF-8 문자 중간에 걸려도 다음 청크와 합쳐서 처리합니다.
+#[derive(Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend(chunk.iter().filter(|b| **b != b'\r'));
+
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.windows(2).position(|w| w == b"\n\n") {
+            let block: Vec<u8> = self.buffer.drain(..pos + 2).collect();
+            if let Some(event) = parse_event_block(&String::from_utf8_lossy(&block)) {
+                events.push(event);
+            }
+        }
+        events
+    }
+}
+
+fn parse_event_block(block: &str) -> Option<SseEvent> {
+    let mut event = SseEvent::default();
+    let mut data_lines = Vec::new();
+
+    for line in block.lines() {
+        if line.is_empty() || line.starts_with(':') {
+            continue;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event.event = Some(value.to_string()),
+            "data" => data_lines.push(value),
+            _ => {}
+        }
+    }
+
+    if data_lines.is_empty() {
+        return None;
+    }
+    event.data = data_lines.join("\n");
+    Some(event)
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicEvent {
+    MessageStart { message: StartMessage },
+    ContentBlockDelta { delta: BlockDelta },
+    MessageDelta { usage: Option<DeltaUsage> },
+    MessageStop,
+    Error { error: ErrorBody },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct StartMessage {
+    usage: Option<TokenUsage>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BlockDelta {
+    TextDelta { text: String },
+    InputJsonDelta { partial_json: String },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct DeltaUsage {
+    output_tokens: u32,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    #[serde(rename = "type")]
+    error_type: String,
+    message: String,
+}
+
+/// Anthropic 스트림 이벤트를 누적해 최종 응답을 만드는 상태
+#[derive(Default)]
+pub struct AnthropicStreamState {
+    text: String,
+    tool_json: String,
+    usage: Option<TokenUsage>,
+    finished: bool,
+}
+
+impl AnthropicStreamState {
+    /// 이벤트 하나를 반영하고, 새로 도착한 텍스트(또는 부분 JSON)를 반환
+    pub fn apply(&mut self, event: &SseEvent) -> Result<Option<String>, LLMError> {
+        let parsed: AnthropicEvent = serde_json::from_str(&event.data)
+            .map_err(|e| LLMError::ParseError(format!("Invalid stream event: {}", e)))?;
+
+        match parsed {
+            AnthropicEvent::MessageStart { message } => {
+                self.usage = message.usage;
+                Ok(None)
+            }
+            AnthropicEvent::ContentBlockDelta { delta } => match delta {
+                BlockDelta::TextDelta { text } => {
+                    self.text.push_str(&text);
+                    Ok(Some(text))
+                }
+                BlockDelta::InputJsonDelta { partial_json } => {
+                    self.tool_json.push_str(&partial_json);
+                    Ok(Some(partial_json))
+                }
+                BlockDelta::Other => Ok(None),
+            },
+            AnthropicEvent::MessageDelta { usage } => {
+                if let Some(delta_usage) = usage {
+                    // message_delta의 output_tokens는 누적값
+                    let usage = self.usage.get_or_insert(TokenUsage {
+                        input_tokens: 0,
+                        output_tokens: 0,
+                    });
+                    usage.output_tokens = delta_usage.output_tokens;
+                }
+                Ok(None)
+            }
+            AnthropicEvent::MessageStop => {
+                self.finished = true;
+                Ok(None)
+            }
+            AnthropicEvent::Error { error } => {
+                Err(retry::error_from_type(&error.error_type, error.message, None))
+            }
+            AnthropicEvent::Other => Ok(None),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// 최종 응답 생성 (`structured`면 tool_use 입력 JSON을 content로 사용)
+    pub fn into_response(self, structured: bool) -> Result<LLMResponse, LLMError> {
+        if !self.finished {
+            return Err(LLMError::ParseError(
+                "Stream ended before message_stop".to_string(),
+            ));
+        }
+
+        let content = if structured {
+            let json = if self.tool_json.trim().is_empty() {
+                "{}".to_string()
+            } else {
+                self.tool_json
+            };
+            // 조립된 JSON이 유효한지 확인
+            serde_json::from_str::<serde_json::Value>(&json)
+                .map_err(|e| LLMError::ParseError(format!("Invalid tool input JSON: {}", e)))?;
+            json
+        } else {
+            self.text
+        };
+
+        Ok(LLMResponse {
+            content,
+            usage: self.usage,
+        })
+    }
+}
+
+/// 진행 중인 스트리밍 요청의 취소 신호 (request id 기준)
+#[derive(Default)]
+pub struct StreamRegistry(Mutex<HashMap<String, Arc<Notify>>>);
+
+impl StreamRegistry {
+    /// 요청 등록. 같은 id의 요청이 진행 중이면 거부
+    pub fn register(&self, request_id: &str) -> Result<Arc<Notify>, LLMError> {
+        match self.0.lock().unwrap().entry(request_id.to_string()) {
+            Entry::Occupied(_) => Err(LLMError::InvalidInput(format!(
+                "request id already in use: {}",
+                request_id
+            ))),
+            Entry::Vacant(entry) => Ok(entry.insert(Arc::new(Notify::new())).clone()),
+        }
+    }
+
+    /// 요청 취소. 해당 id의 요청이 진행 중이었으면 true
+    pub fn cancel(&self, request_id: &str) -> bool {
+        match self.0.lock().unwrap().remove(request_id) {
+            Some(notify) => {
+                notify.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 등록을 해제. 취소 뒤 같은 id로 새로 등록된 요청은 그대로 둠
+    fn finish(&self, request_id: &str, cancel: &Arc<Notify>) {
+        let mut requests = self.0.lock().unwrap();
+        if requests.get(request_id).is_some_and(|current| Arc::ptr_eq(current, cancel)) {
+            requests.remove(request_id);
+        }
+    }
+}
+
+/// `llm-done` 이벤트에 토큰 사용량을 실을 수 있는 결과 타입
+pub trait UsageReport {
+    fn usage(&self) -> Option<TokenUsage>;
+}
+
+impl UsageReport for LLMResponse {
+    fn usage(&self) -> Option<TokenUsage> {
+        self.usage.clone()
+    }
+}
+
+/// 취소 신호가 오면 `LLMError::Cancelled`로 중단 (future는 drop되어 HTTP 스트림도 닫힘)
+pub async fn cancellable<T, F>(cancel: Arc<Notify>, future: F) -> Result<T, LLMError>
+where
+    F: Future<Output = Result<T, LLMError>>,
+{
+    tokio::select! {
+        result = future => result,
+        _ = cancel.notified() => Err(LLMError::Cancelled),
+    }
+}
+
+/// delta를 `llm-delta` 이벤트로 프론트엔드에 보내는 콜백
+pub fn delta_emitter(app: &AppHandle, request_id: &str) -> impl Fn(&str) + Send + Sync {
+    let app = app.clone();
+    let request_id = request_id.to_string();
+    move |delta: &str| {
+        let _ = app.emit(
+            DELTA_EVENT,
+            LlmDeltaEvent {
+                request_id: request_id.clone(),
+                delta: delta.to_string(),
+            },
+        );
+    }
+}
+
+/// 스트리밍 요청을 취소 가능하게 실행하고 결과를 `llm-done` 이벤트로 알림
+pub async fn run_with_events<T, F>(
+    app: &AppHandle,
+    registry: &StreamRegistry,
+    request_id: &str,
+    future: F,
+) -> Result<T, LLMError>
+where
+    T: UsageReport,
+    F: Future<Output = Result<T, LLMError>>,
+{
+    let cancel = registry.register(request_id)?;
+    let result = cancellable(cancel.clone(), future).await;
+    registry.finish(request_id, &cancel);
+
+    let done = match &result {
+        Ok(response) => LlmDoneEvent {
+            request_id: request_id.to_string(),
+            status: StreamStatus::Completed,
+            usage: response.usage(),
+            error: None,
+        },
+        Err(LLMError::Cancelled) => LlmDoneEvent {
+            request_id: request_id.to_string(),
+            status: StreamStatus::Cancelled,
+            usage: None,
+            error: None,
+        },
+        Err(e) => LlmDoneEvent {
+            request_id: request_id.to_string(),
+            status: StreamStatus::Failed,
+            usage: None,
+            error: Some(e.info()),
+        },
+    };
+    let _ = app.emit(DONE_EVENT, done);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{ClaudeProvider, LLMMessage, LLMProvider, LLMRequest};
+
+    const TEXT_STREAM: &str = "event: message_start\n\
+data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"usage\":{\"input_tokens\":25,\"output_tokens\":1}}}\n\n\
+event: content_block_start\n\
+data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n\
+event: ping\n\
+data: {\"type\":\"ping\"}\n\n\
+event: content_block_delta\n\
+data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"안녕\"}}\n\n\
+event: content_block_delta\n\
+data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"하세요\"}}\n\n\
+event: content_block_stop\n\
+data: {\"type\":\"content_block_stop\",\"index\":0}\n\n\
+event: message_delta\n\
+data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},\"usage\":{\"output_tokens\":7}}\n\n\
+event: message_stop\n\
+data: {\"type\":\"message_stop\"}\n\n";
+
+    fn request() -> LLMRequest {
+        LLMRequest {
+            messages: vec![LLMMessage {
+                role: "user".to_string(),
+                content: "Hi".to_string(),
+            }],
+            max_tokens: Some(64),
+            temperature: None,
+        }
+    }
+
+    #[test]
+    fn test_sse_parser_handles_split_chunks() {
+        let mut parser = SseParser::default();
+        let bytes = TEXT_STREAM.as_bytes();
+
+        // 이벤트와 멀티바이트 문자 중간에서 잘라도 동일한 결과
+        let mut events = Vec::new();
+        for chunk in bytes.chunks(7) {
+            events.extend(parser.push(chunk));
+        }
+
+        assert_eq!(events.len(), 8);
+        assert_eq!(events[0].event.as_deref(), Some("message_start"));
+        assert!(events[3].data.contains("안녕"));
+    }
+
+    #[test]
+    fn test_sse_parser_crlf_and_comments() {
+        let mut parser = SseParser::default();
+        let events = parser.push(b": keep-alive\r\n\r\nevent: a\r\ndata: 1\r\ndata: 2\r\n\r\n");
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: Some("a".to_string()),
+                data: "1\n2".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_state_accumulates_tool_json() {
+        let mut state = AnthropicStreamState::default();
+        let events = [
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"tool_use","id":"t","name":"x","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"insight\": \"집"}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"중\"}"}}"#,
+            r#"{"type":"message_stop"}"#,
+        ];
+        let mut deltas = Vec::new();
+        for data in events {
+            let event = SseEvent {
+                event: None,
+                data: data.to_string(),
+            };
+            if let Some(delta) = state.apply(&event).unwrap() {
+                deltas.push(delta);
+            }
+        }
+
+        assert_eq!(deltas.len(), 2);
+        let response = state.into_response(true).unwrap();
+        assert_eq!(response.content, r#"{"insight": "집중"}"#);
+    }
+
+    #[test]
+    fn test_state_error_event() {
+        let mut state = AnthropicStreamState::default();
+        let event = SseEvent {
+            event: Some("error".to_string()),
+            data: r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#
+                .to_string(),
+        };
+        assert!(matches!(state.apply(&event), Err(LLMError::Overloaded(m)) if m == "Overloaded"));
+    }
+
+    #[tokio::test]
+    async fn test_claude_complete_stream() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"stream": true})))
+            .with_status(200)
+            .with_header("content-type", "text/event-stream")
+            .with_body(TEXT_STREAM)
+            .create_async()
+            .await;
+
+        let provider = ClaudeProvider::new("sk-test".to_string()).with_base_url(server.url());
+        let deltas = Mutex::new(Vec::new());
+        let on_delta = |delta: &str| deltas.lock().unwrap().push(delta.to_string());
+
+        let response = provider.complete_stream(request(), &on_delta).await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(*deltas.lock().unwrap(), vec!["안녕", "하세요"]);
+        assert_eq!(response.content, "안녕하세요");
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.input_tokens, 25);
+        assert_eq!(usage.output_tokens, 7);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_pending_request() {
+        let registry = StreamRegistry::default();
+        let cancel = registry.register("req-1").unwrap();
+
+        let pending = std::future::pending::<Result<LLMResponse, LLMError>>();
+        let (result, cancelled) = tokio::join!(cancellable(cancel, pending), async {
+            registry.cancel("req-1")
+        });
+
+        assert!(cancelled);
+        assert!(matches!(result, Err(LLMError::Cancelled)));
+        assert!(!registry.cancel("req-1"));
+    }
+
+    #[test]
+    fn test_registry_keeps_each_request_separate() {
+        let registry = StreamRegistry::default();
+        let first = registry.register("req-1").unwrap();
+        assert!(matches!(registry.register("req-1"), Err(LLMError::InvalidInput(_))));
+
+        // 취소 뒤 같은 id로 등록한 요청은 먼저 끝난 요청이 지우지 않음
+        assert!(registry.cancel("req-1"));
+        let second = registry.register("req-1").unwrap();
+        registry.finish("req-1", &first);
+        assert!(registry.0.lock().unwrap().contains_key("req-1"));
+
+        registry.finish("req-1", &second);
+        assert!(!registry.cancel("req-1"));
+    }
+}
@@ -0,0 +1,187 @@
+//! 타입 기반 structured output 파이프라인
+//!
+//! 응답 타입에서 JSON schema를 생성해 provider의 structured output(Claude tool use,
+//! OpenAI `json_schema`, Ollama `format`)으로 요청하고, 파싱/검증 결과를 타입으로 돌려줍니다.
+//! 검증에 실패하면 오류 내용을 모델에게 알려주고 한 번 재시도합니다.
+
+use chrono::{NaiveDate, NaiveTime};
+use schemars::{generate::SchemaSettings, JsonSchema};
+use serde::de::DeserializeOwned;
+
+use super::stream::UsageReport;
+use super::{strip_code_fence, LLMMessage, LLMRequest, TokenUsage};
+
+/// 검증 실패 시 모델에게 다시 요청하는 횟수
+pub const MAX_RETRIES: usize = 1;
+
+/// structured output으로 받을 수 있는 응답 타입
+pub trait StructuredOutput: DeserializeOwned + JsonSchema {
+    /// Claude tool 이름 / OpenAI `json_schema.name`
+    const TOOL_NAME: &'static str;
+
+    /// schema로 표현할 수 없는 값 검증 (날짜 형식, 범위 등)
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// 검증된 응답과 (재시도를 포함한) 전체 토큰 사용량
+#[derive(Debug, Clone)]
+pub struct Structured<T> {
+    pub value: T,
+    pub usage: Option<TokenUsage>,
+}
+
+impl<T> UsageReport for Structured<T> {
+    fn usage(&self) -> Option<TokenUsage> {
+        self.usage.clone()
+    }
+}
+
+/// 응답 타입의 JSON schema (서브스키마는 인라인, `$schema`/`title` 제외)
+pub fn schema_for<T: JsonSchema>() -> serde_json::Value {
+    let generator = SchemaSettings::draft2020_12()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator();
+    let mut schema = generator.into_root_schema_for::<T>().to_value();
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+        object.remove("title");
+    }
+    schema
+}
+
+/// 모델 응답을 타입으로 파싱하고 검증
+pub fn parse<T: StructuredOutput>(content: &str) -> Result<T, String> {
+    let value: T = serde_json::from_str(strip_code_fence(content)).map_err(|e| e.to_string())?;
+    value.validate()?;
+    Ok(value)
+}
+
+/// 잘못된 응답과 오류를 대화에 덧붙여 재시도 요청을 만듦
+pub fn retry_request(mut request: LLMRequest, content: String, error: &str, tool_name: &str) -> LLMRequest {
+    request.messages.push(LLMMessage {
+        role: "assistant".to_string(),
+        content,
+    });
+    request.messages.push(LLMMessage {
+        role: "user".to_string(),
+        content: format!(
+            "The previous {} output was invalid: {}\nFix the error and respond again in the same language.",
+            tool_name, error
+        ),
+    });
+    request
+}
+
+/// 재시도까지 포함한 토큰 사용량 합산
+pub fn add_usage(total: &mut Option<TokenUsage>, usage: Option<TokenUsage>) {
+    if let Some(usage) = usage {
+        let total = total.get_or_insert(TokenUsage {
+            input_tokens: 0,
+            output_tokens: 0,
+        });
+        total.input_tokens += usage.input_tokens;
+        total.output_tokens += usage.output_tokens;
+    }
+}
+
+/// "YYYY-MM-DD" 형식 검증 (값이 없으면 통과)
+pub fn check_date(field: &str, value: Option<&str>) -> Result<(), String> {
+    match value {
+        Some(v) if NaiveDate::parse_from_str(v, "%Y-%m-%d").is_err() => {
+            Err(format!("{} must be a date in YYYY-MM-DD format, got \"{}\"", field, v))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// "HH:MM" 형식 검증 (값이 없으면 통과)
+pub fn check_time(field: &str, value: Option<&str>) -> Result<(), String> {
+    match value {
+        Some(v) if v.len() != 5 || NaiveTime::parse_from_str(v, "%H:%M").is_err() => {
+            Err(format!("{} must be a time in HH:MM format, got \"{}\"", field, v))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 정수 범위 검증 (값이 없으면 통과)
+pub fn check_range(field: &str, value: Option<i32>, min: i32, max: i32) -> Result<(), String> {
+    match value {
+        Some(v) if v < min || v > max => {
+            Err(format!("{} must be between {} and {}, got {}", field, min, max, v))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    #[serde(rename_all = "camelCase")]
+    struct Sample {
+        /// 제목
+        title: String,
+        scheduled_time: Option<String>,
+        items: Vec<Item>,
+    }
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct Item {
+        minutes: i32,
+    }
+
+    impl StructuredOutput for Sample {
+        const TOOL_NAME: &'static str = "sample";
+
+        fn validate(&self) -> Result<(), String> {
+            check_time("scheduledTime", self.scheduled_time.as_deref())
+        }
+    }
+
+    #[test]
+    fn test_schema_for_inlines_subschemas() {
+        let schema = schema_for::<Sample>();
+        assert_eq!(schema["type"], "object");
+        assert!(schema.get("$schema").is_none());
+        assert!(schema.get("$defs").is_none());
+        assert_eq!(schema["properties"]["title"]["description"], "제목");
+        assert_eq!(schema["properties"]["items"]["items"]["type"], "object");
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        assert!(required.contains(&"title"));
+        assert!(!required.contains(&"scheduledTime"));
+    }
+
+    #[test]
+    fn test_parse_validates() {
+        let ok: Sample =
+            parse("```json\n{\"title\":\"a\",\"scheduledTime\":\"09:30\",\"items\":[{\"minutes\":5}]}\n```")
+                .unwrap();
+        assert_eq!(ok.title, "a");
+        assert_eq!(ok.items[0].minutes, 5);
+
+        let err = parse::<Sample>(r#"{"title":"a","scheduledTime":"9시","items":[]}"#).unwrap_err();
+        assert!(err.contains("HH:MM"));
+        assert!(parse::<Sample>(r#"{"items":[]}"#).is_err());
+    }
+
+    #[test]
+    fn test_checks() {
+        assert!(check_date("d", Some("2026-02-30")).is_err());
+        assert!(check_date("d", Some("2026-02-28")).is_ok());
+        assert!(check_date("d", None).is_ok());
+        assert!(check_time("t", Some("24:00")).is_err());
+        assert!(check_time("t", Some("7:00")).is_err());
+        assert!(check_range("p", Some(4), 0, 3).is_err());
+        assert!(check_range("p", Some(0), 0, 3).is_ok());
+    }
+}
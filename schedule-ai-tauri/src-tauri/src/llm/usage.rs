@@ -0,0 +1,416 @@
+//! LLM 사용량 기록(ledger)과 월간 예산
+//!
+//! 모든 provider 호출을 `llm_usage` 테이블에 남기고, 날짜/작업별 사용량과
+//! 이번 달 누적 토큰·비용을 조회합니다. 예산을 넘으면 이후의 provider 호출은 `BudgetExceeded`로 거부됩니다.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use std::time::Duration;
+
+use super::stream::UsageReport;
+use super::LLMError;
+use crate::models::{generate_id, now_iso};
+
+/// 모델별 가격 (USD / 1M 토큰, 입력 / 출력). 앞에서부터 prefix 매칭
+const PRICING: &[(&str, f64, f64)] = &[
+    ("claude-opus-4", 15.0, 75.0),
+    ("claude-sonnet-4", 3.0, 15.0),
+    ("claude-3-7-sonnet", 3.0, 15.0),
+    ("claude-3-5-sonnet", 3.0, 15.0),
+    ("claude-haiku-4", 1.0, 5.0),
+    ("claude-3-5-haiku", 0.8, 4.0),
+    ("gpt-4o-mini", 0.15, 0.6),
+    ("gpt-4o", 2.5, 10.0),
+    ("gpt-4.1-mini", 0.4, 1.6),
+    ("gpt-4.1", 2.0, 8.0),
+];
+
+/// 호출 비용 추정 (로컬 Ollama는 0, 가격을 모르는 모델은 None)
+pub fn estimate_cost_usd(provider: &str, model: &str, input_tokens: u32, output_tokens: u32) -> Option<f64> {
+    if provider == "ollama" {
+        return Some(0.0);
+    }
+    PRICING
+        .iter()
+        .find(|(prefix, _, _)| model.starts_with(prefix))
+        .map(|(_, input, output)| {
+            (input_tokens as f64 * input + output_tokens as f64 * output) / 1_000_000.0
+        })
+}
+
+/// `llm_usage`에 기록되는 호출 한 건
+#[derive(Debug, Clone)]
+pub struct UsageRecord {
+    pub operation: String,
+    pub provider: String,
+    pub model: String,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub latency: Duration,
+    pub success: bool,
+    pub error_kind: Option<String>,
+    pub usage_date: String,
+}
+
+impl UsageRecord {
+    pub fn from_result<T: UsageReport>(
+        operation: &str,
+        provider: &str,
+        model: &str,
+        result: &Result<T, LLMError>,
+        latency: Duration,
+    ) -> Self {
+        let (input_tokens, output_tokens) = match result.as_ref().ok().and_then(UsageReport::usage) {
+            Some(usage) => (usage.input_tokens, usage.output_tokens),
+            None => (0, 0),
+        };
+        let error_kind = result.as_ref().err().map(|e| {
+            serde_json::to_value(e.kind())
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default()
+        });
+
+        Self {
+            operation: operation.to_string(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            input_tokens,
+            output_tokens,
+            latency,
+            success: result.is_ok(),
+            error_kind,
+            usage_date: today(),
+        }
+    }
+}
+
+/// 로컬 날짜 "YYYY-MM-DD"
+pub fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// 이번 달 "YYYY-MM"
+pub fn current_month() -> String {
+    chrono::Local::now().format("%Y-%m").to_string()
+}
+
+pub async fn record(pool: &SqlitePool, record: &UsageRecord) -> Result<(), sqlx::Error> {
+    let cost = estimate_cost_usd(&record.provider, &record.model, record.input_tokens, record.output_tokens);
+    sqlx::query(
+        "INSERT INTO llm_usage (id, operation, provider, model, input_tokens, output_tokens, cost_usd,
+            latency_ms, success, error_kind, usage_date, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(generate_id())
+    .bind(&record.operation)
+    .bind(&record.provider)
+    .bind(&record.model)
+    .bind(record.input_tokens as i64)
+    .bind(record.output_tokens as i64)
+    .bind(cost)
+    .bind(record.latency.as_millis() as i64)
+    .bind(record.success)
+    .bind(&record.error_kind)
+    .bind(&record.usage_date)
+    .bind(now_iso())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 날짜 × 작업별 사용량
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyUsage {
+    pub date: String,
+    pub operation: String,
+    pub calls: i64,
+    pub failures: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+    pub avg_latency_ms: i64,
+}
+
+/// 기간 내 날짜 × 작업별 사용량 (`from`, `to`는 "YYYY-MM-DD", 양끝 포함)
+pub async fn daily_usage(pool: &SqlitePool, from: &str, to: &str) -> Result<Vec<DailyUsage>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT usage_date, operation, COUNT(*) AS calls,
+                SUM(CASE WHEN success = 0 THEN 1 ELSE 0 END) AS failures,
+                SUM(input_tokens) AS input_tokens, SUM(output_tokens) AS output_tokens,
+                COALESCE(SUM(cost_usd), 0.0) AS cost_usd, CAST(AVG(latency_ms) AS INTEGER) AS avg_latency_ms
+         FROM llm_usage
+         WHERE usage_date BETWEEN ? AND ?
+         GROUP BY usage_date, operation
+         ORDER BY usage_date, operation",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| DailyUsage {
+            date: row.get("usage_date"),
+            operation: row.get("operation"),
+            calls: row.get("calls"),
+            failures: row.get("failures"),
+            input_tokens: row.get("input_tokens"),
+            output_tokens: row.get("output_tokens"),
+            cost_usd: row.get("cost_usd"),
+            avg_latency_ms: row.get("avg_latency_ms"),
+        })
+        .collect())
+}
+
+/// 기간 누적 사용량
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotals {
+    pub calls: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    pub fn total_tokens(&self) -> i64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// 한 달("YYYY-MM") 누적 사용량
+pub async fn monthly_totals(pool: &SqlitePool, month: &str) -> Result<UsageTotals, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT COUNT(*) AS calls, COALESCE(SUM(input_tokens), 0) AS input_tokens,
+                COALESCE(SUM(output_tokens), 0) AS output_tokens, COALESCE(SUM(cost_usd), 0.0) AS cost_usd
+         FROM llm_usage
+         WHERE usage_date LIKE ? || '-%'",
+    )
+    .bind(month)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(UsageTotals {
+        calls: row.get("calls"),
+        input_tokens: row.get("input_tokens"),
+        output_tokens: row.get("output_tokens"),
+        cost_usd: row.get("cost_usd"),
+    })
+}
+
+/// 월간 예산 (settings.json의 `llm_budget`)
+///
+/// 비용 한도는 가격을 아는 모델의 추정 비용만 합산합니다.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageBudget {
+    pub monthly_token_limit: Option<i64>,
+    pub monthly_cost_limit_usd: Option<f64>,
+}
+
+impl UsageBudget {
+    /// 이번 달 사용량이 한도에 도달했으면 `BudgetExceeded`
+    pub fn check(&self, totals: &UsageTotals) -> Result<(), LLMError> {
+        if let Some(limit) = self.monthly_token_limit {
+            if totals.total_tokens() >= limit {
+                return Err(LLMError::BudgetExceeded(format!(
+                    "monthly token budget of {} reached ({} tokens used)",
+                    limit,
+                    totals.total_tokens()
+                )));
+            }
+        }
+        if let Some(limit) = self.monthly_cost_limit_usd {
+            if totals.cost_usd >= limit {
+                return Err(LLMError::BudgetExceeded(format!(
+                    "monthly cost budget of ${:.2} reached (${:.2} used)",
+                    limit, totals.cost_usd
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// LLMService가 호출마다 사용량을 남기는 대상
+#[derive(Clone)]
+pub struct UsageLedger {
+    pool: SqlitePool,
+    operation: String,
+    budget: UsageBudget,
+}
+
+impl UsageLedger {
+    pub fn new(pool: SqlitePool, operation: &str) -> Self {
+        Self {
+            pool,
+            operation: operation.to_string(),
+            budget: UsageBudget::default(),
+        }
+    }
+
+    /// 호출 전마다 확인할 월간 예산
+    pub fn with_budget(mut self, budget: UsageBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// 이번 달 사용량이 예산에 도달했으면 `BudgetExceeded` (조회 실패는 로그만 남기고 통과)
+    pub async fn check_budget(&self) -> Result<(), LLMError> {
+        if self.budget == UsageBudget::default() {
+            return Ok(());
+        }
+        match monthly_totals(&self.pool, &current_month()).await {
+            Ok(totals) => self.budget.check(&totals),
+            Err(e) => {
+                eprintln!("Failed to load LLM usage totals: {}", e);
+                Ok(())
+            }
+        }
+    }
+
+    /// 기록 실패는 AI 호출 결과에 영향을 주지 않도록 로그만 남김
+    pub async fn record<T: UsageReport>(
+        &self,
+        provider: &str,
+        model: &str,
+        result: &Result<T, LLMError>,
+        latency: Duration,
+    ) {
+        let usage = UsageRecord::from_result(&self.operation, provider, model, result, latency);
+        if let Err(e) = record(&self.pool, &usage).await {
+            eprintln!("Failed to record LLM usage: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{LLMResponse, TokenUsage};
+
+    fn ok(input_tokens: u32, output_tokens: u32) -> Result<LLMResponse, LLMError> {
+        Ok(LLMResponse {
+            content: String::new(),
+            usage: Some(TokenUsage {
+                input_tokens,
+                output_tokens,
+            }),
+        })
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let cost = estimate_cost_usd("claude", "claude-sonnet-4-20250514", 1_000_000, 100_000).unwrap();
+        assert!((cost - 4.5).abs() < 1e-9);
+        assert_eq!(estimate_cost_usd("openai", "gpt-4o-mini", 1_000_000, 0), Some(0.15));
+        assert_eq!(estimate_cost_usd("ollama", "llama3.1", 5000, 5000), Some(0.0));
+        assert_eq!(estimate_cost_usd("openai", "local-model", 5000, 5000), None);
+    }
+
+    #[tokio::test]
+    async fn test_record_and_query_usage() {
+        let pool = crate::db::test_pool().await;
+        let latency = Duration::from_millis(120);
+
+        let mut first = UsageRecord::from_result("split_task", "claude", "claude-sonnet-4", &ok(1000, 200), latency);
+        first.usage_date = "2026-03-01".to_string();
+        let mut failed = UsageRecord::from_result::<LLMResponse>(
+            "split_task",
+            "claude",
+            "claude-sonnet-4",
+            &Err(LLMError::Overloaded("busy".to_string())),
+            Duration::from_millis(80),
+        );
+        failed.usage_date = "2026-03-01".to_string();
+        let mut other = UsageRecord::from_result("parse_plan", "ollama", "llama3.1", &ok(500, 500), latency);
+        other.usage_date = "2026-03-02".to_string();
+        let mut previous_month = UsageRecord::from_result("parse_plan", "claude", "claude-sonnet-4", &ok(9, 9), latency);
+        previous_month.usage_date = "2026-02-28".to_string();
+
+        for r in [&first, &failed, &other, &previous_month] {
+            record(&pool, r).await.unwrap();
+        }
+        assert_eq!(failed.error_kind.as_deref(), Some("overloaded"));
+
+        let daily = daily_usage(&pool, "2026-03-01", "2026-03-31").await.unwrap();
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].operation, "split_task");
+        assert_eq!(daily[0].calls, 2);
+        assert_eq!(daily[0].failures, 1);
+        assert_eq!(daily[0].input_tokens, 1000);
+        assert_eq!(daily[0].avg_latency_ms, 100);
+        assert_eq!(daily[1].date, "2026-03-02");
+
+        let totals = monthly_totals(&pool, "2026-03").await.unwrap();
+        assert_eq!(totals.calls, 3);
+        assert_eq!(totals.total_tokens(), 2200);
+        assert!((totals.cost_usd - 0.006).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_budget_check() {
+        let totals = UsageTotals {
+            calls: 10,
+            input_tokens: 9_000,
+            output_tokens: 1_000,
+            cost_usd: 0.5,
+        };
+        assert!(UsageBudget::default().check(&totals).is_ok());
+
+        let tokens = UsageBudget {
+            monthly_token_limit: Some(10_000),
+            monthly_cost_limit_usd: None,
+        };
+        assert!(matches!(tokens.check(&totals), Err(LLMError::BudgetExceeded(_))));
+
+        let cost = UsageBudget {
+            monthly_token_limit: Some(50_000),
+            monthly_cost_limit_usd: Some(1.0),
+        };
+        assert!(cost.check(&totals).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_service_checks_budget_before_every_call() {
+        use crate::llm::{ClaudeProvider, LLMMessage, LLMRequest, LLMService};
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .with_status(200)
+            .with_body(r#"{"content":[{"type":"text","text":"ok"}],"usage":{"input_tokens":80,"output_tokens":40}}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let pool = crate::db::test_pool().await;
+        let budget = UsageBudget {
+            monthly_token_limit: Some(100),
+            monthly_cost_limit_usd: None,
+        };
+        let service = LLMService::new(std::sync::Arc::new(
+            ClaudeProvider::new("sk-test".to_string()).with_base_url(server.url()),
+        ))
+        .with_ledger(UsageLedger::new(pool, "agent").with_budget(budget));
+        let request = || LLMRequest {
+            messages: vec![LLMMessage {
+                role: "user".to_string(),
+                content: "hi".to_string(),
+            }],
+            max_tokens: Some(100),
+            temperature: None,
+        };
+
+        // 첫 호출로 한도를 넘으면 같은 서비스의 다음 호출은 provider에 닿기 전에 거부
+        service.complete(request()).await.unwrap();
+        assert!(matches!(
+            service.complete(request()).await,
+            Err(LLMError::BudgetExceeded(_))
+        ));
+        mock.assert_async().await;
+    }
+}
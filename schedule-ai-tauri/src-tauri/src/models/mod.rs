@@ -40,6 +40,31 @@ pub struct Task {
     pub completed_at: Option<String>,
 }
 
+#[cfg(test)]
+impl Task {
+    /// 테스트용 태스크 (제목은 id, 할 일 상태, 나머지는 비어 있음)
+    pub fn test(id: &str, date: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            plan_id: None,
+            title: id.to_string(),
+            description: None,
+            location: None,
+            scheduled_date: date.to_string(),
+            scheduled_time: None,
+            estimated_duration: None,
+            actual_duration: None,
+            priority: 0,
+            status: TaskStatus::Pending,
+            order_index: 0,
+            subtasks: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            completed_at: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubTask {
@@ -127,6 +152,9 @@ pub struct RecurrencePattern {
     pub days_of_week: Option<Vec<i32>>,
     pub day_of_month: Option<i32>,
     pub end_date: Option<String>,
+    /// `Custom` 반복의 RFC 5545 RRULE
+    #[serde(default)]
+    pub rrule: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,6 +163,7 @@ pub enum RecurrenceType {
     Daily,
     Weekly,
     Monthly,
+    Yearly,
     Custom,
 }
 
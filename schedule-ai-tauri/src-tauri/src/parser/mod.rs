@@ -0,0 +1,544 @@
+//! 규칙 기반 오프라인 태스크 파서
+//!
+//! API 키가 없거나 AI 호출이 실패하면 `parse_task_with_ai` 대신 사용합니다.
+//! 입력에서 날짜, 시간, 소요시간, 장소, 우선순위 표현을 찾아 지우고 남은 부분을 제목으로 씁니다.
+
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::commands::llm::ParseTaskResponse;
+
+/// 시간 한 점: (오전/오후)? 시 (:분 | 시 (반|분)?)? (am|pm)?
+const TIME: &str = r"(?:(오전|오후|아침|저녁|밤|새벽)\s*)?(\d{1,2})(?::(\d{2})|\s*(시)(?:\s*(반|\d{1,2}\s*분))?)?\s*(am|pm|a\.m\.|p\.m\.)?";
+const MONTHS: &str = r"(jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*\.?";
+const EN_WEEKDAYS: &str =
+    r"(monday|tuesday|wednesday|thursday|friday|saturday|sunday|mon|tues|tue|wed|thurs|thur|thu|fri)";
+
+lazy_static! {
+    static ref DATE_ISO: Regex = Regex::new(r"\b(\d{4})-(\d{1,2})-(\d{1,2})\b").unwrap();
+    static ref DATE_KO: Regex = Regex::new(r"(?:(\d{4})\s*년\s*)?(\d{1,2})\s*월\s*(\d{1,2})\s*일").unwrap();
+    static ref DATE_EN: Regex =
+        Regex::new(&format!(r"(?i)\b{}\s+(\d{{1,2}})(?:st|nd|rd|th)?\b", MONTHS)).unwrap();
+    static ref DATE_EN_DAY_FIRST: Regex =
+        Regex::new(&format!(r"(?i)\b(\d{{1,2}})(?:st|nd|rd|th)?\s+(?:of\s+)?{}\b", MONTHS)).unwrap();
+    static ref DATE_SLASH: Regex = Regex::new(r"\b(\d{1,2})/(\d{1,2})\b").unwrap();
+    static ref DATE_IN: Regex =
+        Regex::new(r"(?i)\bin\s+(\d{1,3})\s+(days?|weeks?)\b|(\d{1,3})\s*(일|주)\s*(?:후|뒤)").unwrap();
+    static ref WEEKDAY_KO: Regex =
+        Regex::new(r"(다다음\s*주|다음\s*주|담주|이번\s*주|이번|다음)?\s*(월|화|수|목|금|토|일)요일").unwrap();
+    static ref WEEKDAY_EN: Regex =
+        Regex::new(&format!(r"(?i)\b(?:(next|this|coming)\s+(?:week\s+)?)?{}\b", EN_WEEKDAYS)).unwrap();
+    static ref DATE_WORD: Regex = Regex::new(
+        r"(?i)(day after tomorrow|오늘\s*밤|오늘|\btoday\b|\btonight\b|내일|\btomorrow\b|\btmrw?\b|모레|글피|다음\s*주말|이번\s*주말|주말|\bnext weekend\b|\bthis weekend\b|\bweekend\b|다음\s*주|\bnext week\b|다음\s*달|\bnext month\b)"
+    )
+    .unwrap();
+    static ref TIME_RANGE: Regex = Regex::new(&format!(
+        r"(?i){}\s*(?:-|~|–|\bto\b|부터|\buntil\b|\btill\b)\s*{}(?:\s*까지)?",
+        TIME, TIME
+    ))
+    .unwrap();
+    static ref TIME_SINGLE: Regex = Regex::new(&format!(r"(?i)(?:\b(at)\s+)?{}", TIME)).unwrap();
+    static ref TIME_WORD: Regex =
+        Regex::new(r"(?i)(정오|자정|\bnoon\b|\bmidnight\b|아침|점심|저녁|\bmorning\b|\blunch\b|\bafternoon\b|\bevening\b)").unwrap();
+    static ref DURATION_HOURS: Regex = Regex::new(
+        r"(?i)(?:\bfor\s+)?(\d+(?:\.\d+)?)\s*(?:시간|hours?\b|hrs?\b|h\b|h(\d{1,2})\s*m(?:ins?)?\b)(?:\s*(\d{1,2})\s*(?:분|minutes?\b|mins?\b|m\b))?(?:\s*동안)?"
+    )
+    .unwrap();
+    static ref DURATION_MINUTES: Regex =
+        Regex::new(r"(?i)(?:\bfor\s+)?(\d+)\s*(?:분|minutes?\b|mins?\b|m\b)(?:\s*동안)?").unwrap();
+    static ref LOCATION_LABEL: Regex = Regex::new(r"(?i)(?:장소|\blocation|\bplace|\bwhere)\s*[:：]\s*([^,\n]+)").unwrap();
+    static ref LOCATION_AT_SIGN: Regex = Regex::new(r"@\s*([^\s,]+)").unwrap();
+    static ref LOCATION_KO: Regex = Regex::new(r"([^\s,]+)\s*에서").unwrap();
+    static ref LOCATION_EN_PLACE: Regex =
+        Regex::new(r"(?i)\bat\s+(?:the\s+)?(gym|home|office|library|school|work|cafe|café|hospital|clinic)\b").unwrap();
+    static ref LOCATION_EN_NAME: Regex =
+        Regex::new(r"\b(?:at|in)\s+(?:the\s+)?([A-Z][\w'&.-]*(?:\s+[A-Z][\w'&.-]*)*)").unwrap();
+    static ref PRIORITY_URGENT: Regex = Regex::new(r"(?i)(긴급|급함|급해|\burgent\b|\basap\b|!!+)").unwrap();
+    static ref PRIORITY_HIGH: Regex = Regex::new(r"(?i)(중요|\bimportant\b|\bhigh priority\b)").unwrap();
+    static ref PRIORITY_LOW: Regex = Regex::new(r"(?i)(나중에|언젠가|\bsomeday\b|\blow priority\b|\bwhenever\b)").unwrap();
+}
+
+/// 제목에서 지우는 연결어
+const FILLER_WORDS: &[&str] = &[
+    "at", "on", "by", "until", "till", "for", "from", "to", "in", "@", "에", "까지", "부터", "에서", "쯤",
+];
+
+/// 기본 우선순위 (AI 파서와 같음)
+const DEFAULT_PRIORITY: i32 = 1;
+
+/// 입력에서 찾은 부분을 지워 가며 값을 꺼냄
+struct Scanner {
+    text: String,
+}
+
+type Groups = Vec<Option<String>>;
+
+impl Scanner {
+    /// `accept`가 값을 돌려주는 첫 일치 부분을 지우고 그 값을 반환
+    fn take<T>(&mut self, re: &Regex, accept: impl Fn(&Groups) -> Option<T>) -> Option<T> {
+        let (range, value) = re.captures_iter(&self.text).find_map(|captures| {
+            let groups: Groups = captures.iter().map(|m| m.map(|m| m.as_str().to_string())).collect();
+            let range = captures.get(0)?.range();
+            accept(&groups).map(|value| (range, value))
+        })?;
+        self.text.replace_range(range, " ");
+        Some(value)
+    }
+}
+
+fn number(groups: &Groups, index: usize) -> Option<i64> {
+    groups.get(index)?.as_deref()?.trim().parse().ok()
+}
+
+fn text(groups: &Groups, index: usize) -> Option<String> {
+    groups.get(index)?.as_ref().map(|s| s.trim().to_lowercase())
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let index = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"]
+        .iter()
+        .position(|m| name.to_lowercase().starts_with(m))?;
+    Some(index as u32 + 1)
+}
+
+/// 연도가 없는 날짜는 오늘 이후 가장 가까운 날
+fn upcoming(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if date >= today {
+        Some(date)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    }
+}
+
+fn weekday_from(name: &str) -> Option<Weekday> {
+    let name = name.to_lowercase();
+    Some(match name.as_str() {
+        "월" => Weekday::Mon,
+        "화" => Weekday::Tue,
+        "수" => Weekday::Wed,
+        "목" => Weekday::Thu,
+        "금" => Weekday::Fri,
+        "토" => Weekday::Sat,
+        "일" => Weekday::Sun,
+        _ if name.starts_with("mon") => Weekday::Mon,
+        _ if name.starts_with("tue") => Weekday::Tue,
+        _ if name.starts_with("wed") => Weekday::Wed,
+        _ if name.starts_with("thu") => Weekday::Thu,
+        _ if name.starts_with("fri") => Weekday::Fri,
+        _ if name.starts_with("sat") => Weekday::Sat,
+        _ if name.starts_with("sun") => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+/// 요일 날짜. `week_offset`이 없으면 오늘부터 가장 가까운 요일,
+/// 있으면 월요일 시작 기준 이번 주(0), 다음 주(1)의 요일
+fn weekday_date(today: NaiveDate, weekday: Weekday, week_offset: Option<i64>) -> NaiveDate {
+    let target = weekday.num_days_from_monday() as i64;
+    let current = today.weekday().num_days_from_monday() as i64;
+    match week_offset {
+        None => today + Duration::days((target - current).rem_euclid(7)),
+        Some(weeks) => today + Duration::days(target - current + weeks * 7),
+    }
+}
+
+fn parse_date(scanner: &mut Scanner, today: NaiveDate) -> Option<NaiveDate> {
+    if let Some(date) = scanner.take(&DATE_ISO, |g| {
+        NaiveDate::from_ymd_opt(number(g, 1)? as i32, number(g, 2)? as u32, number(g, 3)? as u32)
+    }) {
+        return Some(date);
+    }
+    if let Some(date) = scanner.take(&DATE_KO, |g| {
+        let (month, day) = (number(g, 2)? as u32, number(g, 3)? as u32);
+        match number(g, 1) {
+            Some(year) => NaiveDate::from_ymd_opt(year as i32, month, day),
+            None => upcoming(today, month, day),
+        }
+    }) {
+        return Some(date);
+    }
+    if let Some(date) = scanner.take(&DATE_EN, |g| upcoming(today, month_number(&text(g, 1)?)?, number(g, 2)? as u32)) {
+        return Some(date);
+    }
+    if let Some(date) = scanner.take(&DATE_EN_DAY_FIRST, |g| {
+        upcoming(today, month_number(&text(g, 2)?)?, number(g, 1)? as u32)
+    }) {
+        return Some(date);
+    }
+    if let Some(date) = scanner.take(&DATE_SLASH, |g| upcoming(today, number(g, 1)? as u32, number(g, 2)? as u32)) {
+        return Some(date);
+    }
+    if let Some(date) = scanner.take(&DATE_IN, |g| {
+        let (count, unit) = match number(g, 1) {
+            Some(count) => (count, text(g, 2)?),
+            None => (number(g, 3)?, text(g, 4)?),
+        };
+        let days = if unit.starts_with("week") || unit == "주" { count * 7 } else { count };
+        Some(today + Duration::days(days))
+    }) {
+        return Some(date);
+    }
+    if let Some(date) = scanner.take(&WEEKDAY_KO, |g| {
+        let weekday = weekday_from(&text(g, 2)?)?;
+        let offset = text(g, 1).map(|prefix| {
+            let prefix: String = prefix.split_whitespace().collect();
+            match prefix.as_str() {
+                "다다음주" => 2,
+                "다음주" | "담주" | "다음" => 1,
+                _ => 0,
+            }
+        });
+        Some(weekday_date(today, weekday, offset))
+    }) {
+        return Some(date);
+    }
+    if let Some(date) = scanner.take(&WEEKDAY_EN, |g| {
+        let weekday = weekday_from(&text(g, 2)?)?;
+        let offset = text(g, 1).map(|prefix| if prefix == "next" { 1 } else { 0 });
+        Some(match offset {
+            // "this friday", "coming friday"는 가장 가까운 요일
+            Some(0) => weekday_date(today, weekday, None),
+            offset => weekday_date(today, weekday, offset),
+        })
+    }) {
+        return Some(date);
+    }
+    scanner.take(&DATE_WORD, |g| {
+        let word: String = text(g, 1)?.split_whitespace().collect::<Vec<_>>().join(" ");
+        let compact = word.replace(' ', "");
+        Some(match (word.as_str(), compact.as_str()) {
+            ("day after tomorrow", _) | (_, "모레") => today + Duration::days(2),
+            (_, "오늘" | "오늘밤") | ("today" | "tonight", _) => today,
+            (_, "내일") | ("tomorrow" | "tmrw" | "tmr", _) => today + Duration::days(1),
+            (_, "글피") => today + Duration::days(3),
+            (_, "다음주말") | ("next weekend", _) => weekday_date(today, Weekday::Sat, Some(1)),
+            (_, "이번주말" | "주말") | ("this weekend" | "weekend", _) => weekday_date(today, Weekday::Sat, None),
+            (_, "다음주") | ("next week", _) => today + Duration::days(7),
+            (_, "다음달") | ("next month", _) => today.checked_add_months(Months::new(1))?,
+            _ => return None,
+        })
+    })
+}
+
+/// 시간 한 점의 (시, 분, 오전/오후 표시, 시간 표시가 있는지)
+struct TimePoint {
+    hour: i64,
+    minute: i64,
+    meridiem: Option<bool>,
+    marked: bool,
+}
+
+/// `TIME` 그룹(6개)을 `offset`부터 읽음
+fn time_point(groups: &Groups, offset: usize) -> Option<TimePoint> {
+    let hour = number(groups, offset + 1)?;
+    let minute = match (number(groups, offset + 2), text(groups, offset + 4)) {
+        (Some(minute), _) => minute,
+        (None, Some(half)) if half == "반" => 30,
+        (None, Some(minutes)) => minutes.trim_end_matches('분').trim().parse().ok()?,
+        (None, None) => 0,
+    };
+    let korean = text(groups, offset).map(|word| matches!(word.as_str(), "오후" | "저녁" | "밤"));
+    let english = text(groups, offset + 5).map(|word| word.starts_with('p'));
+    let meridiem = english.or(korean);
+    let marked = meridiem.is_some() || groups[offset + 2].is_some() || groups[offset + 3].is_some();
+    if hour > 23 || minute > 59 || (meridiem.is_some() && hour > 12) {
+        return None;
+    }
+    Some(TimePoint {
+        hour,
+        minute,
+        meridiem,
+        marked,
+    })
+}
+
+/// 24시간제 분. 오전/오후 표시가 없으면 1-6시는 오후로 봄
+fn to_minutes(point: &TimePoint, meridiem: Option<bool>) -> i64 {
+    let hour = match meridiem {
+        Some(true) if point.hour < 12 => point.hour + 12,
+        Some(false) if point.hour == 12 => 0,
+        None if (1..=6).contains(&point.hour) => point.hour + 12,
+        _ => point.hour,
+    };
+    hour * 60 + point.minute
+}
+
+fn format_minutes(minutes: i64) -> String {
+    format!("{:02}:{:02}", (minutes / 60) % 24, minutes % 60)
+}
+
+fn parse_time_range(scanner: &mut Scanner) -> Option<(i64, i64)> {
+    scanner.take(&TIME_RANGE, |g| {
+        let start = time_point(g, 1)?;
+        let end = time_point(g, 7)?;
+        if !start.marked && !end.marked {
+            return None;
+        }
+        // "2-4pm", "오후 2-4시"는 한쪽 표시를 같이 씀
+        let end_minutes = to_minutes(&end, end.meridiem.or(start.meridiem));
+        let mut start_minutes = to_minutes(&start, start.meridiem.or(end.meridiem));
+        if start.meridiem.is_none() && start_minutes > end_minutes {
+            start_minutes = to_minutes(&start, Some(false));
+        }
+        (end_minutes > start_minutes).then_some((start_minutes, end_minutes))
+    })
+}
+
+fn parse_time(scanner: &mut Scanner) -> Option<i64> {
+    scanner.take(&TIME_SINGLE, |g| {
+        let point = time_point(g, 2)?;
+        (point.marked || g[1].is_some()).then(|| to_minutes(&point, point.meridiem))
+    })
+}
+
+/// 숫자 없이 시간대만 말한 경우 ("점심", "tonight")
+fn parse_time_word(scanner: &mut Scanner) -> Option<i64> {
+    scanner.take(&TIME_WORD, |g| {
+        Some(match text(g, 1)?.as_str() {
+            "자정" | "midnight" => 0,
+            "아침" | "morning" => 9 * 60,
+            "정오" | "noon" | "점심" | "lunch" => 12 * 60,
+            "afternoon" => 14 * 60,
+            "저녁" | "evening" => 19 * 60,
+            _ => return None,
+        })
+    })
+}
+
+/// 시간 단위 소요시간 ("1시간 30분", "1.5h", "1h30m"). 시각보다 먼저 찾아야 "1시간"을 1시로 읽지 않음
+fn parse_hours(scanner: &mut Scanner) -> Option<i64> {
+    scanner.take(&DURATION_HOURS, |g| {
+        let hours: f64 = g.get(1)?.as_deref()?.parse().ok()?;
+        let minutes = number(g, 2).or(number(g, 3)).unwrap_or(0);
+        Some((hours * 60.0).round() as i64 + minutes)
+    })
+}
+
+/// 분 단위 소요시간 ("30분", "15 min"). "3시 30분"의 분을 가져가지 않게 시각 다음에 찾음
+fn parse_minutes(scanner: &mut Scanner) -> Option<i64> {
+    scanner.take(&DURATION_MINUTES, |g| number(g, 1))
+}
+
+fn parse_location(scanner: &mut Scanner) -> Option<String> {
+    let name = |g: &Groups| g.get(1)?.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string);
+    scanner
+        .take(&LOCATION_LABEL, name)
+        .or_else(|| scanner.take(&LOCATION_AT_SIGN, name))
+        .or_else(|| scanner.take(&LOCATION_KO, name))
+        .or_else(|| scanner.take(&LOCATION_EN_NAME, name))
+        .or_else(|| scanner.take(&LOCATION_EN_PLACE, name))
+}
+
+fn parse_priority(scanner: &mut Scanner) -> Option<i32> {
+    let found = |g: &Groups| g.get(1).map(|_| ());
+    if scanner.take(&PRIORITY_URGENT, found).is_some() {
+        Some(3)
+    } else if scanner.take(&PRIORITY_HIGH, found).is_some() {
+        Some(2)
+    } else if scanner.take(&PRIORITY_LOW, found).is_some() {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+/// 남은 부분에서 연결어와 구두점을 정리한 제목
+fn clean_title(rest: &str) -> String {
+    rest.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| matches!(c, ',' | ';' | ':' | '.' | '-' | '~' | '!')))
+        .filter(|word| !word.is_empty())
+        .filter(|word| !FILLER_WORDS.contains(&word.to_lowercase().as_str()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 자연어 입력을 태스크 정보로 변환 (네트워크 없이)
+///
+/// 날짜가 없으면 `today`, 우선순위가 없으면 1입니다.
+pub fn parse_task(input: &str, today: NaiveDate) -> ParseTaskResponse {
+    let mut scanner = Scanner {
+        text: input.trim().to_string(),
+    };
+
+    let priority = parse_priority(&mut scanner);
+    let date = parse_date(&mut scanner, today);
+    // "tonight"은 날짜와 함께 밤 시간을 뜻함
+    let tonight = input.to_lowercase().contains("tonight") || input.contains("오늘 밤") || input.contains("오늘밤");
+    let hours = parse_hours(&mut scanner);
+    let range = parse_time_range(&mut scanner);
+    let start = range
+        .map(|(start, _)| start)
+        .or_else(|| parse_time(&mut scanner))
+        .or_else(|| parse_time_word(&mut scanner))
+        .or_else(|| tonight.then_some(21 * 60));
+    let duration = hours
+        .or_else(|| parse_minutes(&mut scanner))
+        .filter(|minutes| (1..=24 * 60).contains(minutes))
+        .or(range.map(|(start, end)| end - start));
+    let location = parse_location(&mut scanner);
+
+    let end = range
+        .map(|(_, end)| end)
+        .or_else(|| Some(start? + duration?).filter(|end| *end < 24 * 60));
+
+    let title = clean_title(&scanner.text);
+    ParseTaskResponse {
+        title: if title.is_empty() { input.trim().to_string() } else { title },
+        scheduled_date: Some(date.unwrap_or(today).format("%Y-%m-%d").to_string()),
+        scheduled_time: start.map(format_minutes),
+        end_time: end.map(format_minutes),
+        location,
+        subtasks: None,
+        priority: Some(priority.unwrap_or(DEFAULT_PRIORITY)),
+        estimated_duration: duration.map(|minutes| minutes as i32),
+        parsed_offline: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-03-04 (수요일)
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 4).unwrap()
+    }
+
+    struct Case {
+        input: &'static str,
+        title: &'static str,
+        date: &'static str,
+        time: Option<&'static str>,
+        end: Option<&'static str>,
+        duration: Option<i32>,
+        location: Option<&'static str>,
+        priority: i32,
+    }
+
+    const fn case(input: &'static str, title: &'static str, date: &'static str) -> Case {
+        Case {
+            input,
+            title,
+            date,
+            time: None,
+            end: None,
+            duration: None,
+            location: None,
+            priority: DEFAULT_PRIORITY,
+        }
+    }
+
+    impl Case {
+        const fn at(mut self, time: &'static str) -> Self {
+            self.time = Some(time);
+            self
+        }
+        const fn until(mut self, end: &'static str, duration: i32) -> Self {
+            self.end = Some(end);
+            self.duration = Some(duration);
+            self
+        }
+        const fn lasting(mut self, duration: i32) -> Self {
+            self.duration = Some(duration);
+            self
+        }
+        const fn place(mut self, location: &'static str) -> Self {
+            self.location = Some(location);
+            self
+        }
+        const fn priority(mut self, priority: i32) -> Self {
+            self.priority = priority;
+            self
+        }
+    }
+
+    #[test]
+    fn test_parse_task_table() {
+        let cases = [
+            // 한국어 상대 날짜
+            case("내일 오후 3시 치과", "치과", "2026-03-05").at("15:00"),
+            case("모레 10:30 팀 회의 1시간", "팀 회의", "2026-03-06").at("10:30").until("11:30", 60),
+            case("다음주 월요일 9시 출근", "출근", "2026-03-09").at("09:00"),
+            case("이번주 금요일 발표 준비", "발표 준비", "2026-03-06"),
+            case("금요일까지 보고서 마무리", "보고서 마무리", "2026-03-06"),
+            case("다음주 장보기", "장보기", "2026-03-11"),
+            case("글피 이사 준비", "이사 준비", "2026-03-07"),
+            case("2주 후 병원 예약", "병원 예약", "2026-03-18"),
+            case("3일 뒤 택배 반품", "택배 반품", "2026-03-07"),
+            case("주말에 대청소", "대청소", "2026-03-07"),
+            case("다음 달 건강검진", "건강검진", "2026-04-04"),
+            // 한국어 날짜와 시간
+            case("3월 15일 아침 7시 러닝 30분", "러닝", "2026-03-15").at("07:00").until("07:30", 30),
+            case("2027년 1월 2일 신년회", "신년회", "2027-01-02"),
+            case("중요 프로젝트 회의 내일 3시 반", "프로젝트 회의", "2026-03-05").at("15:30").priority(2),
+            case("오후 2-4시 스터디카페에서 공부", "공부", "2026-03-04")
+                .at("14:00")
+                .until("16:00", 120)
+                .place("스터디카페"),
+            case("3시부터 5시까지 코딩", "코딩", "2026-03-04").at("15:00").until("17:00", 120),
+            case("밤 11시 일기 쓰기 10분", "일기 쓰기", "2026-03-04").at("23:00").until("23:10", 10),
+            case("점심 약속 강남역에서", "약속", "2026-03-04").at("12:00").place("강남역"),
+            case("긴급 세금 납부", "세금 납부", "2026-03-04").priority(3),
+            case("나중에 책장 정리", "책장 정리", "2026-03-04").priority(0),
+            case("1시간 30분 동안 독서", "독서", "2026-03-04").lasting(90),
+            // 영어 상대 날짜
+            case("tomorrow dentist at 3", "dentist", "2026-03-05").at("15:00"),
+            case("next friday lunch with Sam at 12:30", "lunch with Sam", "2026-03-13").at("12:30"),
+            case("call mom in 3 days", "call mom", "2026-03-07"),
+            case("finish report by friday", "finish report", "2026-03-06"),
+            case("this sunday 10am brunch at Cafe Onion", "brunch", "2026-03-08")
+                .at("10:00")
+                .place("Cafe Onion"),
+            case("day after tomorrow haircut", "haircut", "2026-03-06"),
+            case("pay rent in 2 weeks", "pay rent", "2026-03-18"),
+            case("review PR wed", "review PR", "2026-03-04"),
+            case("team sync next week", "team sync", "2026-03-11"),
+            // 영어 날짜와 시간
+            case("gym 2-4pm", "gym", "2026-03-04").at("14:00").until("16:00", 120),
+            case("march 20th dinner 7pm", "dinner", "2026-03-20").at("19:00"),
+            case("2 jan party", "party", "2027-01-02"),
+            case("2026-04-01 15:00~16:30 세미나", "세미나", "2026-04-01").at("15:00").until("16:30", 90),
+            case("11am-1pm workshop", "workshop", "2026-03-04").at("11:00").until("13:00", 120),
+            case("11-1pm workshop", "workshop", "2026-03-04").at("11:00").until("13:00", 120),
+            case("1.5h deep work session tonight", "deep work session", "2026-03-04")
+                .at("21:00")
+                .until("22:30", 90),
+            case("standup 9:15 for 15 min", "standup", "2026-03-04").at("09:15").until("09:30", 15),
+            case("1h30m piano practice", "piano practice", "2026-03-04").lasting(90),
+            case("3/10 meeting @Room301 30 min", "meeting", "2026-03-10").lasting(30).place("Room301"),
+            case("1/5 renew passport", "renew passport", "2027-01-05"),
+            case("yoga 20min at home", "yoga", "2026-03-04").lasting(20).place("home"),
+            case("location: City Hall, submit form", "submit form", "2026-03-04").place("City Hall"),
+            case("urgent: pay rent today", "pay rent", "2026-03-04").priority(3),
+            case("important call with bank", "call with bank", "2026-03-04").priority(2),
+            // 해석할 것이 없는 입력
+            case("read a book", "read a book", "2026-03-04"),
+            case("buy 3 hotdogs", "buy 3 hotdogs", "2026-03-04"),
+            case("시험 공부", "시험 공부", "2026-03-04"),
+            case("내일", "내일", "2026-03-05"),
+        ];
+
+        for case in cases {
+            let parsed = parse_task(case.input, today());
+            assert_eq!(parsed.title, case.title, "title of {:?}", case.input);
+            assert_eq!(parsed.scheduled_date.as_deref(), Some(case.date), "date of {:?}", case.input);
+            assert_eq!(parsed.scheduled_time.as_deref(), case.time, "time of {:?}", case.input);
+            assert_eq!(parsed.end_time.as_deref(), case.end, "end of {:?}", case.input);
+            assert_eq!(parsed.estimated_duration, case.duration, "duration of {:?}", case.input);
+            assert_eq!(parsed.location.as_deref(), case.location, "location of {:?}", case.input);
+            assert_eq!(parsed.priority, Some(case.priority), "priority of {:?}", case.input);
+        }
+    }
+
+    #[test]
+    fn test_parsed_task_passes_validation() {
+        use crate::llm::StructuredOutput;
+        for input in ["내일 오후 3시 치과", "gym 2-4pm", "11:50pm snack 30 min", "25시 회의", "at 99:99"] {
+            let parsed = parse_task(input, today());
+            assert!(parsed.validate().is_ok(), "{:?} -> {:?}", input, parsed);
+            assert!(parsed.parsed_offline);
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! AI가 구조화한 계획을 실제 일정으로 만들기
+//!
+//! 반복 주기가 있는 제안 태스크는 `RecurringPlan`으로, 목표 날짜가 있는 마일스톤에 속한 태스크는
+//! 그 날짜의 태스크로 만듭니다. 나머지 태스크는 하루 태스크 생성에서 다룹니다.
+
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+
+use crate::agent::{self, AppliedChanges, Change, ProposedChange};
+use crate::models::{generate_id, now_iso, ParsedPlanContent, Plan, RecurrenceType, SuggestedTask, Task, TaskStatus};
+use crate::recurring::rrule::RRule;
+use crate::recurring::{self, MonthDayPolicy, RecurringPlan};
+
+/// 이미 만들어 둔 일정 (다시 실행해도 중복 생성하지 않도록)
+#[derive(Debug, Clone, Default)]
+pub struct Existing {
+    /// 계획에 연결된 반복 일정 제목
+    pub recurring_titles: Vec<String>,
+    /// 계획에 연결된 태스크 (제목, 날짜)
+    pub tasks: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaterializedPlan {
+    pub changes: Vec<ProposedChange>,
+    pub applied: AppliedChanges,
+}
+
+fn recurring_plan(plan: &Plan, task: &SuggestedTask, start_date: &str) -> Option<RecurringPlan> {
+    let pattern = task.frequency.as_ref()?;
+    // Custom은 RRULE이 있을 때만
+    let rrule = match pattern.recurrence_type {
+        RecurrenceType::Custom => Some(pattern.rrule.as_deref()?.parse::<RRule>().ok()?),
+        _ => None,
+    };
+    let recurrence_type = match pattern.recurrence_type {
+        RecurrenceType::Daily => recurring::RecurrenceType::Daily,
+        RecurrenceType::Weekly => recurring::RecurrenceType::Weekly,
+        RecurrenceType::Monthly => recurring::RecurrenceType::Monthly,
+        RecurrenceType::Yearly => recurring::RecurrenceType::Yearly,
+        RecurrenceType::Custom => recurring::RecurrenceType::from_rule(rrule.as_ref()?),
+    };
+
+    // 요일/날짜가 없으면 시작일 기준
+    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d").ok()?;
+    let days_of_week = match recurrence_type {
+        recurring::RecurrenceType::Weekly if rrule.is_none() => pattern
+            .days_of_week
+            .clone()
+            .or_else(|| Some(vec![start.weekday().num_days_from_sunday() as i32])),
+        _ => pattern.days_of_week.clone(),
+    };
+    let day_of_month = match recurrence_type {
+        recurring::RecurrenceType::Monthly if rrule.is_none() => pattern.day_of_month.or(Some(start.day() as i32)),
+        _ => pattern.day_of_month,
+    };
+
+    let now = now_iso();
+    Some(RecurringPlan {
+        id: generate_id(),
+        plan_id: Some(plan.id.clone()),
+        title: task.title.clone(),
+        description: None,
+        location: None,
+        recurrence_type,
+        interval_value: pattern.interval.max(1),
+        days_of_week,
+        day_of_month,
+        scheduled_time: None,
+        end_time: None,
+        estimated_duration: task.estimated_duration,
+        start_date: start_date.to_string(),
+        end_date: pattern.end_date.clone().or_else(|| plan.end_date.clone()),
+        rrule: rrule.map(|rule| rule.to_string()),
+        month_day_policy: MonthDayPolicy::Clamp,
+        exceptions: Vec::new(),
+        is_active: true,
+        created_at: now.clone(),
+        updated_at: now,
+    })
+}
+
+/// 계획 구조에서 만들 반복 일정과 태스크
+///
+/// 계획 시작일이 `today`보다 늦으면 계획 시작일부터, 지난 마일스톤은 건너뜁니다.
+pub fn materialize(plan: &Plan, content: &ParsedPlanContent, today: &str, existing: &Existing) -> Vec<ProposedChange> {
+    let start_date = plan
+        .start_date
+        .as_deref()
+        .filter(|d| *d > today)
+        .unwrap_or(today);
+    let mut changes = Vec::new();
+
+    for task in &content.suggested_tasks {
+        if task.frequency.is_none() || existing.recurring_titles.contains(&task.title) {
+            continue;
+        }
+        if let Some(recurring) = recurring_plan(plan, task, start_date) {
+            let summary = format!("Create recurring \"{}\" from {}", recurring.title, recurring.start_date);
+            changes.push(ProposedChange {
+                summary,
+                change: Change::CreateRecurringPlan { plan: recurring },
+            });
+        }
+    }
+
+    for milestone in &content.milestones {
+        let Some(date) = milestone.target_date.as_deref().filter(|d| *d >= start_date) else {
+            continue;
+        };
+        let tasks = content
+            .suggested_tasks
+            .iter()
+            .filter(|t| t.frequency.is_none() && milestone.tasks.contains(&t.title))
+            .filter(|t| !existing.tasks.iter().any(|(title, d)| *title == t.title && d == date));
+        for task in tasks {
+            let now = now_iso();
+            let summary = format!("Create \"{}\" on {} ({})", task.title, date, milestone.title);
+            changes.push(ProposedChange {
+                summary,
+                change: Change::CreateTask {
+                    task: Task {
+                        id: generate_id(),
+                        plan_id: Some(plan.id.clone()),
+                        title: task.title.clone(),
+                        description: Some(milestone.title.clone()),
+                        location: None,
+                        scheduled_date: date.to_string(),
+                        scheduled_time: None,
+                        estimated_duration: task.estimated_duration,
+                        actual_duration: None,
+                        priority: task.priority,
+                        status: TaskStatus::Pending,
+                        order_index: 0,
+                        subtasks: None,
+                        created_at: now.clone(),
+                        updated_at: now,
+                        completed_at: None,
+                    },
+                },
+            });
+        }
+    }
+
+    changes
+}
+
+async fn existing(pool: &SqlitePool, plan_id: &str) -> Result<Existing, sqlx::Error> {
+    let recurring_titles = sqlx::query_scalar("SELECT title FROM recurring_plans WHERE plan_id = ?")
+        .bind(plan_id)
+        .fetch_all(pool)
+        .await?;
+    let tasks = sqlx::query("SELECT title, scheduled_date FROM tasks WHERE plan_id = ?")
+        .bind(plan_id)
+        .fetch_all(pool)
+        .await?
+        .iter()
+        .map(|row| (row.get("title"), row.get("scheduled_date")))
+        .collect();
+    Ok(Existing {
+        recurring_titles,
+        tasks,
+    })
+}
+
+/// 계획 구조를 저장하고 반복 일정과 태스크를 만듦 (반복 태스크는 `horizon_days`까지)
+pub async fn materialize_plan(
+    pool: &SqlitePool,
+    plan: &Plan,
+    content: &ParsedPlanContent,
+    today: &str,
+    horizon_days: i64,
+) -> Result<MaterializedPlan, sqlx::Error> {
+    sqlx::query("UPDATE plans SET parsed_content = ?, updated_at = ? WHERE id = ?")
+        .bind(serde_json::to_string(content).unwrap_or_default())
+        .bind(now_iso())
+        .bind(&plan.id)
+        .execute(pool)
+        .await?;
+
+    let changes = materialize(plan, content, today, &existing(pool, &plan.id).await?);
+    let applied = agent::apply(pool, &changes, horizon_days).await?;
+    Ok(MaterializedPlan { changes, applied })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Milestone, RecurrencePattern};
+
+    fn suggested(title: &str, frequency: Option<RecurrenceType>) -> SuggestedTask {
+        SuggestedTask {
+            title: title.to_string(),
+            estimated_duration: Some(30),
+            priority: 1,
+            frequency: frequency.map(|recurrence_type| RecurrencePattern {
+                recurrence_type,
+                interval: 1,
+                days_of_week: None,
+                day_of_month: None,
+                end_date: None,
+                rrule: None,
+            }),
+        }
+    }
+
+    fn toefl() -> (Plan, ParsedPlanContent) {
+        let plan = Plan {
+            id: "toefl".to_string(),
+            title: "TOEFL".to_string(),
+            description: None,
+            original_input: None,
+            parsed_content: None,
+            priority: 0,
+            start_date: Some("2026-03-02".to_string()),
+            end_date: Some("2026-03-31".to_string()),
+            recurrence: None,
+            status: Default::default(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let content = ParsedPlanContent {
+            goals: vec!["Score 100".to_string()],
+            milestones: vec![
+                Milestone {
+                    title: "Mock test".to_string(),
+                    target_date: Some("2026-03-20".to_string()),
+                    tasks: vec!["Take mock test".to_string(), "Vocabulary".to_string()],
+                },
+                Milestone {
+                    title: "Kickoff".to_string(),
+                    target_date: Some("2026-02-01".to_string()),
+                    tasks: vec!["Buy books".to_string()],
+                },
+            ],
+            suggested_tasks: vec![
+                suggested("Vocabulary", Some(RecurrenceType::Daily)),
+                suggested("Weekly review", Some(RecurrenceType::Weekly)),
+                suggested("Take mock test", None),
+                suggested("Buy books", None),
+                suggested("Read a passage", None),
+            ],
+        };
+        (plan, content)
+    }
+
+    #[test]
+    fn test_materialize_plan_structure() {
+        let (plan, content) = toefl();
+        let changes = materialize(&plan, &content, "2026-02-25", &Existing::default());
+
+        let summaries: Vec<&str> = changes.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "Create recurring \"Vocabulary\" from 2026-03-02",
+                "Create recurring \"Weekly review\" from 2026-03-02",
+                "Create \"Take mock test\" on 2026-03-20 (Mock test)",
+            ]
+        );
+        match &changes[1].change {
+            // 2026-03-02는 월요일
+            Change::CreateRecurringPlan { plan } => {
+                assert_eq!(plan.days_of_week, Some(vec![1]));
+                assert_eq!(plan.end_date.as_deref(), Some("2026-03-31"));
+            }
+            other => panic!("unexpected change {:?}", other),
+        }
+
+        let existing = Existing {
+            recurring_titles: vec!["Vocabulary".to_string(), "Weekly review".to_string()],
+            tasks: vec![("Take mock test".to_string(), "2026-03-20".to_string())],
+        };
+        assert!(materialize(&plan, &content, "2026-02-25", &existing).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_materialize_plan_is_idempotent() {
+        let pool = crate::db::test_pool().await;
+        let (plan, content) = toefl();
+        sqlx::query("INSERT INTO plans (id, title, status, created_at, updated_at) VALUES (?, ?, 'active', '', '')")
+            .bind(&plan.id)
+            .bind(&plan.title)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let first = materialize_plan(&pool, &plan, &content, "2026-02-25", 14).await.unwrap();
+        assert_eq!(first.applied.applied, 3);
+        let second = materialize_plan(&pool, &plan, &content, "2026-02-25", 14).await.unwrap();
+        assert!(second.changes.is_empty());
+
+        let stored = crate::db::plans_by_ids(&pool, std::slice::from_ref(&plan.id)).await.unwrap();
+        assert_eq!(stored[0].parsed_content.as_ref().unwrap().milestones.len(), 2);
+        let vocabulary: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE title = 'Vocabulary'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        // 03-02~03-31 매일 중 설정한 2주만 바로 만들고 나머지는 롤링 윈도우가 채움
+        assert_eq!(vocabulary, 15);
+    }
+}
@@ -0,0 +1,814 @@
+//! 반복 일정의 발생별 예외와 "이번만 / 이후 모두 / 전체" 수정
+//!
+//! 예외는 규칙상 날짜(`original_date`)로 찾습니다. 이미 태스크로 생성된 발생은 태스크도 함께 바꾸고,
+//! `generated_tasks`의 날짜를 태스크 날짜에 맞춰 같은 발생을 다시 만들지 않도록 합니다.
+//! 완료한 태스크는 건드리지 않습니다.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::BTreeSet;
+
+use super::rrule::RRule;
+use super::{format_date, occurrence_on, parse_date, time_to_minutes, Occurrence, RecurrenceType, RecurringPlan};
+use crate::db;
+use crate::llm::structured::{check_date, check_time};
+use crate::models::{generate_id, now_iso};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExceptionKind {
+    /// 이 발생은 없음 (EXDATE)
+    Skip,
+    /// 이 발생의 날짜/시간/제목/장소 변경
+    Override,
+}
+
+impl ExceptionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExceptionKind::Skip => "skip",
+            ExceptionKind::Override => "override",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceException {
+    pub id: String,
+    pub recurring_plan_id: String,
+    /// 규칙상 날짜 "YYYY-MM-DD"
+    pub original_date: String,
+    pub kind: ExceptionKind,
+    /// 옮긴 날짜 (None이면 원래 날짜)
+    pub date: Option<String>,
+    pub scheduled_time: Option<String>,
+    pub end_time: Option<String>,
+    pub title: Option<String>,
+    pub location: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 수정 범위
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum EditScope {
+    /// 이번 발생만
+    This,
+    /// 이번 발생부터 이후 모두 (반복 일정을 둘로 나눔)
+    ThisAndFollowing,
+    /// 반복 일정 전체
+    All,
+}
+
+/// 바꿀 값 (None이면 그대로)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OccurrenceEdit {
+    /// 옮길 날짜 (`EditScope::This`에서만)
+    pub date: Option<String>,
+    pub scheduled_time: Option<String>,
+    pub end_time: Option<String>,
+    pub title: Option<String>,
+    pub location: Option<String>,
+}
+
+impl OccurrenceEdit {
+    fn validate(&self) -> Result<(), String> {
+        check_date("date", self.date.as_deref())?;
+        check_time("scheduledTime", self.scheduled_time.as_deref())?;
+        check_time("endTime", self.end_time.as_deref())?;
+        if self.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            return Err("title must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    fn changes_time(&self) -> bool {
+        self.scheduled_time.is_some() || self.end_time.is_some()
+    }
+}
+
+/// 수정 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OccurrenceChanges {
+    /// 수정한 발생이 속한 반복 일정 id ("이후 모두"로 나눴으면 새 반복 일정)
+    pub recurring_plan_id: String,
+    pub updated_tasks: usize,
+    pub deleted_tasks: usize,
+    /// 태스크가 바뀐 날짜 (화면 갱신용)
+    pub dates: Vec<String>,
+}
+
+/// 반복 일정을 `at`(규칙상 발생 날짜)에서 둘로 나눔: (`at` 전날에 끝나는 기존 일정, `at`부터의 새 일정)
+///
+/// `at` 이전에 발생이 없으면 나눌 것이 없으므로 None입니다. COUNT가 있는 RRULE은 횟수도 나눕니다.
+pub fn split_series(plan: &RecurringPlan, at: NaiveDate) -> Result<Option<(RecurringPlan, RecurringPlan)>, String> {
+    let start = parse_date(&plan.start_date)?;
+    let rule = plan.rule()?;
+    let Some(day_before) = at.pred_opt().filter(|d| *d >= start) else {
+        return Ok(None);
+    };
+    let before = rule.between(start, start, day_before).len() as u32;
+    if before == 0 {
+        return Ok(None);
+    }
+
+    let now = now_iso();
+    let mut ended = plan.clone();
+    ended.end_date = Some(format_date(day_before));
+    ended.exceptions.retain(|e| e.original_date < format_date(at));
+    ended.updated_at = now.clone();
+
+    let mut following = plan.clone();
+    following.id = generate_id();
+    following.start_date = format_date(at);
+    following.exceptions = plan
+        .exceptions
+        .iter()
+        .filter(|e| e.original_date >= format_date(at))
+        .cloned()
+        .map(|e| RecurrenceException {
+            recurring_plan_id: following.id.clone(),
+            ..e
+        })
+        .collect();
+    following.created_at = now.clone();
+    following.updated_at = now;
+    // 날짜를 시작일에서 가져오는 월간 반복은 새 시작일(말일로 당긴 날짜일 수 있음) 대신 원래 날짜로 고정
+    if plan.recurrence_type == RecurrenceType::Monthly && plan.day_of_month.is_none() {
+        following.day_of_month = Some(start.day() as i32);
+    }
+
+    if let (Some(count), Some(rrule)) = (rule.count, plan.rrule.as_deref()) {
+        let mut ended_rule: RRule = rrule.parse()?;
+        ended_rule.count = Some(before);
+        ended.rrule = Some(ended_rule.to_string());
+        let mut following_rule = ended_rule;
+        following_rule.count = Some(count.saturating_sub(before).max(1));
+        following.rrule = Some(following_rule.to_string());
+    }
+    Ok(Some((ended, following)))
+}
+
+/// 발생 하나를 수정 (`scope`에 따라 이후 발생이나 전체도)
+///
+/// `date`는 지금 보이는 발생 날짜입니다 (옮긴 발생이면 옮긴 날짜). 날짜 이동은 이번 발생만 할 수 있습니다.
+/// 전체 수정은 `today` 이후 태스크에만 반영합니다.
+pub async fn edit_occurrence(
+    pool: &SqlitePool,
+    recurring_plan_id: &str,
+    date: &str,
+    scope: EditScope,
+    edit: &OccurrenceEdit,
+    today: &str,
+) -> Result<OccurrenceChanges, String> {
+    edit.validate()?;
+    let (plan, occurrence) = load_occurrence(pool, recurring_plan_id, date).await?;
+    if edit.date.is_some() && scope != EditScope::This {
+        return Err("Only a single occurrence can be moved to another date".to_string());
+    }
+
+    let database = |e: sqlx::Error| e.to_string();
+    let mut tx = pool.begin().await.map_err(database)?;
+    let mut changes = OccurrenceChanges {
+        recurring_plan_id: plan.id.clone(),
+        updated_tasks: 0,
+        deleted_tasks: 0,
+        dates: Vec::new(),
+    };
+    let mut dates = BTreeSet::new();
+
+    let split = match scope {
+        EditScope::ThisAndFollowing => split_series(&plan, occurrence.original_date)?,
+        _ => None,
+    };
+    match (scope, split) {
+        (EditScope::This, _) => {
+            let exception = override_exception(&plan, &occurrence, edit);
+            let moved = plan.occurrence(occurrence.original_date, Some(&exception));
+            if moved.date != occurrence.date && occurrence_on(&plan, &format_date(moved.date)).is_some() {
+                return Err(format!("{} already occurs on {}", plan.title, format_date(moved.date)));
+            }
+            db::upsert_recurrence_exception(&mut tx, &exception).await.map_err(database)?;
+
+            let task_edit = OccurrenceEdit {
+                date: Some(format_date(moved.date)),
+                ..edit.clone()
+            };
+            let duration = edit.changes_time().then(|| moved.minutes()).flatten();
+            let from = format_date(occurrence.date);
+            for (task_id, task_date) in generated_tasks(&mut tx, &plan.id, &from, &from).await.map_err(database)? {
+                // 완료한 태스크는 옮기지 않으므로 기록된 날짜도 그대로
+                if !update_task(&mut tx, &task_id, &task_edit, duration).await.map_err(database)? {
+                    continue;
+                }
+                changes.updated_tasks += 1;
+                dates.extend([task_date, format_date(moved.date)]);
+                sqlx::query("UPDATE generated_tasks SET scheduled_date = ? WHERE recurring_plan_id = ? AND task_id = ?")
+                    .bind(format_date(moved.date))
+                    .bind(&plan.id)
+                    .bind(&task_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(database)?;
+            }
+        }
+        (EditScope::ThisAndFollowing, Some((ended, mut following))) => {
+            apply_series_edit(&mut following, edit);
+            db::update_recurring_plan(&mut tx, &ended).await.map_err(database)?;
+            db::insert_recurring_plan(&mut tx, &following).await.map_err(database)?;
+            // 이미 만든 범위도 이어받아야 지운 태스크가 다시 생기지 않음
+            let until = db::materialized_until(&mut tx, &plan.id).await.map_err(database)?;
+            db::set_materialized_until(&mut tx, &following.id, until.as_deref()).await.map_err(database)?;
+            sqlx::query(
+                "UPDATE recurrence_exceptions SET recurring_plan_id = ? \
+                 WHERE recurring_plan_id = ? AND original_date >= ?",
+            )
+            .bind(&following.id)
+            .bind(&plan.id)
+            .bind(&following.start_date)
+            .execute(&mut *tx)
+            .await
+            .map_err(database)?;
+            sqlx::query(
+                "UPDATE generated_tasks SET recurring_plan_id = ? WHERE recurring_plan_id = ? AND scheduled_date >= ?",
+            )
+            .bind(&following.id)
+            .bind(&plan.id)
+            .bind(format_date(occurrence.date))
+            .execute(&mut *tx)
+            .await
+            .map_err(database)?;
+
+            // 지난 발생은 전체 수정처럼 그대로 둠
+            let from = following.start_date.as_str().max(today);
+            changes.updated_tasks = update_series_tasks(&mut tx, &following, edit, from, &mut dates)
+                .await
+                .map_err(database)?;
+            changes.recurring_plan_id = following.id;
+        }
+        // "이후 모두"인데 첫 발생이면 전체 수정과 같음
+        (_, _) => {
+            let mut edited = plan.clone();
+            apply_series_edit(&mut edited, edit);
+            db::update_recurring_plan(&mut tx, &edited).await.map_err(database)?;
+            changes.updated_tasks = update_series_tasks(&mut tx, &edited, edit, today, &mut dates)
+                .await
+                .map_err(database)?;
+        }
+    }
+
+    for date in &dates {
+        db::refresh_daily_progress(&mut tx, date).await.map_err(database)?;
+    }
+    tx.commit().await.map_err(database)?;
+    changes.dates = dates.into_iter().collect();
+    Ok(changes)
+}
+
+/// 발생 하나를 취소 (`scope`에 따라 이후 발생이나 전체도)
+///
+/// 생성된 태스크 중 완료하지 않은 것은 지웁니다. 전체 취소는 반복 일정을 비활성화하고 `today` 이후 태스크만 지웁니다.
+pub async fn skip_occurrence(
+    pool: &SqlitePool,
+    recurring_plan_id: &str,
+    date: &str,
+    scope: EditScope,
+    today: &str,
+) -> Result<OccurrenceChanges, String> {
+    let (plan, occurrence) = load_occurrence(pool, recurring_plan_id, date).await?;
+
+    let database = |e: sqlx::Error| e.to_string();
+    let mut tx = pool.begin().await.map_err(database)?;
+    let mut dates = BTreeSet::new();
+    let from = format_date(occurrence.date);
+
+    let split = match scope {
+        EditScope::ThisAndFollowing => split_series(&plan, occurrence.original_date)?,
+        _ => None,
+    };
+    let deleted = match (scope, split) {
+        (EditScope::This, _) => {
+            let now = now_iso();
+            let exception = RecurrenceException {
+                id: generate_id(),
+                recurring_plan_id: plan.id.clone(),
+                original_date: format_date(occurrence.original_date),
+                kind: ExceptionKind::Skip,
+                date: None,
+                scheduled_time: None,
+                end_time: None,
+                title: None,
+                location: None,
+                created_at: now.clone(),
+                updated_at: now,
+            };
+            db::upsert_recurrence_exception(&mut tx, &exception).await.map_err(database)?;
+            delete_tasks(&mut tx, &plan.id, &from, &from, &mut dates).await.map_err(database)?
+        }
+        (EditScope::ThisAndFollowing, Some((ended, _))) => {
+            db::update_recurring_plan(&mut tx, &ended).await.map_err(database)?;
+            sqlx::query("DELETE FROM recurrence_exceptions WHERE recurring_plan_id = ? AND original_date >= ?")
+                .bind(&plan.id)
+                .bind(format_date(occurrence.original_date))
+                .execute(&mut *tx)
+                .await
+                .map_err(database)?;
+            delete_tasks(&mut tx, &plan.id, &from, "9999-12-31", &mut dates).await.map_err(database)?
+        }
+        // "이후 모두"인데 첫 발생이면 전체 취소와 같음
+        (_, _) => {
+            sqlx::query("UPDATE recurring_plans SET is_active = 0, updated_at = ? WHERE id = ?")
+                .bind(now_iso())
+                .bind(&plan.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(database)?;
+            delete_tasks(&mut tx, &plan.id, today, "9999-12-31", &mut dates).await.map_err(database)?
+        }
+    };
+
+    for date in &dates {
+        db::refresh_daily_progress(&mut tx, date).await.map_err(database)?;
+    }
+    tx.commit().await.map_err(database)?;
+    Ok(OccurrenceChanges {
+        recurring_plan_id: plan.id,
+        updated_tasks: 0,
+        deleted_tasks: deleted,
+        dates: dates.into_iter().collect(),
+    })
+}
+
+/// 반복 일정과 `date`에 보이는 발생
+async fn load_occurrence(
+    pool: &SqlitePool,
+    recurring_plan_id: &str,
+    date: &str,
+) -> Result<(RecurringPlan, Occurrence), String> {
+    let plan = db::recurring_plan_by_id(pool, recurring_plan_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Recurring plan not found: {}", recurring_plan_id))?;
+    parse_date(date)?;
+    let occurrence = occurrence_on(&plan, date).ok_or_else(|| format!("{} does not occur on {}", plan.title, date))?;
+    Ok((plan, occurrence))
+}
+
+/// 기존 예외에 이번 수정을 합친 override 예외
+fn override_exception(plan: &RecurringPlan, occurrence: &Occurrence, edit: &OccurrenceEdit) -> RecurrenceException {
+    let original_date = format_date(occurrence.original_date);
+    let now = now_iso();
+    let existing = plan.exceptions.iter().find(|e| e.original_date == original_date);
+    let keep = |edited: &Option<String>, previous: Option<&Option<String>>| {
+        edited.clone().or_else(|| previous.cloned().flatten())
+    };
+    RecurrenceException {
+        id: existing.map_or_else(generate_id, |e| e.id.clone()),
+        recurring_plan_id: plan.id.clone(),
+        original_date: original_date.clone(),
+        kind: ExceptionKind::Override,
+        // 원래 날짜로 되돌리면 날짜 변경 없음
+        date: keep(&edit.date, existing.map(|e| &e.date)).filter(|d| *d != original_date),
+        scheduled_time: keep(&edit.scheduled_time, existing.map(|e| &e.scheduled_time)),
+        end_time: keep(&edit.end_time, existing.map(|e| &e.end_time)),
+        title: keep(&edit.title, existing.map(|e| &e.title)),
+        location: keep(&edit.location, existing.map(|e| &e.location)),
+        created_at: existing.map_or_else(|| now.clone(), |e| e.created_at.clone()),
+        updated_at: now,
+    }
+}
+
+/// 반복 일정 자체에 수정 반영 (시간을 바꾸면 예상 소요시간도)
+fn apply_series_edit(plan: &mut RecurringPlan, edit: &OccurrenceEdit) {
+    if let Some(title) = &edit.title {
+        plan.title = title.clone();
+    }
+    if let Some(location) = &edit.location {
+        plan.location = Some(location.clone());
+    }
+    if let Some(time) = &edit.scheduled_time {
+        plan.scheduled_time = Some(time.clone());
+    }
+    if let Some(end_time) = &edit.end_time {
+        plan.end_time = Some(end_time.clone());
+    }
+    if edit.changes_time() {
+        let start = plan.scheduled_time.as_deref().and_then(time_to_minutes);
+        let end = plan.end_time.as_deref().and_then(time_to_minutes);
+        if let Some((start, end)) = start.zip(end).filter(|(start, end)| end > start) {
+            plan.estimated_duration = Some(end - start);
+        }
+    }
+    plan.updated_at = now_iso();
+}
+
+/// 반복 일정이 `from` 이후에 생성한 태스크 중 예외가 없는 발생의 태스크에 수정 반영
+///
+/// 사용자가 고쳤거나 시작한 태스크는 그대로 둡니다.
+async fn update_series_tasks(
+    conn: &mut SqliteConnection,
+    plan: &RecurringPlan,
+    edit: &OccurrenceEdit,
+    from: &str,
+    dates: &mut BTreeSet<String>,
+) -> Result<usize, sqlx::Error> {
+    let overridden: Vec<String> = plan
+        .exceptions
+        .iter()
+        .map(|e| e.date.clone().unwrap_or_else(|| e.original_date.clone()))
+        .collect();
+    let duration = edit.changes_time().then_some(plan.estimated_duration).flatten();
+    let mut updated = 0;
+    for generated in db::generated_tasks_from(conn, &plan.id, from).await? {
+        if overridden.contains(&generated.occurrence_date) || !generated.is_untouched() {
+            continue;
+        }
+        if update_task(conn, &generated.task.id, edit, duration).await? {
+            updated += 1;
+            dates.insert(generated.task.scheduled_date);
+        }
+    }
+    Ok(updated)
+}
+
+/// 반복 일정이 `from`~`to` (포함) 날짜에 생성한 (태스크 id, 날짜)
+async fn generated_tasks(
+    conn: &mut SqliteConnection,
+    recurring_plan_id: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<(String, String)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT task_id, scheduled_date FROM generated_tasks \
+         WHERE recurring_plan_id = ? AND scheduled_date BETWEEN ? AND ? ORDER BY scheduled_date",
+    )
+    .bind(recurring_plan_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(conn)
+    .await
+}
+
+/// 완료하지 않은 태스크에 수정 반영 (바뀌었으면 true)
+async fn update_task(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    edit: &OccurrenceEdit,
+    duration: Option<i32>,
+) -> Result<bool, sqlx::Error> {
+    // 반복 일정 쪽 수정이므로 사용자가 고치지 않은 태스크는 계속 그런 것으로
+    let now = now_iso();
+    db::resync_generated_task(conn, task_id, &now).await?;
+    let result = sqlx::query(
+        "UPDATE tasks SET title = COALESCE(?, title), location = COALESCE(?, location), \
+         scheduled_date = COALESCE(?, scheduled_date), scheduled_time = COALESCE(?, scheduled_time), \
+         estimated_duration = COALESCE(?, estimated_duration), updated_at = ? \
+         WHERE id = ? AND status != 'completed'",
+    )
+    .bind(&edit.title)
+    .bind(&edit.location)
+    .bind(&edit.date)
+    .bind(&edit.scheduled_time)
+    .bind(duration)
+    .bind(&now)
+    .bind(task_id)
+    .execute(conn)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// 반복 일정이 `from`~`to` (포함) 날짜에 생성한 태스크 중 완료하지 않은 것을 지움 (`generated_tasks`는 CASCADE)
+async fn delete_tasks(
+    conn: &mut SqliteConnection,
+    recurring_plan_id: &str,
+    from: &str,
+    to: &str,
+    dates: &mut BTreeSet<String>,
+) -> Result<usize, sqlx::Error> {
+    let deleted: Vec<String> = sqlx::query_scalar(
+        "DELETE FROM tasks WHERE status != 'completed' AND id IN \
+         (SELECT task_id FROM generated_tasks WHERE recurring_plan_id = ? AND scheduled_date BETWEEN ? AND ?) \
+         RETURNING scheduled_date",
+    )
+    .bind(recurring_plan_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(conn)
+    .await?;
+    let count = deleted.len();
+    dates.extend(deleted);
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recurring::{generate_tasks_from_recurring_plan, GeneratedTaskInput, MonthDayPolicy};
+    use chrono::Duration;
+
+    fn days_after(date: &str, days: i64) -> String {
+        format_date(parse_date(date).unwrap() + Duration::days(days))
+    }
+
+    /// 시작일부터 종료일까지 생성되는 태스크
+    fn generate(plan: &RecurringPlan) -> Vec<GeneratedTaskInput> {
+        let end = plan.end_date.as_deref().unwrap();
+        generate_tasks_from_recurring_plan(plan, parse_date(&plan.start_date).unwrap(), parse_date(end).unwrap())
+    }
+
+    fn weekly(start: &str) -> RecurringPlan {
+        RecurringPlan {
+            id: "r".to_string(),
+            plan_id: None,
+            title: "Study group".to_string(),
+            description: None,
+            location: Some("Library".to_string()),
+            recurrence_type: RecurrenceType::Weekly,
+            interval_value: 1,
+            days_of_week: Some(vec![2]),
+            day_of_month: None,
+            scheduled_time: Some("19:00".to_string()),
+            end_time: Some("21:00".to_string()),
+            estimated_duration: Some(120),
+            start_date: start.to_string(),
+            end_date: Some(days_after(start, 27)),
+            rrule: None,
+            month_day_policy: MonthDayPolicy::Clamp,
+            exceptions: Vec::new(),
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn exception(original_date: &str, kind: ExceptionKind) -> RecurrenceException {
+        RecurrenceException {
+            id: original_date.to_string(),
+            recurring_plan_id: "r".to_string(),
+            original_date: original_date.to_string(),
+            kind,
+            date: None,
+            scheduled_time: None,
+            end_time: None,
+            title: None,
+            location: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_generator_honors_exceptions() {
+        // 화요일 4번: 03-03, 03-10, 03-17, 03-24
+        let mut plan = weekly("2026-03-03");
+        plan.exceptions.push(exception("2026-03-10", ExceptionKind::Skip));
+        plan.exceptions.push(RecurrenceException {
+            date: Some("2026-03-19".to_string()),
+            scheduled_time: Some("18:00".to_string()),
+            ..exception("2026-03-17", ExceptionKind::Override)
+        });
+        // 규칙에 없는 날짜의 예외는 무시
+        plan.exceptions.push(RecurrenceException {
+            title: Some("Ghost".to_string()),
+            ..exception("2026-03-11", ExceptionKind::Override)
+        });
+
+        let generated = generate(&plan);
+        let dates: Vec<&str> = generated.iter().map(|t| t.scheduled_date.as_str()).collect();
+        assert_eq!(dates, vec!["2026-03-03", "2026-03-19", "2026-03-24"]);
+        assert_eq!(generated[1].scheduled_time.as_deref(), Some("18:00"));
+        assert_eq!(generated[1].end_time.as_deref(), Some("20:00"));
+        assert_eq!(generated[1].estimated_duration, Some(120));
+
+        // 시작 시간만 옮기면 종료 시간도 같이
+        let moved = occurrence_on(&plan, "2026-03-19").unwrap();
+        assert_eq!(moved.original_date, parse_date("2026-03-17").unwrap());
+        assert_eq!(moved.end_time.as_deref(), Some("20:00"));
+        assert!(occurrence_on(&plan, "2026-03-17").is_none());
+        assert!(occurrence_on(&plan, "2026-03-10").is_none());
+    }
+
+    #[test]
+    fn test_split_series() {
+        let mut plan = weekly("2026-03-03");
+        plan.exceptions.push(exception("2026-03-03", ExceptionKind::Skip));
+        plan.exceptions.push(exception("2026-03-24", ExceptionKind::Skip));
+
+        assert!(split_series(&plan, parse_date("2026-03-03").unwrap()).unwrap().is_none());
+        let (ended, following) = split_series(&plan, parse_date("2026-03-17").unwrap()).unwrap().unwrap();
+        assert_eq!(ended.end_date.as_deref(), Some("2026-03-16"));
+        assert_eq!(ended.exceptions.len(), 1);
+        assert_eq!(following.start_date, "2026-03-17");
+        assert_ne!(following.id, plan.id);
+        assert_eq!(following.exceptions[0].recurring_plan_id, following.id);
+
+        // COUNT는 나눈 만큼
+        let mut counted = weekly("2026-03-03");
+        counted.end_date = None;
+        counted.rrule = Some("FREQ=WEEKLY;COUNT=5;BYDAY=TU".to_string());
+        let (ended, following) = split_series(&counted, parse_date("2026-03-17").unwrap()).unwrap().unwrap();
+        assert_eq!(ended.rrule.as_deref(), Some("FREQ=WEEKLY;COUNT=2;BYDAY=TU"));
+        assert_eq!(following.rrule.as_deref(), Some("FREQ=WEEKLY;COUNT=3;BYDAY=TU"));
+    }
+
+    /// 반복 일정과 생성된 태스크를 저장
+    async fn seed(pool: &SqlitePool, plan: &RecurringPlan) {
+        let mut conn = pool.acquire().await.unwrap();
+        db::insert_recurring_plan(&mut conn, plan).await.unwrap();
+        for (i, generated) in generate(plan).into_iter().enumerate() {
+            let task_id = format!("t{}", i);
+            sqlx::query(
+                "INSERT INTO tasks (id, title, location, scheduled_date, scheduled_time, estimated_duration, status, created_at, updated_at) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, '', '')",
+            )
+            .bind(&task_id)
+            .bind(&generated.title)
+            .bind(&plan.location)
+            .bind(&generated.scheduled_date)
+            .bind(&generated.scheduled_time)
+            .bind(generated.estimated_duration)
+            .bind(if i == 0 { "completed" } else { "pending" })
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+            db::record_generated_task(&mut conn, &plan.id, &task_id, &generated.scheduled_date, "").await.unwrap();
+        }
+    }
+
+    async fn task_rows(pool: &SqlitePool) -> Vec<(String, String, Option<String>, String)> {
+        sqlx::query_as("SELECT title, scheduled_date, scheduled_time, status FROM tasks ORDER BY scheduled_date")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_edit_this_occurrence() {
+        let pool = db::test_pool().await;
+        seed(&pool, &weekly("2026-03-03")).await;
+
+        let edit = OccurrenceEdit {
+            date: Some("2026-03-12".to_string()),
+            scheduled_time: Some("18:00".to_string()),
+            ..Default::default()
+        };
+        let changes = edit_occurrence(&pool, "r", "2026-03-10", EditScope::This, &edit, "2026-03-01").await.unwrap();
+        assert_eq!(changes.updated_tasks, 1);
+        assert_eq!(changes.dates, vec!["2026-03-10", "2026-03-12"]);
+
+        let tasks = task_rows(&pool).await;
+        assert_eq!(tasks[1].1, "2026-03-12");
+        assert_eq!(tasks[1].2.as_deref(), Some("18:00"));
+        assert_eq!(db::generated_recurring_ids(&pool, "2026-03-12").await.unwrap(), vec!["r"]);
+
+        // 옮긴 발생은 옮긴 날짜로 다시 수정
+        let rename = OccurrenceEdit {
+            title: Some("Study group (online)".to_string()),
+            ..Default::default()
+        };
+        edit_occurrence(&pool, "r", "2026-03-12", EditScope::This, &rename, "2026-03-01").await.unwrap();
+        let plan = db::recurring_plan_by_id(&pool, "r").await.unwrap().unwrap();
+        assert_eq!(plan.exceptions.len(), 1);
+        assert_eq!(plan.exceptions[0].date.as_deref(), Some("2026-03-12"));
+        assert_eq!(plan.exceptions[0].scheduled_time.as_deref(), Some("18:00"));
+        assert_eq!(task_rows(&pool).await[1].0, "Study group (online)");
+
+        // 같은 반복 일정의 다른 발생 위로는 옮길 수 없음
+        let collide = OccurrenceEdit {
+            date: Some("2026-03-17".to_string()),
+            ..Default::default()
+        };
+        assert!(edit_occurrence(&pool, "r", "2026-03-12", EditScope::This, &collide, "2026-03-01").await.is_err());
+        assert!(edit_occurrence(&pool, "r", "2026-03-11", EditScope::This, &rename, "2026-03-01").await.is_err());
+        assert!(edit_occurrence(&pool, "r", "2026-03-17", EditScope::All, &collide, "2026-03-01").await.is_err());
+
+        // 완료한 발생은 태스크도 생성 기록도 제자리
+        let move_done = OccurrenceEdit {
+            date: Some("2026-03-05".to_string()),
+            ..Default::default()
+        };
+        let changes = edit_occurrence(&pool, "r", "2026-03-03", EditScope::This, &move_done, "2026-03-01")
+            .await
+            .unwrap();
+        assert_eq!(changes.updated_tasks, 0);
+        assert_eq!(task_rows(&pool).await[0].1, "2026-03-03");
+        assert_eq!(db::generated_recurring_ids(&pool, "2026-03-03").await.unwrap(), vec!["r"]);
+        assert!(db::generated_recurring_ids(&pool, "2026-03-05").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_edit_this_and_following_and_all() {
+        let pool = db::test_pool().await;
+        seed(&pool, &weekly("2026-03-03")).await;
+
+        let later = OccurrenceEdit {
+            scheduled_time: Some("20:00".to_string()),
+            end_time: Some("21:30".to_string()),
+            ..Default::default()
+        };
+        let changes = edit_occurrence(&pool, "r", "2026-03-17", EditScope::ThisAndFollowing, &later, "2026-03-01")
+            .await
+            .unwrap();
+        assert_ne!(changes.recurring_plan_id, "r");
+        assert_eq!(changes.updated_tasks, 2);
+
+        let old = db::recurring_plan_by_id(&pool, "r").await.unwrap().unwrap();
+        assert_eq!(old.end_date.as_deref(), Some("2026-03-16"));
+        let new = db::recurring_plan_by_id(&pool, &changes.recurring_plan_id).await.unwrap().unwrap();
+        assert_eq!(new.scheduled_time.as_deref(), Some("20:00"));
+        assert_eq!(new.estimated_duration, Some(90));
+        assert_eq!(
+            db::generated_recurring_ids(&pool, "2026-03-24").await.unwrap(),
+            vec![changes.recurring_plan_id.clone()]
+        );
+        let times: Vec<Option<String>> = task_rows(&pool).await.into_iter().map(|t| t.2).collect();
+        assert_eq!(times, vec![Some("19:00".into()), Some("19:00".into()), Some("20:00".into()), Some("20:00".into())]);
+
+        // 전체: 완료한 첫 태스크는 그대로
+        let rename = OccurrenceEdit {
+            title: Some("Reading group".to_string()),
+            ..Default::default()
+        };
+        let changes = edit_occurrence(&pool, "r", "2026-03-10", EditScope::All, &rename, "2026-03-01").await.unwrap();
+        assert_eq!(changes.recurring_plan_id, "r");
+        assert_eq!(changes.updated_tasks, 1);
+        let titles: Vec<String> = task_rows(&pool).await.into_iter().map(|t| t.0).collect();
+        assert_eq!(titles, vec!["Study group", "Reading group", "Study group", "Study group"]);
+    }
+
+    #[tokio::test]
+    async fn test_edit_all_skips_past_and_user_edited_tasks() {
+        let pool = db::test_pool().await;
+        seed(&pool, &weekly("2026-03-03")).await;
+        // 사용자가 03-17 태스크를 직접 고침
+        sqlx::query("UPDATE tasks SET title = 'Study group + snacks', updated_at = '2026-03-05T10:00:00' WHERE id = 't2'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let rename = OccurrenceEdit {
+            title: Some("Reading group".to_string()),
+            ..Default::default()
+        };
+        let changes = edit_occurrence(&pool, "r", "2026-03-17", EditScope::All, &rename, "2026-03-12")
+            .await
+            .unwrap();
+        assert_eq!(changes.updated_tasks, 1);
+        assert_eq!(changes.dates, vec!["2026-03-24"]);
+        let titles: Vec<String> = task_rows(&pool).await.into_iter().map(|t| t.0).collect();
+        assert_eq!(titles, vec!["Study group", "Study group", "Study group + snacks", "Reading group"]);
+        let plan = db::recurring_plan_by_id(&pool, "r").await.unwrap().unwrap();
+        assert_eq!(plan.title, "Reading group");
+    }
+
+    #[tokio::test]
+    async fn test_edit_this_and_following_skips_past_tasks() {
+        let pool = db::test_pool().await;
+        seed(&pool, &weekly("2026-03-03")).await;
+
+        let later = OccurrenceEdit {
+            scheduled_time: Some("20:00".to_string()),
+            ..Default::default()
+        };
+        let changes = edit_occurrence(&pool, "r", "2026-03-17", EditScope::ThisAndFollowing, &later, "2026-03-20")
+            .await
+            .unwrap();
+        assert_eq!(changes.updated_tasks, 1);
+        assert_eq!(changes.dates, vec!["2026-03-24"]);
+        let times: Vec<Option<String>> = task_rows(&pool).await.into_iter().map(|t| t.2).collect();
+        assert_eq!(times, vec![Some("19:00".into()), Some("19:00".into()), Some("19:00".into()), Some("20:00".into())]);
+    }
+
+    #[tokio::test]
+    async fn test_skip_occurrences() {
+        let pool = db::test_pool().await;
+        seed(&pool, &weekly("2026-03-03")).await;
+
+        let changes = skip_occurrence(&pool, "r", "2026-03-10", EditScope::This, "2026-03-01").await.unwrap();
+        assert_eq!(changes.deleted_tasks, 1);
+        let plan = db::recurring_plan_by_id(&pool, "r").await.unwrap().unwrap();
+        assert!(!generate(&plan)
+            .iter()
+            .any(|t| t.scheduled_date == "2026-03-10"));
+        assert!(skip_occurrence(&pool, "r", "2026-03-10", EditScope::This, "2026-03-01").await.is_err());
+
+        let changes = skip_occurrence(&pool, "r", "2026-03-24", EditScope::ThisAndFollowing, "2026-03-01")
+            .await
+            .unwrap();
+        assert_eq!(changes.dates, vec!["2026-03-24"]);
+        let plan = db::recurring_plan_by_id(&pool, "r").await.unwrap().unwrap();
+        assert_eq!(plan.end_date.as_deref(), Some("2026-03-23"));
+
+        // 전체: 비활성화, 완료한 태스크는 남김
+        skip_occurrence(&pool, "r", "2026-03-17", EditScope::All, "2026-03-01").await.unwrap();
+        assert!(db::active_recurring_plans(&pool).await.unwrap().is_empty());
+        let tasks = task_rows(&pool).await;
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].3, "completed");
+    }
+}
@@ -0,0 +1,422 @@
+//! 반복 일정 태스크를 앞으로 일정 기간만 미리 만들어 두는 롤링 윈도우
+//!
+//! 반복 일정마다 태스크를 어디까지 만들었는지(`materialized_until`)를 기록하고,
+//! 앱을 시작할 때와 날짜가 바뀔 때 그 다음 날부터 `오늘 + horizon`까지만 채웁니다.
+//! 한 번 채운 범위는 다시 채우지 않으므로 사용자가 지운 태스크가 되살아나지 않고,
+//! 같은 날짜는 `generated_tasks`로 한 번만 만듭니다.
+//! 반복 일정을 바꾸면 `reconcile_plan`이 이미 만든 태스크를 새 발생에 맞춥니다.
+
+use chrono::{Duration, NaiveDate};
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::RangeInclusive;
+
+use super::{format_date, generate_tasks_from_recurring_plan, parse_date, GeneratedTaskInput, RecurringPlan};
+use crate::db;
+use crate::models::{generate_id, now_iso, Task, TaskStatus};
+
+/// 기본으로 미리 만들어 두는 기간 (4주)
+pub const DEFAULT_HORIZON_DAYS: i64 = 28;
+/// 설정할 수 있는 기간 (일)
+pub const HORIZON_RANGE: RangeInclusive<i64> = 1..=365;
+
+/// 생성/정리 결과
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaterializeSummary {
+    pub created_tasks: usize,
+    pub updated_tasks: usize,
+    pub deleted_tasks: usize,
+    /// 반복 일정과 달라졌지만 완료했거나 시작했거나 사용자가 고쳐서 그대로 둔 태스크
+    pub kept_tasks: usize,
+    /// 태스크가 바뀐 날짜 (화면 갱신용)
+    pub dates: Vec<String>,
+}
+
+/// 활성 반복 일정의 태스크를 `today + horizon_days`까지 채움
+pub async fn top_up(pool: &SqlitePool, today: NaiveDate, horizon_days: i64) -> Result<MaterializeSummary, sqlx::Error> {
+    let to = today + Duration::days(horizon_days);
+    let plans = db::active_recurring_plans(pool).await?;
+
+    let mut tx = pool.begin().await?;
+    let mut summary = MaterializeSummary::default();
+    let mut dates = BTreeSet::new();
+    for plan in &plans {
+        let created = materialize_plan(&mut tx, plan, today, to).await?;
+        summary.created_tasks += created.len();
+        dates.extend(created);
+    }
+    for date in &dates {
+        db::refresh_daily_progress(&mut tx, date).await?;
+    }
+    tx.commit().await?;
+
+    summary.dates = dates.into_iter().collect();
+    Ok(summary)
+}
+
+/// `from`~`to` (포함) 중 아직 만들지 않은 발생을 태스크로 만들고 만든 날짜를 반환
+///
+/// `materialized_until`까지는 이미 만든 것으로 보고 건너뜁니다.
+pub async fn materialize_plan(
+    conn: &mut SqliteConnection,
+    plan: &RecurringPlan,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<String>, sqlx::Error> {
+    let until = db::materialized_until(conn, &plan.id).await?;
+    let from = match until.as_deref().and_then(|date| parse_date(date).ok()) {
+        Some(until) => from.max(until + Duration::days(1)),
+        None => from,
+    };
+    if from > to {
+        return Ok(Vec::new());
+    }
+
+    let existing: HashSet<String> = db::generated_dates(conn, &plan.id, &format_date(from), &format_date(to))
+        .await?
+        .into_iter()
+        .collect();
+    let mut created = Vec::new();
+    for generated in generate_tasks_from_recurring_plan(plan, from, to) {
+        if !existing.contains(&generated.scheduled_date) {
+            created.push(create_task(conn, plan, generated).await?);
+        }
+    }
+    db::set_materialized_until(conn, &plan.id, Some(format_date(to).as_str())).await?;
+    Ok(created)
+}
+
+/// 반복 일정을 바꾸거나 끈 뒤 `today`부터 이미 만든 태스크를 새 발생에 맞춤
+///
+/// `previous`는 바꾸기 전 반복 일정입니다. 사용자가 고치지 않은 태스크는 제자리에서 고치거나 더 이상 발생하지 않으면 지우고,
+/// 바뀐 규칙으로 새로 생긴 발생만 만듭니다 (예전에도 있던 발생의 태스크가 없으면 사용자가 지운 것이므로 다시 만들지 않음).
+/// 완료했거나 시작했거나 사용자가 고친 태스크는 그대로 둡니다. 꺼진 반복 일정은 발생이 없는 것으로 봅니다.
+pub async fn reconcile_plan(
+    pool: &SqlitePool,
+    previous: &RecurringPlan,
+    today: NaiveDate,
+    horizon_days: i64,
+) -> Result<MaterializeSummary, String> {
+    let plan = db::recurring_plan_by_id(pool, &previous.id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Recurring plan not found: {}", previous.id))?;
+
+    let database = |e: sqlx::Error| e.to_string();
+    let mut tx = pool.begin().await.map_err(database)?;
+    let existing = db::generated_tasks_from(&mut tx, &plan.id, &format_date(today))
+        .await
+        .map_err(database)?;
+
+    // 이미 만든 범위의 새 발생 (발생 날짜 → 태스크)와 바꾸기 전 발생 날짜
+    let until = db::materialized_until(&mut tx, &plan.id)
+        .await
+        .map_err(database)?
+        .and_then(|date| parse_date(&date).ok())
+        .into_iter()
+        .chain(existing.iter().filter_map(|g| parse_date(&g.occurrence_date).ok()))
+        .max();
+    let occurrences = |plan: &RecurringPlan| match until {
+        Some(until) if plan.is_active => generate_tasks_from_recurring_plan(plan, today, until),
+        _ => Vec::new(),
+    };
+    let mut wanted: BTreeMap<String, GeneratedTaskInput> = occurrences(&plan)
+        .into_iter()
+        .map(|generated| (generated.scheduled_date.clone(), generated))
+        .collect();
+    // 예외는 반복 일정을 고쳐도 그대로
+    let previous = RecurringPlan {
+        exceptions: plan.exceptions.clone(),
+        ..previous.clone()
+    };
+    let previous_dates: HashSet<String> = occurrences(&previous).into_iter().map(|g| g.scheduled_date).collect();
+
+    let mut summary = MaterializeSummary::default();
+    let mut dates = BTreeSet::new();
+    for generated in &existing {
+        let target = wanted.remove(&generated.occurrence_date);
+        if target.as_ref().is_some_and(|target| is_in_sync(&generated.task, target)) {
+            continue;
+        }
+        if !generated.is_untouched() {
+            summary.kept_tasks += 1;
+            continue;
+        }
+        match target {
+            Some(target) => {
+                update_task(&mut tx, &generated.task.id, &target).await.map_err(database)?;
+                summary.updated_tasks += 1;
+            }
+            None => {
+                sqlx::query("DELETE FROM tasks WHERE id = ?")
+                    .bind(&generated.task.id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(database)?;
+                summary.deleted_tasks += 1;
+            }
+        }
+        dates.insert(generated.task.scheduled_date.clone());
+    }
+
+    // 규칙이 바뀌어 새로 생긴 발생, 그리고 아직 만들지 않은 기간
+    for generated in wanted.into_values().filter(|g| !previous_dates.contains(&g.scheduled_date)) {
+        dates.insert(create_task(&mut tx, &plan, generated).await.map_err(database)?);
+        summary.created_tasks += 1;
+    }
+    if plan.is_active {
+        let created = materialize_plan(&mut tx, &plan, today, today + Duration::days(horizon_days))
+            .await
+            .map_err(database)?;
+        summary.created_tasks += created.len();
+        dates.extend(created);
+    }
+
+    for date in &dates {
+        db::refresh_daily_progress(&mut tx, date).await.map_err(database)?;
+    }
+    tx.commit().await.map_err(database)?;
+    summary.dates = dates.into_iter().collect();
+    Ok(summary)
+}
+
+/// 발생 하나를 태스크로 만들고 날짜를 반환
+async fn create_task(
+    conn: &mut SqliteConnection,
+    plan: &RecurringPlan,
+    generated: GeneratedTaskInput,
+) -> Result<String, sqlx::Error> {
+    let now = now_iso();
+    let task = Task {
+        id: generate_id(),
+        plan_id: plan.plan_id.clone(),
+        title: generated.title,
+        description: generated.description,
+        location: generated.location,
+        scheduled_date: generated.scheduled_date,
+        scheduled_time: generated.scheduled_time,
+        estimated_duration: generated.estimated_duration,
+        actual_duration: None,
+        priority: generated.priority,
+        status: TaskStatus::Pending,
+        order_index: 0,
+        subtasks: None,
+        created_at: now.clone(),
+        updated_at: now,
+        completed_at: None,
+    };
+    db::insert_task(conn, &task).await?;
+    db::record_generated_task(conn, &plan.id, &task.id, &task.scheduled_date, &task.updated_at).await?;
+    Ok(task.scheduled_date)
+}
+
+/// 태스크가 발생과 같은지 (반복 일정에서 오는 값만 비교)
+fn is_in_sync(task: &Task, generated: &GeneratedTaskInput) -> bool {
+    task.title == generated.title
+        && task.description == generated.description
+        && task.location == generated.location
+        && task.scheduled_date == generated.scheduled_date
+        && task.scheduled_time == generated.scheduled_time
+        && task.estimated_duration == generated.estimated_duration
+}
+
+/// 사용자가 고치지 않은 태스크를 발생에 맞춤
+async fn update_task(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    generated: &GeneratedTaskInput,
+) -> Result<(), sqlx::Error> {
+    let now = now_iso();
+    db::resync_generated_task(conn, task_id, &now).await?;
+    sqlx::query(
+        "UPDATE tasks SET title = ?, description = ?, location = ?, scheduled_time = ?, \
+         estimated_duration = ?, updated_at = ? WHERE id = ?",
+    )
+    .bind(&generated.title)
+    .bind(&generated.description)
+    .bind(&generated.location)
+    .bind(&generated.scheduled_time)
+    .bind(generated.estimated_duration)
+    .bind(&now)
+    .bind(task_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recurring::{MonthDayPolicy, RecurrenceType};
+
+    fn day(date: &str) -> NaiveDate {
+        parse_date(date).unwrap()
+    }
+
+    /// 2026-03-02(월)부터 매주 월요일, 끝 없음
+    async fn seed(pool: &SqlitePool) -> RecurringPlan {
+        let plan = RecurringPlan {
+            id: "r".to_string(),
+            plan_id: None,
+            title: "Swim".to_string(),
+            description: None,
+            location: Some("Pool".to_string()),
+            recurrence_type: RecurrenceType::Weekly,
+            interval_value: 1,
+            days_of_week: Some(vec![1]),
+            day_of_month: None,
+            scheduled_time: Some("07:00".to_string()),
+            end_time: Some("08:00".to_string()),
+            estimated_duration: Some(60),
+            start_date: "2026-03-02".to_string(),
+            end_date: None,
+            rrule: None,
+            month_day_policy: MonthDayPolicy::Clamp,
+            exceptions: Vec::new(),
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let mut conn = pool.acquire().await.unwrap();
+        db::insert_recurring_plan(&mut conn, &plan).await.unwrap();
+        plan
+    }
+
+    async fn current(pool: &SqlitePool) -> RecurringPlan {
+        db::recurring_plan_by_id(pool, "r").await.unwrap().unwrap()
+    }
+
+    async fn task_rows(pool: &SqlitePool) -> Vec<(String, Option<String>, String)> {
+        sqlx::query_as("SELECT scheduled_date, scheduled_time, status FROM tasks ORDER BY scheduled_date")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_top_up_rolling_window() {
+        let pool = db::test_pool().await;
+        seed(&pool).await;
+
+        let summary = top_up(&pool, day("2026-03-02"), 14).await.unwrap();
+        assert_eq!(summary.created_tasks, 3);
+        assert_eq!(summary.dates, vec!["2026-03-02", "2026-03-09", "2026-03-16"]);
+        let task = &db::tasks_between(&pool, "2026-03-02", "2026-03-02").await.unwrap()[0];
+        assert_eq!((task.location.as_deref(), task.estimated_duration), (Some("Pool"), Some(60)));
+
+        // 같은 날 다시 채워도 그대로
+        assert_eq!(top_up(&pool, day("2026-03-02"), 14).await.unwrap().created_tasks, 0);
+
+        // 사용자가 지운 태스크는 다시 만들지 않고 새로 들어온 날짜만 채움
+        sqlx::query("DELETE FROM tasks WHERE scheduled_date = '2026-03-09'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let summary = top_up(&pool, day("2026-03-10"), 14).await.unwrap();
+        assert_eq!(summary.dates, vec!["2026-03-23"]);
+        let dates: Vec<String> = task_rows(&pool).await.into_iter().map(|(date, _, _)| date).collect();
+        assert_eq!(dates, vec!["2026-03-02", "2026-03-16", "2026-03-23"]);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_after_time_change_and_deactivate() {
+        let pool = db::test_pool().await;
+        let previous = seed(&pool).await;
+        top_up(&pool, day("2026-03-02"), 14).await.unwrap();
+        sqlx::raw_sql(
+            "UPDATE tasks SET status = 'completed' WHERE scheduled_date = '2026-03-02';
+             UPDATE tasks SET status = 'in_progress' WHERE scheduled_date = '2026-03-09';
+             UPDATE recurring_plans SET scheduled_time = '06:30';",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let summary = reconcile_plan(&pool, &previous, day("2026-03-02"), 14).await.unwrap();
+        assert_eq!((summary.updated_tasks, summary.kept_tasks), (1, 2));
+        assert_eq!((summary.created_tasks, summary.deleted_tasks), (0, 0));
+        assert_eq!(summary.dates, vec!["2026-03-16"]);
+        assert_eq!(
+            task_rows(&pool).await,
+            vec![
+                ("2026-03-02".to_string(), Some("07:00".to_string()), "completed".to_string()),
+                ("2026-03-09".to_string(), Some("07:00".to_string()), "in_progress".to_string()),
+                ("2026-03-16".to_string(), Some("06:30".to_string()), "pending".to_string()),
+            ]
+        );
+        // 다시 맞춰도 바뀌는 것 없음
+        let previous = current(&pool).await;
+        assert_eq!(reconcile_plan(&pool, &previous, day("2026-03-02"), 14).await.unwrap().updated_tasks, 0);
+
+        // 끄면 고치지 않은 태스크만 지우고 더 채우지 않음
+        sqlx::query("UPDATE recurring_plans SET is_active = 0").execute(&pool).await.unwrap();
+        let summary = reconcile_plan(&pool, &previous, day("2026-03-02"), 14).await.unwrap();
+        assert_eq!((summary.deleted_tasks, summary.kept_tasks, summary.created_tasks), (1, 2, 0));
+        assert_eq!(top_up(&pool, day("2026-03-20"), 14).await.unwrap().created_tasks, 0);
+        assert_eq!(task_rows(&pool).await.len(), 2);
+        let missing = RecurringPlan {
+            id: "missing".to_string(),
+            ..previous
+        };
+        assert!(reconcile_plan(&pool, &missing, day("2026-03-02"), 14).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_after_days_change_keeps_user_edits() {
+        let pool = db::test_pool().await;
+        let previous = seed(&pool).await;
+        top_up(&pool, day("2026-03-02"), 14).await.unwrap();
+        sqlx::raw_sql(
+            "UPDATE tasks SET title = 'Swim (late)', updated_at = 'edited' WHERE scheduled_date = '2026-03-09';
+             UPDATE recurring_plans SET days_of_week = '[3]', location = 'Lake';",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // 월 → 수: 고치지 않은 월요일은 지우고, 이미 만든 기간의 수요일을 새로 만듦
+        let summary = reconcile_plan(&pool, &previous, day("2026-03-02"), 14).await.unwrap();
+        assert_eq!((summary.created_tasks, summary.deleted_tasks, summary.kept_tasks), (2, 2, 1));
+        assert_eq!(summary.dates, vec!["2026-03-02", "2026-03-04", "2026-03-11", "2026-03-16"]);
+        let tasks = db::tasks_between(&pool, "2026-03-01", "2026-03-31").await.unwrap();
+        let tasks: Vec<(&str, &str, Option<&str>)> = tasks
+            .iter()
+            .map(|t| (t.scheduled_date.as_str(), t.title.as_str(), t.location.as_deref()))
+            .collect();
+        assert_eq!(
+            tasks,
+            vec![
+                ("2026-03-04", "Swim", Some("Lake")),
+                ("2026-03-09", "Swim (late)", Some("Pool")),
+                ("2026-03-11", "Swim", Some("Lake")),
+            ]
+        );
+
+        // 이후 채우기는 새 규칙으로 이어짐
+        let summary = top_up(&pool, day("2026-03-10"), 14).await.unwrap();
+        assert_eq!(summary.dates, vec!["2026-03-18"]);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_does_not_recreate_deleted_tasks() {
+        let pool = db::test_pool().await;
+        let previous = seed(&pool).await;
+        top_up(&pool, day("2026-03-02"), 14).await.unwrap();
+        sqlx::raw_sql(
+            "DELETE FROM tasks WHERE scheduled_date = '2026-03-09';
+             UPDATE recurring_plans SET title = 'Swim laps';",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // 예전에도 있던 발생이므로 지운 03-09는 그대로 없음
+        let summary = reconcile_plan(&pool, &previous, day("2026-03-02"), 14).await.unwrap();
+        assert_eq!((summary.updated_tasks, summary.created_tasks), (2, 0));
+        let tasks = db::tasks_between(&pool, "2026-03-01", "2026-03-31").await.unwrap();
+        let tasks: Vec<(&str, &str)> = tasks.iter().map(|t| (t.scheduled_date.as_str(), t.title.as_str())).collect();
+        assert_eq!(tasks, vec![("2026-03-02", "Swim laps"), ("2026-03-16", "Swim laps")]);
+    }
+}
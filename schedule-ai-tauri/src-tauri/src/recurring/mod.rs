@@ -1,6 +1,17 @@
-use chrono::{Datelike, Duration, NaiveDate};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::llm::structured::{check_date, check_range, check_time};
+use crate::llm::StructuredOutput;
+
+pub mod exceptions;
+pub mod materialize;
+pub mod rrule;
+
+use exceptions::{ExceptionKind, RecurrenceException};
+use rrule::{Frequency, RRule, WeekdayNum};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecurringPlan {
@@ -12,23 +23,249 @@ pub struct RecurringPlan {
     pub recurrence_type: RecurrenceType,
     pub interval_value: i32,
     pub days_of_week: Option<Vec<i32>>, // 0=일, 1=월, ..., 6=토
-    pub day_of_month: Option<i32>,
+    pub day_of_month: Option<i32>,      // 1-31, -1=말일
     pub scheduled_time: Option<String>,
     pub end_time: Option<String>,
     pub estimated_duration: Option<i32>,
     pub start_date: String,
     pub end_date: Option<String>,
+    /// RFC 5545 RRULE (예: "FREQ=MONTHLY;BYDAY=-1FR"), 있으면 위의 반복 필드보다 우선
+    #[serde(default)]
+    pub rrule: Option<String>,
+    #[serde(default)]
+    pub month_day_policy: MonthDayPolicy,
+    /// 발생별 예외 (건너뛰기, 날짜/시간/제목/장소 변경)
+    #[serde(default)]
+    pub exceptions: Vec<RecurrenceException>,
     pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum RecurrenceType {
     Daily,
     Weekly,
     Monthly,
+    Yearly,
+}
+
+impl RecurrenceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecurrenceType::Daily => "daily",
+            RecurrenceType::Weekly => "weekly",
+            RecurrenceType::Monthly => "monthly",
+            RecurrenceType::Yearly => "yearly",
+        }
+    }
+
+    pub fn from_rule(rule: &RRule) -> Self {
+        match rule.freq {
+            Frequency::Daily => RecurrenceType::Daily,
+            Frequency::Weekly => RecurrenceType::Weekly,
+            Frequency::Monthly => RecurrenceType::Monthly,
+            Frequency::Yearly => RecurrenceType::Yearly,
+        }
+    }
+}
+
+/// 그 날짜가 없는 달(예: 31일, 2월 29일)의 처리
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MonthDayPolicy {
+    /// 그 달은 건너뜀
+    Skip,
+    /// 그 달의 마지막 날로 당김
+    #[default]
+    Clamp,
+}
+
+impl MonthDayPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MonthDayPolicy::Skip => "skip",
+            MonthDayPolicy::Clamp => "clamp",
+        }
+    }
+}
+
+/// 해당 월의 `day`일 (음수는 끝에서부터, -1=말일)
+///
+/// Clamp는 "28~day일 중 마지막으로 있는 날"(BYMONTHDAY=28,...,day;BYSETPOS=-1)로 표현합니다.
+fn month_day_rule(rule: &mut RRule, day: i32, policy: MonthDayPolicy) {
+    if day > 28 && policy == MonthDayPolicy::Clamp {
+        rule.by_month_day = (28..=day).collect();
+        rule.by_set_pos = vec![-1];
+    } else {
+        rule.by_month_day = vec![day];
+    }
+}
+
+impl RecurringPlan {
+    /// 반복 규칙 (`rrule`이 없으면 반복 필드로 만들고, `end_date`는 UNTIL로)
+    ///
+    /// 간격은 `start_date`가 속한 주/달/해부터 셉니다. `month_day_policy`는 반복 필드로 만든 규칙에만 적용되고,
+    /// RRULE 문자열은 RFC 5545대로 없는 날짜를 건너뜁니다.
+    pub fn rule(&self) -> Result<RRule, String> {
+        let mut rule = match self.rrule.as_deref().filter(|r| !r.trim().is_empty()) {
+            Some(rrule) => rrule.parse::<RRule>()?,
+            None => {
+                let start = NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d")
+                    .map_err(|_| format!("invalid startDate \"{}\"", self.start_date))?;
+                let mut rule = RRule::new(match self.recurrence_type {
+                    RecurrenceType::Daily => Frequency::Daily,
+                    RecurrenceType::Weekly => Frequency::Weekly,
+                    RecurrenceType::Monthly => Frequency::Monthly,
+                    RecurrenceType::Yearly => Frequency::Yearly,
+                });
+                rule.interval = self.interval_value.max(1) as u32;
+                match self.recurrence_type {
+                    RecurrenceType::Daily => {}
+                    RecurrenceType::Weekly => {
+                        rule.by_day = self
+                            .days_of_week
+                            .iter()
+                            .flatten()
+                            .filter_map(|day| weekday_from_sunday(*day))
+                            .map(|weekday| WeekdayNum { ordinal: None, weekday })
+                            .collect();
+                    }
+                    RecurrenceType::Monthly => {
+                        let day = self.day_of_month.unwrap_or(start.day() as i32);
+                        month_day_rule(&mut rule, day, self.month_day_policy);
+                    }
+                    RecurrenceType::Yearly => {
+                        // 2월 29일 시작 등
+                        rule.by_month = vec![start.month()];
+                        month_day_rule(&mut rule, start.day() as i32, self.month_day_policy);
+                    }
+                }
+                rule
+            }
+        };
+        if let Some(end) = self.end_date.as_deref().and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()) {
+            if rule.count.is_none() {
+                rule.until = Some(rule.until.map_or(end, |until| until.min(end)));
+            }
+        }
+        Ok(rule)
+    }
+
+    /// `from`~`to` (포함) 안의 발생 (날짜 순)
+    ///
+    /// 건너뛴 발생은 빠지고, 옮긴 발생은 원래 날짜가 기간 밖이어도 옮긴 날짜로 들어갑니다.
+    pub fn occurrences(&self, from: NaiveDate, to: NaiveDate) -> Vec<Occurrence> {
+        let (Ok(start), Ok(rule)) = (parse_date(&self.start_date), self.rule()) else {
+            return Vec::new();
+        };
+        let has_exception = |date: NaiveDate| self.exceptions.iter().any(|e| e.original_date == format_date(date));
+
+        let mut occurrences: Vec<Occurrence> = rule
+            .between(start, from, to)
+            .into_iter()
+            .filter(|date| !has_exception(*date))
+            .map(|date| self.occurrence(date, None))
+            .collect();
+        for exception in self.exceptions.iter().filter(|e| e.kind == ExceptionKind::Override) {
+            let Ok(original) = parse_date(&exception.original_date) else {
+                continue;
+            };
+            // 규칙이 바뀌어 더 이상 발생하지 않는 날짜의 예외는 무시
+            if rule.between(start, original, original).is_empty() {
+                continue;
+            }
+            let occurrence = self.occurrence(original, Some(exception));
+            if (from..=to).contains(&occurrence.date) {
+                occurrences.push(occurrence);
+            }
+        }
+        occurrences.sort_by_key(|o| o.date);
+        occurrences
+    }
+
+    /// 규칙상 `date`의 발생에 예외를 반영
+    ///
+    /// 시작 시간만 옮기면 종료 시간도 같은 길이만큼 옮깁니다.
+    fn occurrence(&self, date: NaiveDate, exception: Option<&RecurrenceException>) -> Occurrence {
+        let mut occurrence = Occurrence {
+            original_date: date,
+            date,
+            title: self.title.clone(),
+            scheduled_time: self.scheduled_time.clone(),
+            end_time: self.end_time.clone(),
+            location: self.location.clone(),
+        };
+        let Some(exception) = exception else {
+            return occurrence;
+        };
+        if let Some(moved) = exception.date.as_deref().and_then(|d| parse_date(d).ok()) {
+            occurrence.date = moved;
+        }
+        if let Some(time) = &exception.scheduled_time {
+            if exception.end_time.is_none() {
+                occurrence.end_time = occurrence.minutes().zip(time_to_minutes(time)).map(|(minutes, start)| {
+                    let end = (start + minutes).min(24 * 60 - 1);
+                    format!("{:02}:{:02}", end / 60, end % 60)
+                });
+            }
+            occurrence.scheduled_time = Some(time.clone());
+        }
+        if let Some(end_time) = &exception.end_time {
+            occurrence.end_time = Some(end_time.clone());
+        }
+        if let Some(title) = &exception.title {
+            occurrence.title = title.clone();
+        }
+        if let Some(location) = &exception.location {
+            occurrence.location = Some(location.clone());
+        }
+        occurrence
+    }
+}
+
+/// 반복 일정의 한 번 발생 (예외 반영)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence {
+    /// 규칙상 날짜 (예외를 찾는 키)
+    pub original_date: NaiveDate,
+    pub date: NaiveDate,
+    pub title: String,
+    pub scheduled_time: Option<String>,
+    pub end_time: Option<String>,
+    pub location: Option<String>,
+}
+
+impl Occurrence {
+    /// 시작~종료 시간 길이(분)
+    pub fn minutes(&self) -> Option<i32> {
+        let start = time_to_minutes(self.scheduled_time.as_deref()?)?;
+        let end = time_to_minutes(self.end_time.as_deref()?)?;
+        (end > start).then_some(end - start)
+    }
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("invalid date \"{}\"", date))
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// 0=일 ~ 6=토
+fn weekday_from_sunday(day: i32) -> Option<Weekday> {
+    match day {
+        0 => Some(Weekday::Sun),
+        1 => Some(Weekday::Mon),
+        2 => Some(Weekday::Tue),
+        3 => Some(Weekday::Wed),
+        4 => Some(Weekday::Thu),
+        5 => Some(Weekday::Fri),
+        6 => Some(Weekday::Sat),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +284,10 @@ pub struct CreateRecurringPlanInput {
     pub estimated_duration: Option<i32>,
     pub start_date: String,
     pub end_date: Option<String>,
+    #[serde(default)]
+    pub rrule: Option<String>,
+    #[serde(default)]
+    pub month_day_policy: MonthDayPolicy,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,26 +296,71 @@ pub struct GeneratedTaskInput {
     pub plan_id: Option<String>,
     pub title: String,
     pub description: Option<String>,
+    pub location: Option<String>,
     pub scheduled_date: String,
     pub scheduled_time: Option<String>,
+    pub end_time: Option<String>,
     pub estimated_duration: Option<i32>,
     pub priority: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ParsedRecurrencePattern {
     pub recurrence_type: RecurrenceType,
+    /// 반복 간격 (기본값 1, 격주면 2)
     pub interval_value: i32,
+    /// 요일 배열 (0=일, 1=월, ..., 6=토)
     pub days_of_week: Option<Vec<i32>>,
+    /// 월간 반복 날짜 (1-31, 말일은 -1)
     pub day_of_month: Option<i32>,
+    /// 시작 시간 "HH:MM"
     pub scheduled_time: Option<String>,
+    /// 종료 시간 "HH:MM"
     pub end_time: Option<String>,
+    /// 소요 시간(분)
     pub estimated_duration: Option<i32>,
+    /// 시작 날짜 "YYYY-MM-DD"
     pub start_date: Option<String>,
+    /// 종료 날짜 "YYYY-MM-DD"
     pub end_date: Option<String>,
     pub title: Option<String>,
     pub location: Option<String>,
+    /// 위 필드로 표현할 수 없는 규칙 (예: 매달 둘째 월요일 "FREQ=MONTHLY;BYDAY=2MO")
+    #[serde(default)]
+    pub rrule: Option<String>,
+}
+
+impl StructuredOutput for ParsedRecurrencePattern {
+    const TOOL_NAME: &'static str = "parse_recurrence_pattern";
+
+    fn validate(&self) -> Result<(), String> {
+        check_range("intervalValue", Some(self.interval_value), 1, 365)?;
+        for day in self.days_of_week.iter().flatten() {
+            check_range("daysOfWeek[]", Some(*day), 0, 6)?;
+        }
+        if self.recurrence_type == RecurrenceType::Weekly
+            && self.days_of_week.as_ref().is_some_and(|days| days.is_empty())
+        {
+            return Err("daysOfWeek must not be empty for weekly recurrence".to_string());
+        }
+        if self.day_of_month != Some(-1) {
+            check_range("dayOfMonth", self.day_of_month, 1, 31)?;
+        }
+        if let Some(rrule) = self.rrule.as_deref().filter(|r| !r.trim().is_empty()) {
+            rrule.parse::<RRule>().map_err(|e| format!("rrule: {}", e))?;
+        }
+        check_time("scheduledTime", self.scheduled_time.as_deref())?;
+        check_time("endTime", self.end_time.as_deref())?;
+        check_date("startDate", self.start_date.as_deref())?;
+        check_date("endDate", self.end_date.as_deref())?;
+        if let (Some(start), Some(end)) = (&self.start_date, &self.end_date) {
+            if end < start {
+                return Err(format!("endDate {} is before startDate {}", end, start));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for ParsedRecurrencePattern {
@@ -91,6 +377,7 @@ impl Default for ParsedRecurrencePattern {
             end_date: None,
             title: None,
             location: None,
+            rrule: None,
         }
     }
 }
@@ -112,6 +399,7 @@ pub fn parse_recurrence_pattern(input: &str) -> Option<ParsedRecurrencePattern>
         end_date: None,
         title: None,
         location: None,
+        rrule: None,
     };
 
     // 반복 유형 파싱
@@ -210,7 +498,7 @@ fn parse_time_range(input: &str) -> Option<(String, String)> {
     ];
 
     // 간단한 패턴 매칭
-    for i in 0..input.len() {
+    for (i, _) in input.char_indices() {
         let slice = &input[i..];
 
         // "12-16시" 패턴
@@ -245,7 +533,7 @@ fn parse_single_time(input: &str) -> Option<String> {
     let is_am = input_lower.contains("오전") || input_lower.contains("am");
 
     // 시간 숫자 찾기
-    for i in 0..input.len() {
+    for (i, _) in input.char_indices() {
         let slice = &input[i..];
         if let Some(si_idx) = slice.find("시") {
             let before = &slice[..si_idx];
@@ -361,87 +649,40 @@ fn extract_title(input: &str) -> String {
     String::new()
 }
 
-/// RecurringPlan에서 태스크 목록 생성
-pub fn generate_tasks_from_recurring_plan(plan: &RecurringPlan) -> Vec<GeneratedTaskInput> {
-    let mut tasks = Vec::new();
-
-    let start_date = match NaiveDate::parse_from_str(&plan.start_date, "%Y-%m-%d") {
-        Ok(d) => d,
-        Err(_) => return tasks,
-    };
-
-    // 종료일이 없으면 1년 후까지
-    let end_date = plan.end_date.as_ref()
-        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
-        .unwrap_or_else(|| start_date + Duration::days(365));
-
-    let mut current_date = start_date;
-
-    while current_date <= end_date {
-        if should_generate_on_date(plan, current_date) {
-            tasks.push(GeneratedTaskInput {
-                plan_id: plan.plan_id.clone(),
-                title: plan.title.clone(),
-                description: plan.description.clone(),
-                scheduled_date: current_date.format("%Y-%m-%d").to_string(),
-                scheduled_time: plan.scheduled_time.clone(),
-                estimated_duration: plan.estimated_duration,
-                priority: 0,
-            });
-        }
-
-        current_date = match plan.recurrence_type {
-            RecurrenceType::Daily => current_date + Duration::days(plan.interval_value as i64),
-            RecurrenceType::Weekly => {
-                // 주간 반복: 다음 날로 이동 (요일 체크는 should_generate_on_date에서)
-                current_date + Duration::days(1)
-            },
-            RecurrenceType::Monthly => {
-                // 월간 반복: 다음 달 같은 날
-                let year = current_date.year();
-                let month = current_date.month();
-                let day = plan.day_of_month.unwrap_or(current_date.day() as i32) as u32;
-
-                let (next_year, next_month) = if month == 12 {
-                    (year + plan.interval_value, 1)
-                } else {
-                    (year, month + plan.interval_value as u32)
-                };
-
-                NaiveDate::from_ymd_opt(next_year, next_month, day.min(28))
-                    .unwrap_or(current_date + Duration::days(30))
-            }
-        };
-    }
-
-    tasks
+/// RecurringPlan의 `from`~`to` (포함) 발생으로 태스크 목록 생성 (예외 반영)
+///
+/// 열린 반복 일정은 끝이 없으므로 호출하는 쪽에서 기간을 정합니다 (`materialize` 참고).
+pub fn generate_tasks_from_recurring_plan(plan: &RecurringPlan, from: NaiveDate, to: NaiveDate) -> Vec<GeneratedTaskInput> {
+    plan.occurrences(from, to)
+        .into_iter()
+        .map(|occurrence| GeneratedTaskInput {
+            plan_id: plan.plan_id.clone(),
+            title: occurrence.title.clone(),
+            description: plan.description.clone(),
+            location: occurrence.location.clone(),
+            scheduled_date: format_date(occurrence.date),
+            scheduled_time: occurrence.scheduled_time.clone(),
+            end_time: occurrence.end_time.clone(),
+            // 예외로 시간을 바꿨으면 바뀐 길이로
+            estimated_duration: (occurrence.scheduled_time != plan.scheduled_time || occurrence.end_time != plan.end_time)
+                .then(|| occurrence.minutes())
+                .flatten()
+                .or(plan.estimated_duration),
+            priority: 0,
+        })
+        .collect()
 }
 
-/// 해당 날짜에 태스크를 생성해야 하는지 확인
-fn should_generate_on_date(plan: &RecurringPlan, date: NaiveDate) -> bool {
-    match plan.recurrence_type {
-        RecurrenceType::Daily => true,
-        RecurrenceType::Weekly => {
-            if let Some(ref days) = plan.days_of_week {
-                let weekday = date.weekday().num_days_from_sunday() as i32;
-                days.contains(&weekday)
-            } else {
-                true // 요일 지정 없으면 매일
-            }
-        }
-        RecurrenceType::Monthly => {
-            if let Some(day_of_month) = plan.day_of_month {
-                date.day() == day_of_month as u32
-            } else {
-                true
-            }
-        }
-    }
+/// 해당 날짜(YYYY-MM-DD)의 발생 (태스크 생성 규칙과 동일)
+pub fn occurrence_on(plan: &RecurringPlan, date: &str) -> Option<Occurrence> {
+    let day = parse_date(date).ok()?;
+    plan.occurrences(day, day).into_iter().next()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_parse_weekly_pattern() {
@@ -465,4 +706,201 @@ mod tests {
         let result = parse_recurrence_pattern("평일 9시 출근").unwrap();
         assert_eq!(result.days_of_week, Some(vec![1, 2, 3, 4, 5]));
     }
+
+    /// 시작일부터 종료일까지 (종료일이 없으면 1년)
+    fn generate_all(plan: &RecurringPlan) -> Vec<GeneratedTaskInput> {
+        let start = parse_date(&plan.start_date).unwrap();
+        let end = plan.rule().ok().and_then(|rule| rule.until).unwrap_or(start + Duration::days(365));
+        generate_tasks_from_recurring_plan(plan, start, end)
+    }
+
+    fn plan(recurrence_type: RecurrenceType, rrule: Option<&str>) -> RecurringPlan {
+        RecurringPlan {
+            id: "r".to_string(),
+            plan_id: None,
+            title: "Study".to_string(),
+            description: None,
+            location: None,
+            recurrence_type,
+            interval_value: 2,
+            days_of_week: Some(vec![1, 3]),
+            day_of_month: None,
+            scheduled_time: Some("19:00".to_string()),
+            end_time: None,
+            estimated_duration: Some(60),
+            start_date: "2026-03-04".to_string(),
+            end_date: Some("2026-03-31".to_string()),
+            rrule: rrule.map(String::from),
+            month_day_policy: MonthDayPolicy::Clamp,
+            exceptions: Vec::new(),
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_generate_from_rule() {
+        // 예전 필드: 격주 월/수 → INTERVAL=2;BYDAY=MO,WE, end_date는 UNTIL
+        let legacy = plan(RecurrenceType::Weekly, None);
+        assert_eq!(legacy.rule().unwrap().to_string(), "FREQ=WEEKLY;INTERVAL=2;UNTIL=20260331;BYDAY=MO,WE");
+        let dates: Vec<String> = generate_all(&legacy)
+            .into_iter()
+            .map(|t| t.scheduled_date)
+            .collect();
+        assert_eq!(dates, vec!["2026-03-04", "2026-03-16", "2026-03-18", "2026-03-30"]);
+
+        // RRULE이 있으면 RRULE 우선
+        let mut second_monday = plan(RecurrenceType::Monthly, Some("FREQ=MONTHLY;BYDAY=2MO"));
+        second_monday.end_date = None;
+        let generated = generate_all(&second_monday);
+        assert_eq!(generated.len(), 12);
+        assert_eq!(generated[0].scheduled_date, "2026-03-09");
+        assert!(occurrence_on(&second_monday, "2027-06-14").is_some());
+        assert!(occurrence_on(&second_monday, "2027-06-07").is_none());
+
+        second_monday.rrule = Some("FREQ=MONTHLY;BYDAY=2XX".to_string());
+        assert!(generate_all(&second_monday).is_empty());
+    }
+
+    #[test]
+    fn test_interval_anchored_to_start() {
+        // 격주 스터디: 시작 주부터 2주마다 (예전에는 매주 생성됨)
+        let mut study = plan(RecurrenceType::Weekly, None);
+        study.days_of_week = Some(vec![4]);
+        study.start_date = "2026-03-05".to_string();
+        study.end_date = Some("2026-04-30".to_string());
+        let dates: Vec<String> = generate_all(&study)
+            .into_iter()
+            .map(|t| t.scheduled_date)
+            .collect();
+        assert_eq!(dates, vec!["2026-03-05", "2026-03-19", "2026-04-02", "2026-04-16", "2026-04-30"]);
+
+        // 12월 시작 격월: 해가 바뀌어도 2달 간격
+        let mut bimonthly = plan(RecurrenceType::Monthly, None);
+        bimonthly.day_of_month = Some(15);
+        bimonthly.start_date = "2026-12-15".to_string();
+        bimonthly.end_date = Some("2027-06-30".to_string());
+        let dates: Vec<String> = generate_all(&bimonthly)
+            .into_iter()
+            .map(|t| t.scheduled_date)
+            .collect();
+        assert_eq!(dates, vec!["2026-12-15", "2027-02-15", "2027-04-15", "2027-06-15"]);
+    }
+
+    #[test]
+    fn test_month_day_policy() {
+        let mut rent = plan(RecurrenceType::Monthly, None);
+        rent.interval_value = 1;
+        rent.day_of_month = Some(31);
+        rent.start_date = "2028-01-31".to_string();
+        rent.end_date = Some("2028-05-31".to_string());
+        let dates = |plan: &RecurringPlan| -> Vec<String> {
+            generate_all(plan)
+                .into_iter()
+                .map(|t| t.scheduled_date)
+                .collect()
+        };
+
+        assert_eq!(
+            dates(&rent),
+            vec!["2028-01-31", "2028-02-29", "2028-03-31", "2028-04-30", "2028-05-31"]
+        );
+        rent.month_day_policy = MonthDayPolicy::Skip;
+        assert_eq!(dates(&rent), vec!["2028-01-31", "2028-03-31", "2028-05-31"]);
+
+        // 말일
+        rent.day_of_month = Some(-1);
+        rent.start_date = "2027-01-01".to_string();
+        rent.end_date = Some("2027-04-30".to_string());
+        assert_eq!(
+            dates(&rent),
+            vec!["2027-01-31", "2027-02-28", "2027-03-31", "2027-04-30"]
+        );
+
+        // 2월 29일 매년: clamp는 평년에 28일, skip은 윤년만
+        let mut birthday = plan(RecurrenceType::Yearly, None);
+        birthday.interval_value = 1;
+        birthday.start_date = "2028-02-29".to_string();
+        birthday.end_date = Some("2032-12-31".to_string());
+        assert_eq!(
+            dates(&birthday),
+            vec!["2028-02-29", "2029-02-28", "2030-02-28", "2031-02-28", "2032-02-29"]
+        );
+        birthday.month_day_policy = MonthDayPolicy::Skip;
+        assert_eq!(dates(&birthday), vec!["2028-02-29", "2032-02-29"]);
+    }
+
+    /// 시작일부터 하루씩 보며 "있어야 할 날짜"를 직접 계산 (전개 결과와 비교용)
+    fn expected_dates(plan: &RecurringPlan, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        let interval = plan.interval_value.max(1) as i64;
+        let monday = |date: NaiveDate| date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        start
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .filter(|date| match plan.recurrence_type {
+                RecurrenceType::Weekly => {
+                    let days = plan.days_of_week.clone().unwrap_or_default();
+                    days.contains(&(date.weekday().num_days_from_sunday() as i32))
+                        && (monday(*date) - monday(start)).num_weeks() % interval == 0
+                }
+                RecurrenceType::Monthly => {
+                    let months = (date.year() - start.year()) as i64 * 12 + date.month() as i64 - start.month() as i64;
+                    let len = rrule::days_in_month(date.year(), date.month()) as i32;
+                    let target = match plan.day_of_month.unwrap_or(start.day() as i32) {
+                        -1 => Some(len),
+                        day if day > len => (plan.month_day_policy == MonthDayPolicy::Clamp).then_some(len),
+                        day => Some(day),
+                    };
+                    months % interval == 0 && target == Some(date.day() as i32)
+                }
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    fn generated_dates(plan: &RecurringPlan) -> Vec<NaiveDate> {
+        generate_all(plan)
+            .into_iter()
+            .map(|t| NaiveDate::parse_from_str(&t.scheduled_date, "%Y-%m-%d").unwrap())
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_weekly_matches_interval(
+            start in 0i64..50_000,
+            interval in 1i32..5,
+            days in proptest::collection::btree_set(0i32..7, 1..4),
+            span in 0i64..800,
+        ) {
+            let start = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + Duration::days(start);
+            let end = start + Duration::days(span);
+            let mut weekly = plan(RecurrenceType::Weekly, None);
+            weekly.interval_value = interval;
+            weekly.days_of_week = Some(days.into_iter().collect());
+            weekly.start_date = start.format("%Y-%m-%d").to_string();
+            weekly.end_date = Some(end.format("%Y-%m-%d").to_string());
+            prop_assert_eq!(generated_dates(&weekly), expected_dates(&weekly, start, end));
+        }
+
+        #[test]
+        fn prop_monthly_matches_interval(
+            start in 0i64..50_000,
+            interval in 1i32..14,
+            day in prop_oneof![Just(-1i32), 1i32..=31],
+            skip in any::<bool>(),
+            span in 0i64..1500,
+        ) {
+            let start = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + Duration::days(start);
+            let end = start + Duration::days(span);
+            let mut monthly = plan(RecurrenceType::Monthly, None);
+            monthly.interval_value = interval;
+            monthly.day_of_month = Some(day);
+            monthly.month_day_policy = if skip { MonthDayPolicy::Skip } else { MonthDayPolicy::Clamp };
+            monthly.start_date = start.format("%Y-%m-%d").to_string();
+            monthly.end_date = Some(end.format("%Y-%m-%d").to_string());
+            prop_assert_eq!(generated_dates(&monthly), expected_dates(&monthly, start, end));
+        }
+    }
 }
@@ -0,0 +1,526 @@
+//! RFC 5545 RRULE 파싱, 출력, 날짜 전개
+//!
+//! 반복 일정은 날짜 단위로만 전개합니다 (시간은 `scheduled_time`/`end_time`).
+//! FREQ(DAILY/WEEKLY/MONTHLY/YEARLY), INTERVAL, BYDAY(서수 포함, 예: 2MO, -1FR), BYMONTHDAY, BYMONTH,
+//! BYSETPOS, COUNT, UNTIL, WKST를 지원합니다.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn as_str(self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+/// BYDAY 항목 (`ordinal`이 있으면 달/해 안에서 n번째, 음수면 끝에서 n번째)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekdayNum {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<WeekdayNum>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+    pub count: Option<u32>,
+    /// 마지막 날짜 (포함)
+    pub until: Option<NaiveDate>,
+    pub week_start: Weekday,
+}
+
+impl RRule {
+    pub fn new(freq: Frequency) -> Self {
+        Self {
+            freq,
+            interval: 1,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            count: None,
+            until: None,
+            week_start: Weekday::Mon,
+        }
+    }
+
+    /// `dtstart`부터 전개한 날짜 중 `from`~`to` (포함) 안의 날짜
+    ///
+    /// COUNT는 `from`과 관계없이 `dtstart`부터 셉니다.
+    pub fn between(&self, dtstart: NaiveDate, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let last = self.until.map_or(to, |until| until.min(to));
+        let mut dates = Vec::new();
+        let mut seen = 0u32;
+
+        for period in 0u32.. {
+            let Some(period_start) = self.period_start(dtstart, period) else {
+                break;
+            };
+            if period_start > last {
+                break;
+            }
+            for date in self.expand(dtstart, period_start) {
+                if date < dtstart {
+                    continue;
+                }
+                if date > last {
+                    return dates;
+                }
+                seen += 1;
+                if self.count.is_some_and(|count| seen > count) {
+                    return dates;
+                }
+                if date >= from {
+                    dates.push(date);
+                }
+            }
+        }
+        dates
+    }
+
+    /// `period`번째 반복 주기의 첫날
+    fn period_start(&self, dtstart: NaiveDate, period: u32) -> Option<NaiveDate> {
+        let step = i64::from(period) * i64::from(self.interval);
+        match self.freq {
+            Frequency::Daily => dtstart.checked_add_signed(Duration::days(step)),
+            Frequency::Weekly => week_start(dtstart, self.week_start).checked_add_signed(Duration::weeks(step)),
+            Frequency::Monthly => {
+                let months = i64::from(dtstart.month0()) + step;
+                let year = i64::from(dtstart.year()) + months / 12;
+                NaiveDate::from_ymd_opt(i32::try_from(year).ok()?, (months % 12) as u32 + 1, 1)
+            }
+            Frequency::Yearly => NaiveDate::from_ymd_opt(i32::try_from(i64::from(dtstart.year()) + step).ok()?, 1, 1),
+        }
+    }
+
+    /// 한 주기 안의 날짜 (정렬, BYSETPOS 적용)
+    fn expand(&self, dtstart: NaiveDate, period_start: NaiveDate) -> Vec<NaiveDate> {
+        let mut dates = match self.freq {
+            Frequency::Daily => {
+                let day = period_start;
+                let weekday_ok = self.by_day.is_empty() || self.by_day.iter().any(|d| d.weekday == day.weekday());
+                let month_day_ok = self.by_month_day.is_empty() || self.month_day_matches(day);
+                if weekday_ok && month_day_ok {
+                    vec![day]
+                } else {
+                    Vec::new()
+                }
+            }
+            Frequency::Weekly => {
+                let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![dtstart.weekday()]
+                } else {
+                    self.by_day.iter().map(|d| d.weekday).collect()
+                };
+                weekdays
+                    .into_iter()
+                    .map(|weekday| period_start + Duration::days(days_after(self.week_start, weekday)))
+                    .collect()
+            }
+            Frequency::Monthly => self.expand_month(dtstart, period_start.year(), period_start.month()),
+            Frequency::Yearly => self.expand_year(dtstart, period_start.year()),
+        };
+
+        dates.retain(|date| self.by_month.is_empty() || self.by_month.contains(&date.month()));
+        dates.sort();
+        dates.dedup();
+
+        if self.by_set_pos.is_empty() {
+            return dates;
+        }
+        let len = dates.len() as i32;
+        let mut picked: Vec<NaiveDate> = self
+            .by_set_pos
+            .iter()
+            .filter_map(|&pos| {
+                let index = if pos > 0 { pos - 1 } else { len + pos };
+                (0..len).contains(&index).then(|| dates[index as usize])
+            })
+            .collect();
+        picked.sort();
+        picked.dedup();
+        picked
+    }
+
+    fn month_day_matches(&self, date: NaiveDate) -> bool {
+        let len = days_in_month(date.year(), date.month()) as i32;
+        self.by_month_day
+            .iter()
+            .any(|&d| resolve_month_day(d, len) == Some(date.day()))
+    }
+
+    fn expand_month(&self, dtstart: NaiveDate, year: i32, month: u32) -> Vec<NaiveDate> {
+        let len = days_in_month(year, month) as i32;
+        let date = |day: u32| NaiveDate::from_ymd_opt(year, month, day);
+
+        let by_day: Vec<NaiveDate> = self
+            .by_day
+            .iter()
+            .flat_map(|d| pick_ordinal(weekdays_in(year, month, month, d.weekday), d.ordinal))
+            .collect();
+        if self.by_month_day.is_empty() && self.by_day.is_empty() {
+            return date(dtstart.day()).into_iter().collect();
+        }
+        if self.by_month_day.is_empty() {
+            return by_day;
+        }
+        self.by_month_day
+            .iter()
+            .filter_map(|&d| date(resolve_month_day(d, len)?))
+            .filter(|day| self.by_day.is_empty() || by_day.contains(day))
+            .collect()
+    }
+
+    fn expand_year(&self, dtstart: NaiveDate, year: i32) -> Vec<NaiveDate> {
+        if !self.by_month.is_empty() {
+            return self
+                .by_month
+                .iter()
+                .flat_map(|&month| self.expand_month(dtstart, year, month))
+                .collect();
+        }
+        if !self.by_month_day.is_empty() {
+            // 달마다 전개하고 BYDAY는 요일만 봄
+            return (1..=12)
+                .flat_map(|month| {
+                    let len = days_in_month(year, month) as i32;
+                    self.by_month_day
+                        .iter()
+                        .filter_map(move |&d| NaiveDate::from_ymd_opt(year, month, resolve_month_day(d, len)?))
+                })
+                .filter(|day| self.by_day.is_empty() || self.by_day.iter().any(|d| d.weekday == day.weekday()))
+                .collect();
+        }
+        if !self.by_day.is_empty() {
+            // 서수는 해 안에서 n번째
+            return self
+                .by_day
+                .iter()
+                .flat_map(|d| pick_ordinal(weekdays_in(year, 1, 12, d.weekday), d.ordinal))
+                .collect();
+        }
+        NaiveDate::from_ymd_opt(year, dtstart.month(), dtstart.day())
+            .into_iter()
+            .collect()
+    }
+}
+
+/// `date`가 속한 주의 첫날 (`week_start` 요일)
+fn week_start(date: NaiveDate, week_start: Weekday) -> NaiveDate {
+    date - Duration::days(days_after(week_start, date.weekday()))
+}
+
+/// `from` 요일에서 `to` 요일까지 며칠 (0~6)
+fn days_after(from: Weekday, to: Weekday) -> i64 {
+    (i64::from(to.num_days_from_monday()) - i64::from(from.num_days_from_monday())).rem_euclid(7)
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map_or(28, |d| d.day())
+}
+
+/// BYMONTHDAY 값 → 그 달의 날짜 (음수는 끝에서부터, 없는 날은 None)
+fn resolve_month_day(day: i32, len: i32) -> Option<u32> {
+    let resolved = if day > 0 { day } else { len + 1 + day };
+    (1..=len).contains(&resolved).then_some(resolved as u32)
+}
+
+/// `first_month`~`last_month` 안의 특정 요일 날짜들
+fn weekdays_in(year: i32, first_month: u32, last_month: u32, weekday: Weekday) -> Vec<NaiveDate> {
+    let Some(first) = NaiveDate::from_ymd_opt(year, first_month, 1) else {
+        return Vec::new();
+    };
+    let last = NaiveDate::from_ymd_opt(year, last_month, days_in_month(year, last_month)).unwrap_or(first);
+    let mut day = first + Duration::days(days_after(first.weekday(), weekday));
+    let mut days = Vec::new();
+    while day <= last {
+        days.push(day);
+        day += Duration::weeks(1);
+    }
+    days
+}
+
+fn pick_ordinal(days: Vec<NaiveDate>, ordinal: Option<i32>) -> Vec<NaiveDate> {
+    let Some(n) = ordinal else {
+        return days;
+    };
+    let index = if n > 0 { n - 1 } else { days.len() as i32 + n };
+    usize::try_from(index)
+        .ok()
+        .and_then(|i| days.get(i).copied())
+        .into_iter()
+        .collect()
+}
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    WEEKDAYS
+        .iter()
+        .find(|(code, _)| *code == value)
+        .map(|(_, weekday)| *weekday)
+        .ok_or_else(|| format!("invalid weekday \"{}\"", value))
+}
+
+pub fn weekday_code(weekday: Weekday) -> &'static str {
+    WEEKDAYS
+        .iter()
+        .find(|(_, w)| *w == weekday)
+        .map_or("MO", |(code, _)| code)
+}
+
+fn parse_list<T>(value: &str, parse: impl Fn(&str) -> Result<T, String>) -> Result<Vec<T>, String> {
+    value.split(',').map(|item| parse(item.trim())).collect()
+}
+
+fn parse_int(name: &str, value: &str, min: i32, max: i32, allow_negative: bool) -> Result<i32, String> {
+    let n: i32 = value
+        .parse()
+        .map_err(|_| format!("{} must be a number, got \"{}\"", name, value))?;
+    let valid = (min..=max).contains(&n) || (allow_negative && (-max..=-min).contains(&n));
+    if valid {
+        Ok(n)
+    } else {
+        Err(format!("{} out of range: {}", name, n))
+    }
+}
+
+/// UNTIL 값 ("20260131" 또는 "20260131T235959Z")의 날짜
+fn parse_until(value: &str) -> Result<NaiveDate, String> {
+    let date = value.split('T').next().unwrap_or(value);
+    NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| format!("invalid UNTIL \"{}\"", value))
+}
+
+impl FromStr for RRule {
+    type Err = String;
+
+    /// "FREQ=WEEKLY;BYDAY=MO,WE" (앞의 "RRULE:"은 생략 가능)
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let body = input.trim();
+        let body = body
+            .strip_prefix("RRULE:")
+            .or_else(|| body.strip_prefix("rrule:"))
+            .unwrap_or(body);
+
+        let mut freq = None;
+        let mut rule = RRule::new(Frequency::Daily);
+        for part in body.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("invalid RRULE part \"{}\"", part))?;
+            let value = value.trim().to_ascii_uppercase();
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => return Err(format!("unsupported FREQ \"{}\"", other)),
+                    })
+                }
+                "INTERVAL" => rule.interval = parse_int("INTERVAL", &value, 1, 1000, false)? as u32,
+                "COUNT" => rule.count = Some(parse_int("COUNT", &value, 1, 100_000, false)? as u32),
+                "UNTIL" => rule.until = Some(parse_until(&value)?),
+                "WKST" => rule.week_start = parse_weekday(&value)?,
+                "BYMONTH" => {
+                    rule.by_month = parse_list(&value, |v| parse_int("BYMONTH", v, 1, 12, false).map(|m| m as u32))?
+                }
+                "BYMONTHDAY" => rule.by_month_day = parse_list(&value, |v| parse_int("BYMONTHDAY", v, 1, 31, true))?,
+                "BYSETPOS" => rule.by_set_pos = parse_list(&value, |v| parse_int("BYSETPOS", v, 1, 366, true))?,
+                "BYDAY" => {
+                    rule.by_day = parse_list(&value, |v| {
+                        let split = v.len().saturating_sub(2);
+                        let (ordinal, weekday) = v.split_at(split);
+                        Ok(WeekdayNum {
+                            ordinal: match ordinal.trim_start_matches('+') {
+                                "" => None,
+                                n => Some(parse_int("BYDAY ordinal", n, 1, 53, true)?),
+                            },
+                            weekday: parse_weekday(weekday)?,
+                        })
+                    })?
+                }
+                other => return Err(format!("unsupported RRULE part \"{}\"", other)),
+            }
+        }
+
+        rule.freq = freq.ok_or("RRULE requires FREQ")?;
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err("COUNT and UNTIL must not both be set".to_string());
+        }
+        let has_ordinal = rule.by_day.iter().any(|d| d.ordinal.is_some());
+        if has_ordinal && !matches!(rule.freq, Frequency::Monthly | Frequency::Yearly) {
+            return Err("BYDAY ordinals are only allowed with FREQ=MONTHLY or YEARLY".to_string());
+        }
+        if rule.freq == Frequency::Weekly && !rule.by_month_day.is_empty() {
+            return Err("BYMONTHDAY is not allowed with FREQ=WEEKLY".to_string());
+        }
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for RRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |values: Vec<String>| values.join(",");
+        let mut parts = vec![format!("FREQ={}", self.freq.as_str())];
+        if self.interval > 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        if let Some(until) = self.until {
+            parts.push(format!("UNTIL={}", until.format("%Y%m%d")));
+        }
+        if !self.by_month.is_empty() {
+            parts.push(format!(
+                "BYMONTH={}",
+                join(self.by_month.iter().map(u32::to_string).collect())
+            ));
+        }
+        if !self.by_month_day.is_empty() {
+            parts.push(format!(
+                "BYMONTHDAY={}",
+                join(self.by_month_day.iter().map(i32::to_string).collect())
+            ));
+        }
+        if !self.by_day.is_empty() {
+            let days = self
+                .by_day
+                .iter()
+                .map(|d| match d.ordinal {
+                    Some(n) => format!("{}{}", n, weekday_code(d.weekday)),
+                    None => weekday_code(d.weekday).to_string(),
+                })
+                .collect();
+            parts.push(format!("BYDAY={}", join(days)));
+        }
+        if !self.by_set_pos.is_empty() {
+            parts.push(format!(
+                "BYSETPOS={}",
+                join(self.by_set_pos.iter().map(i32::to_string).collect())
+            ));
+        }
+        if self.week_start != Weekday::Mon {
+            parts.push(format!("WKST={}", weekday_code(self.week_start)));
+        }
+        write!(f, "{}", parts.join(";"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn expand(rule: &str, start: &str, to: &str) -> Vec<String> {
+        let rule: RRule = rule.parse().unwrap();
+        rule.between(date(start), date(start), date(to))
+            .iter()
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        let rule: RRule = "RRULE:FREQ=MONTHLY;INTERVAL=2;BYDAY=2MO,-1FR;UNTIL=20261231T235959Z;WKST=SU"
+            .parse()
+            .unwrap();
+        assert_eq!(rule.by_day[1].ordinal, Some(-1));
+        assert_eq!(rule.until, Some(date("2026-12-31")));
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=MONTHLY;INTERVAL=2;UNTIL=20261231;BYDAY=2MO,-1FR;WKST=SU"
+        );
+        assert_eq!(rule.to_string().parse::<RRule>().unwrap(), rule);
+
+        assert!("BYDAY=MO".parse::<RRule>().is_err());
+        assert!("FREQ=WEEKLY;BYDAY=1MO".parse::<RRule>().is_err());
+        assert!("FREQ=DAILY;COUNT=3;UNTIL=20260101".parse::<RRule>().is_err());
+        assert!("FREQ=DAILY;BYHOUR=9".parse::<RRule>().is_err());
+        assert!("FREQ=MONTHLY;BYMONTHDAY=0".parse::<RRule>().is_err());
+    }
+
+    #[test]
+    fn test_expand_rules() {
+        // 격주 월/수, 시작 주부터
+        assert_eq!(
+            expand("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE", "2026-03-04", "2026-03-31"),
+            vec!["2026-03-04", "2026-03-16", "2026-03-18", "2026-03-30"]
+        );
+        // 매달 마지막 금요일 3번
+        assert_eq!(
+            expand("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3", "2026-01-01", "2026-12-31"),
+            vec!["2026-01-30", "2026-02-27", "2026-03-27"]
+        );
+        // 매달 마지막 평일
+        assert_eq!(
+            expand(
+                "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+                "2026-01-01",
+                "2026-03-31"
+            ),
+            vec!["2026-01-30", "2026-02-27", "2026-03-31"]
+        );
+        // 31일이 없는 달은 건너뜀
+        assert_eq!(
+            expand("FREQ=MONTHLY;BYMONTHDAY=31", "2026-01-31", "2026-05-31"),
+            vec!["2026-01-31", "2026-03-31", "2026-05-31"]
+        );
+        // 매년 11월 넷째 목요일
+        assert_eq!(
+            expand(
+                "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH;UNTIL=20281231",
+                "2026-01-01",
+                "2030-12-31"
+            ),
+            vec!["2026-11-26", "2027-11-25", "2028-11-23"]
+        );
+        // 일요일 시작 주(WKST)에서 격주
+        assert_eq!(
+            expand("FREQ=WEEKLY;INTERVAL=2;BYDAY=SU,SA;WKST=SU", "2026-03-01", "2026-03-21"),
+            vec!["2026-03-01", "2026-03-07", "2026-03-15", "2026-03-21"]
+        );
+    }
+
+    #[test]
+    fn test_count_starts_at_dtstart() {
+        let rule: RRule = "FREQ=DAILY;INTERVAL=3;COUNT=4".parse().unwrap();
+        let dates = rule.between(date("2026-03-01"), date("2026-03-05"), date("2026-12-31"));
+        assert_eq!(dates, vec![date("2026-03-07"), date("2026-03-10")]);
+    }
+}
@@ -0,0 +1,447 @@
+//! 밀린 태스크 재배치
+//!
+//! 지난 날짜에 남은 태스크를 오늘부터 며칠 안으로 옮기는 변경안을 만듭니다.
+//! 우선순위가 높고 오래된 태스크부터, 하루 용량(예상 소요시간 합)과 이미 정해진 시간을 넘지 않게 배치합니다.
+//! AI가 원하는 날짜를 제안하면 먼저 시도하고, 들어가지 않으면 규칙대로 배치합니다.
+
+use chrono::{Duration, NaiveDate};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+use crate::agent::{Change, ProposedChange};
+use crate::db;
+use crate::llm::prompts::Locale;
+use crate::llm::structured::{check_date, check_time};
+use crate::llm::StructuredOutput;
+use crate::models::{Task, TaskStatus};
+use crate::recurring::{occurrence_on, RecurringPlan};
+use crate::schedule::{self, SlotAllocator, TimeSlot, DEFAULT_DURATION};
+
+/// 옮길 날짜를 찾는 기간(일, 오늘 포함)
+pub const DEFAULT_HORIZON_DAYS: i64 = 7;
+/// 하루에 배치할 수 있는 태스크 시간 합(분)
+pub const DEFAULT_DAILY_CAPACITY: i32 = 6 * 60;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RescheduleOptions {
+    pub horizon_days: Option<i64>,
+    pub daily_capacity_minutes: Option<i32>,
+    /// 건너뛴 태스크도 다시 배치
+    #[serde(default)]
+    pub include_skipped: bool,
+}
+
+impl RescheduleOptions {
+    fn horizon_days(&self) -> i64 {
+        self.horizon_days.unwrap_or(DEFAULT_HORIZON_DAYS).clamp(1, 60)
+    }
+
+    fn capacity(&self) -> i32 {
+        self.daily_capacity_minutes.unwrap_or(DEFAULT_DAILY_CAPACITY).max(DEFAULT_DURATION)
+    }
+}
+
+/// 기간 안에 넣지 못한 태스크
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnplacedTask {
+    pub task_id: String,
+    pub title: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reschedule {
+    pub changes: Vec<ProposedChange>,
+    pub unplaced: Vec<UnplacedTask>,
+}
+
+fn duration(task: &Task) -> i32 {
+    task.estimated_duration.filter(|d| *d > 0).unwrap_or(DEFAULT_DURATION)
+}
+
+/// 다시 배치할 순서로 정렬 (우선순위 높은 순, 오래된 순)
+pub fn sort_overdue(tasks: &mut [Task]) {
+    tasks.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.scheduled_date.cmp(&b.scheduled_date))
+            .then_with(|| a.scheduled_time.cmp(&b.scheduled_time))
+    });
+}
+
+struct Day {
+    date: String,
+    free: SlotAllocator,
+    /// 이미 배치된 시간(분)
+    load: i32,
+}
+
+/// 날짜별 남은 용량과 빈 시간
+pub struct Planner {
+    days: Vec<Day>,
+    capacity: i32,
+}
+
+impl Planner {
+    pub fn new(capacity: i32) -> Self {
+        Self {
+            days: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// 하루 추가 (날짜 순)
+    ///
+    /// `recurring`은 그날 아직 태스크로 생성되지 않은 반복 일정입니다.
+    pub fn add_day(&mut self, date: &str, tasks: &[Task], recurring: &[RecurringPlan], window: &TimeSlot) {
+        let day = schedule::build_day_schedule(date, tasks, recurring, &[], window);
+        let task_load: i32 = tasks
+            .iter()
+            .filter(|t| t.scheduled_date == date)
+            .filter(|t| matches!(t.status, TaskStatus::Pending | TaskStatus::InProgress))
+            .map(duration)
+            .sum();
+        let recurring_load: i32 = recurring
+            .iter()
+            .filter_map(|p| Some((p, occurrence_on(p, date)?)))
+            .map(|(p, occurrence)| occurrence.minutes().or(p.estimated_duration).unwrap_or(DEFAULT_DURATION))
+            .sum();
+
+        self.days.push(Day {
+            date: date.to_string(),
+            free: SlotAllocator::new(&day.free_slots),
+            load: task_load + recurring_load,
+        });
+    }
+
+    pub fn days(&self) -> usize {
+        self.days.len()
+    }
+
+    /// 하루에 태스크를 넣어봄. 시간이 있던 태스크는 같은 시간, 안 되면 가장 이른 빈 시간
+    fn fit(&mut self, index: usize, task: &Task, time: Option<&str>) -> Option<Option<String>> {
+        let minutes = duration(task);
+        let capacity = self.capacity;
+        let day = &mut self.days[index];
+        // 빈 날에는 용량보다 긴 태스크도 허용
+        if day.load > 0 && day.load + minutes > capacity {
+            return None;
+        }
+        let time = match time {
+            Some(time) => Some(day.free.take_at(time, minutes).or_else(|| day.free.take_earliest(minutes))?),
+            None => None,
+        };
+        day.load += minutes;
+        Some(time)
+    }
+
+    /// 태스크를 넣을 날짜와 시간. `preferred`(날짜, 시간)를 먼저 시도
+    pub fn place(&mut self, task: &Task, preferred: Option<(&str, Option<&str>)>) -> Option<(String, Option<String>)> {
+        if let Some((date, time)) = preferred {
+            if let Some(index) = self.days.iter().position(|d| d.date == date) {
+                let time = time.or(task.scheduled_time.as_deref());
+                if let Some(time) = self.fit(index, task, time) {
+                    return Some((date.to_string(), time));
+                }
+            }
+        }
+        (0..self.days.len()).find_map(|index| {
+            let time = self.fit(index, task, task.scheduled_time.as_deref())?;
+            Some((self.days[index].date.clone(), time))
+        })
+    }
+
+    /// prompt용 날짜별 남은 용량과 빈 시간
+    pub fn describe(&self, locale: Locale) -> String {
+        self.days
+            .iter()
+            .map(|day| {
+                let slots = day
+                    .free
+                    .slots()
+                    .iter()
+                    .map(|s| format!("{}-{}", s.start, s.end))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let left = (self.capacity - day.load).max(0);
+                match locale {
+                    Locale::En => format!("- {}: {} min left, free {}", day.date, left, slots),
+                    Locale::Ko => format!("- {}: {}분 남음, 빈 시간 {}", day.date, left, slots),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// AI가 제안한 날짜/시간 (task id 기준)
+pub type Preferred = HashMap<String, (String, Option<String>)>;
+
+/// 밀린 태스크(`sort_overdue` 순서)를 배치한 변경안
+pub fn reschedule(overdue: &[Task], planner: &mut Planner, preferred: &Preferred) -> Reschedule {
+    let mut changes = Vec::new();
+    let mut unplaced = Vec::new();
+
+    for task in overdue {
+        let hint = preferred
+            .get(&task.id)
+            .map(|(date, time)| (date.as_str(), time.as_deref()));
+        match planner.place(task, hint) {
+            Some((date, time)) => {
+                let at = time.as_deref().map(|t| format!(" {}", t)).unwrap_or_default();
+                changes.push(ProposedChange {
+                    summary: format!("Move \"{}\" from {} to {}{}", task.title, task.scheduled_date, date, at),
+                    change: Change::RescheduleTask {
+                        task_id: task.id.clone(),
+                        scheduled_date: date,
+                        scheduled_time: time,
+                    },
+                });
+            }
+            None => unplaced.push(UnplacedTask {
+                task_id: task.id.clone(),
+                title: task.title.clone(),
+                reason: format!("No room in the next {} days", planner.days()),
+            }),
+        }
+    }
+
+    Reschedule { changes, unplaced }
+}
+
+/// 밀린 태스크와 오늘부터 기간 안의 일정 불러오기
+///
+/// 오늘은 `now`(현재 시각) 이후의 빈 시간만 사용합니다.
+pub async fn load(
+    pool: &SqlitePool,
+    today: NaiveDate,
+    now: &str,
+    options: &RescheduleOptions,
+) -> Result<(Vec<Task>, Planner), sqlx::Error> {
+    let from = today.format("%Y-%m-%d").to_string();
+    let to = (today + Duration::days(options.horizon_days() - 1))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let mut overdue = db::unfinished_tasks_before(pool, &from, options.include_skipped).await?;
+    sort_overdue(&mut overdue);
+    let tasks = db::tasks_between(pool, &from, &to).await?;
+    let recurring = db::active_recurring_plans(pool).await?;
+
+    let mut planner = Planner::new(options.capacity());
+    for offset in 0..options.horizon_days() {
+        let date = (today + Duration::days(offset)).format("%Y-%m-%d").to_string();
+        let generated = db::generated_recurring_ids(pool, &date).await?;
+        let pending: Vec<RecurringPlan> = recurring
+            .iter()
+            .filter(|p| !generated.contains(&p.id))
+            .cloned()
+            .collect();
+        let start = if offset == 0 { now.max(schedule::DAY_START) } else { schedule::DAY_START };
+        let window = TimeSlot {
+            start: start.to_string(),
+            end: schedule::DAY_END.to_string(),
+        };
+        planner.add_day(&date, &tasks, &pending, &window);
+    }
+
+    Ok((overdue, planner))
+}
+
+/// prompt용 밀린 태스크 목록
+pub fn describe_overdue(tasks: &[Task], locale: Locale) -> String {
+    tasks
+        .iter()
+        .map(|t| {
+            let time = t.scheduled_time.as_deref().map(|t| format!(" {}", t)).unwrap_or_default();
+            match locale {
+                Locale::En => format!(
+                    "- [{}] {} (was {}{}, priority {}, {} min)",
+                    t.id, t.title, t.scheduled_date, time, t.priority, duration(t)
+                ),
+                Locale::Ko => format!(
+                    "- [{}] {} (원래 {}{}, 우선순위 {}, {}분)",
+                    t.id, t.title, t.scheduled_date, time, t.priority, duration(t)
+                ),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// AI가 제안한 태스크 하나의 새 날짜
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RescheduleMove {
+    pub task_id: String,
+    /// 새 날짜 "YYYY-MM-DD"
+    pub scheduled_date: String,
+    /// 새 시작 시간 "HH:MM"
+    pub scheduled_time: Option<String>,
+}
+
+/// `propose_reschedule`의 AI 출력
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RescheduleOutput {
+    pub moves: Vec<RescheduleMove>,
+}
+
+impl StructuredOutput for RescheduleOutput {
+    const TOOL_NAME: &'static str = "reschedule_tasks";
+
+    fn validate(&self) -> Result<(), String> {
+        for (index, m) in self.moves.iter().enumerate() {
+            check_date(&format!("moves[{}].scheduledDate", index), Some(&m.scheduled_date))?;
+            check_time(&format!("moves[{}].scheduledTime", index), m.scheduled_time.as_deref())?;
+        }
+        Ok(())
+    }
+}
+
+impl RescheduleOutput {
+    pub fn into_preferred(self) -> Preferred {
+        self.moves
+            .into_iter()
+            .map(|m| (m.task_id, (m.scheduled_date, m.scheduled_time)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(title: &str, date: &str, time: Option<&str>, minutes: i32, priority: i32) -> Task {
+        Task {
+            scheduled_time: time.map(String::from),
+            estimated_duration: Some(minutes),
+            priority,
+            ..Task::test(title, date)
+        }
+    }
+
+    fn window(start: &str) -> TimeSlot {
+        TimeSlot {
+            start: start.to_string(),
+            end: "12:00".to_string(),
+        }
+    }
+
+    fn moves(result: &Reschedule) -> Vec<(String, String, Option<String>)> {
+        result
+            .changes
+            .iter()
+            .map(|c| match &c.change {
+                Change::RescheduleTask {
+                    task_id,
+                    scheduled_date,
+                    scheduled_time,
+                } => (task_id.clone(), scheduled_date.clone(), scheduled_time.clone()),
+                other => panic!("unexpected change {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reschedule_respects_capacity_priority_and_busy_times() {
+        let existing = vec![
+            task("Standup", "2026-03-04", Some("10:00"), 60, 1),
+            task("Lunch prep", "2026-03-05", None, 60, 1),
+        ];
+        let mut planner = Planner::new(180);
+        planner.add_day("2026-03-04", &existing, &[], &window("09:30"));
+        planner.add_day("2026-03-05", &existing, &[], &window("08:00"));
+
+        let mut overdue = vec![
+            task("Laundry", "2026-03-01", None, 90, 0),
+            task("Call bank", "2026-03-02", Some("10:00"), 30, 2),
+            task("Taxes", "2026-02-20", Some("09:00"), 120, 2),
+            task("Huge", "2026-02-01", None, 600, 0),
+        ];
+        sort_overdue(&mut overdue);
+        let result = reschedule(&overdue, &mut planner, &Preferred::new());
+
+        let at = |date: &str, time: Option<&str>| (date.to_string(), time.map(String::from));
+        let moved: Vec<_> = moves(&result).into_iter().map(|(id, d, t)| (id, at(&d, t.as_deref()))).collect();
+        assert_eq!(
+            moved,
+            vec![
+                // 오늘은 10:00에 일정이 있고 09:30 전에는 배치하지 않음
+                ("Taxes".to_string(), at("2026-03-05", Some("09:00"))),
+                ("Call bank".to_string(), at("2026-03-04", Some("09:30"))),
+                ("Laundry".to_string(), at("2026-03-04", None)),
+            ]
+        );
+        assert_eq!(result.unplaced.len(), 1);
+        assert_eq!(result.unplaced[0].task_id, "Huge");
+        assert_eq!(result.changes[1].summary, "Move \"Call bank\" from 2026-03-02 to 2026-03-04 09:30");
+    }
+
+    #[test]
+    fn test_reschedule_prefers_ai_dates_when_they_fit() {
+        let mut planner = Planner::new(120);
+        planner.add_day("2026-03-04", &[], &[], &window("08:00"));
+        planner.add_day("2026-03-05", &[], &[], &window("08:00"));
+        planner.add_day("2026-03-06", &[], &[], &window("08:00"));
+
+        let overdue = vec![task("Gym", "2026-03-01", None, 60, 1), task("Read", "2026-03-01", None, 60, 1)];
+        let preferred = Preferred::from([
+            ("Gym".to_string(), ("2026-03-06".to_string(), Some("11:30".to_string()))),
+            ("Read".to_string(), ("2026-03-20".to_string(), None)),
+        ]);
+        let result = reschedule(&overdue, &mut planner, &preferred);
+
+        // 11:30에는 60분이 들어가지 않아 그날 가장 이른 시간, 기간 밖 날짜는 규칙대로
+        assert_eq!(
+            moves(&result),
+            vec![
+                ("Gym".to_string(), "2026-03-06".to_string(), Some("08:00".to_string())),
+                ("Read".to_string(), "2026-03-04".to_string(), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_skips_generated_recurring_and_finished_tasks() {
+        let pool = db::test_pool().await;
+        sqlx::raw_sql(
+            "INSERT INTO tasks (id, title, scheduled_date, scheduled_time, estimated_duration, status, priority, created_at, updated_at) VALUES
+                ('old', 'Old', '2026-03-01', NULL, 30, 'pending', 1, '', ''),
+                ('done', 'Done', '2026-03-01', NULL, 30, 'completed', 1, '', ''),
+                ('skip', 'Skip', '2026-03-02', NULL, 30, 'skipped', 1, '', ''),
+                ('today', 'Today', '2026-03-04', '08:00', 60, 'pending', 1, '', '');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let options = RescheduleOptions {
+            horizon_days: Some(2),
+            ..Default::default()
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let (overdue, mut planner) = load(&pool, today, "07:00", &options).await.unwrap();
+        assert_eq!(overdue.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), vec!["old"]);
+        assert_eq!(planner.days(), 2);
+        assert!(planner.describe(Locale::En).starts_with("- 2026-03-04: 300 min left, free 09:00-22:00"));
+
+        let timed = task("Old", "2026-03-01", Some("08:00"), 30, 0);
+        assert_eq!(
+            planner.place(&timed, None),
+            Some(("2026-03-04".to_string(), Some("09:00".to_string())))
+        );
+
+        let options = RescheduleOptions {
+            include_skipped: true,
+            ..options
+        };
+        let (overdue, _) = load(&pool, today, "07:00", &options).await.unwrap();
+        assert_eq!(overdue.len(), 2);
+    }
+}
@@ -0,0 +1,567 @@
+//! 주간 회고
+//!
+//! 한 주(월~일)의 태스크, 완료/건너뛰기 패턴, 예상 대비 실제 소요시간, Focus 앱 차단 기록을 모아
+//! AI에게 회고를 요청하고, 결과를 `weekly_reviews`에 저장해 지난 회고를 다시 볼 수 있게 합니다.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
+
+use crate::db;
+use crate::llm::prompts::Locale;
+use crate::llm::StructuredOutput;
+use crate::models::{generate_id, now_iso, Task, TaskStatus};
+use crate::progress::{calculate_daily_progress, DailyProgress};
+
+/// 예상 시간과 가장 많이 어긋난 태스크를 몇 개까지 보여줄지
+const ESTIMATE_MISS_LIMIT: usize = 3;
+/// 많이 차단된 앱을 몇 개까지 보여줄지
+const TOP_APP_LIMIT: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameCount {
+    pub name: String,
+    pub count: i32,
+}
+
+/// 끝내지 못한 태스크
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlippedTask {
+    pub title: String,
+    pub scheduled_date: String,
+    pub status: TaskStatus,
+}
+
+/// 예상과 실제 소요시간이 다른 태스크
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateMiss {
+    pub title: String,
+    pub estimated_minutes: i32,
+    pub actual_minutes: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusStats {
+    /// 앱 차단 횟수
+    pub blocks: i32,
+    pub top_apps: Vec<NameCount>,
+    /// 차단이 가장 많았던 날
+    pub busiest_date: Option<String>,
+}
+
+/// 회고에 쓰는 한 주의 통계
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyStats {
+    pub week_start: String,
+    pub week_end: String,
+    pub days: Vec<DailyProgress>,
+    pub total_tasks: i32,
+    pub completed_tasks: i32,
+    pub skipped_tasks: i32,
+    /// 지난 날짜인데 완료하지 못한 태스크 수
+    pub unfinished_tasks: i32,
+    pub completion_rate: f64,
+    /// 예상/실제 시간이 모두 있는 완료 태스크의 합
+    pub estimated_minutes: i32,
+    pub actual_minutes: i32,
+    /// 실제 / 예상 (1보다 크면 과소 추정)
+    pub duration_ratio: Option<f64>,
+    pub estimate_misses: Vec<EstimateMiss>,
+    /// 건너뛰었거나 지난 날짜에 끝내지 못한 태스크
+    pub slipped: Vec<SlippedTask>,
+    /// 이번 주에 두 번 이상 건너뛴 태스크 제목
+    pub repeated_skips: Vec<NameCount>,
+    pub focus: FocusStats,
+}
+
+/// `date`가 속한 주의 월요일과 일요일
+pub fn week_bounds(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    (start, start + Duration::days(6))
+}
+
+fn count_by<'a>(names: impl Iterator<Item = &'a str>) -> Vec<NameCount> {
+    let mut counts: HashMap<&str, i32> = HashMap::new();
+    for name in names {
+        *counts.entry(name).or_default() += 1;
+    }
+    let mut counts: Vec<NameCount> = counts
+        .into_iter()
+        .map(|(name, count)| NameCount {
+            name: name.to_string(),
+            count,
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    counts
+}
+
+/// 한 주의 태스크와 차단 기록((앱 이름, 날짜))으로 통계 계산
+///
+/// `today` 이후의 미완료 태스크는 아직 밀린 것으로 보지 않습니다.
+pub fn summarize(week_start: NaiveDate, tasks: &[Task], blocks: &[(String, String)], today: &str) -> WeeklyStats {
+    let (start, end) = week_bounds(week_start);
+    let days: Vec<DailyProgress> = (0..7)
+        .map(|offset| {
+            let date = (start + Duration::days(offset)).format("%Y-%m-%d").to_string();
+            let day_tasks: Vec<Task> = tasks.iter().filter(|t| t.scheduled_date == date).cloned().collect();
+            calculate_daily_progress(&date, &day_tasks)
+        })
+        .collect();
+    let week = calculate_daily_progress("", tasks);
+
+    let slipped: Vec<SlippedTask> = tasks
+        .iter()
+        .filter(|t| match t.status {
+            TaskStatus::Skipped => true,
+            TaskStatus::Pending | TaskStatus::InProgress => t.scheduled_date.as_str() < today,
+            TaskStatus::Completed => false,
+        })
+        .map(|t| SlippedTask {
+            title: t.title.clone(),
+            scheduled_date: t.scheduled_date.clone(),
+            status: t.status.clone(),
+        })
+        .collect();
+    let unfinished_tasks = slipped.iter().filter(|t| !matches!(t.status, TaskStatus::Skipped)).count() as i32;
+    let repeated_skips = count_by(
+        tasks
+            .iter()
+            .filter(|t| matches!(t.status, TaskStatus::Skipped))
+            .map(|t| t.title.as_str()),
+    )
+    .into_iter()
+    .filter(|c| c.count >= 2)
+    .collect();
+
+    let measured: Vec<(&Task, i32, i32)> = tasks
+        .iter()
+        .filter(|t| matches!(t.status, TaskStatus::Completed))
+        .filter_map(|t| Some((t, t.estimated_duration?, t.actual_duration?)))
+        .collect();
+    let estimated_minutes: i32 = measured.iter().map(|(_, e, _)| e).sum();
+    let actual_minutes: i32 = measured.iter().map(|(_, _, a)| a).sum();
+    let mut estimate_misses: Vec<EstimateMiss> = measured
+        .iter()
+        .filter(|(_, e, a)| e != a)
+        .map(|(t, e, a)| EstimateMiss {
+            title: t.title.clone(),
+            estimated_minutes: *e,
+            actual_minutes: *a,
+        })
+        .collect();
+    estimate_misses.sort_by_key(|m| std::cmp::Reverse((m.actual_minutes - m.estimated_minutes).abs()));
+    estimate_misses.truncate(ESTIMATE_MISS_LIMIT);
+
+    let mut top_apps = count_by(blocks.iter().map(|(app, _)| app.as_str()));
+    top_apps.truncate(TOP_APP_LIMIT);
+    let busiest_date = count_by(blocks.iter().map(|(_, date)| date.as_str()))
+        .into_iter()
+        .next()
+        .map(|c| c.name);
+
+    WeeklyStats {
+        week_start: start.format("%Y-%m-%d").to_string(),
+        week_end: end.format("%Y-%m-%d").to_string(),
+        days,
+        total_tasks: week.total_tasks,
+        completed_tasks: week.completed_tasks,
+        skipped_tasks: week.skipped_tasks,
+        unfinished_tasks,
+        completion_rate: week.completion_rate,
+        estimated_minutes,
+        actual_minutes,
+        duration_ratio: (estimated_minutes > 0).then(|| actual_minutes as f64 / estimated_minutes as f64),
+        estimate_misses,
+        slipped,
+        repeated_skips,
+        focus: FocusStats {
+            blocks: blocks.len() as i32,
+            top_apps,
+            busiest_date,
+        },
+    }
+}
+
+/// DB에서 `date`가 속한 주의 통계 계산
+pub async fn collect(pool: &SqlitePool, date: NaiveDate, today: &str) -> Result<WeeklyStats, sqlx::Error> {
+    let (start, end) = week_bounds(date);
+    let from = start.format("%Y-%m-%d").to_string();
+    let to = end.format("%Y-%m-%d").to_string();
+
+    let tasks = db::tasks_between(pool, &from, &to).await?;
+    let blocks = sqlx::query(
+        "SELECT app_name, DATE(blocked_at) AS date FROM focus_block_events \
+         WHERE DATE(blocked_at) BETWEEN ? AND ?",
+    )
+    .bind(&from)
+    .bind(&to)
+    .fetch_all(pool)
+    .await?
+    .iter()
+    .map(|row| (row.get("app_name"), row.get("date")))
+    .collect::<Vec<(String, String)>>();
+
+    Ok(summarize(start, &tasks, &blocks, today))
+}
+
+impl WeeklyStats {
+    pub fn is_empty(&self) -> bool {
+        self.total_tasks == 0 && self.focus.blocks == 0
+    }
+
+    /// prompt용 통계 요약
+    pub fn describe(&self, locale: Locale) -> String {
+        let rate = (self.completion_rate * 100.0).round();
+        let mut lines = match locale {
+            Locale::En => vec![
+                format!(
+                    "- Tasks: {} total, {} completed, {} skipped, {} left unfinished",
+                    self.total_tasks, self.completed_tasks, self.skipped_tasks, self.unfinished_tasks
+                ),
+                format!("- Completion rate: {}%", rate),
+                "- By day:".to_string(),
+            ],
+            Locale::Ko => vec![
+                format!(
+                    "- 태스크: 전체 {}개, 완료 {}개, 건너뜀 {}개, 미완료 {}개",
+                    self.total_tasks, self.completed_tasks, self.skipped_tasks, self.unfinished_tasks
+                ),
+                format!("- 완료율: {}%", rate),
+                "- 요일별:".to_string(),
+            ],
+        };
+        for day in &self.days {
+            let weekday = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+                .map(|d| d.format("%a").to_string())
+                .unwrap_or_default();
+            lines.push(match locale {
+                Locale::En => format!(
+                    "  - {} {}: {}/{} done, {} skipped",
+                    day.date, weekday, day.completed_tasks, day.total_tasks, day.skipped_tasks
+                ),
+                Locale::Ko => format!(
+                    "  - {} {}: {}/{} 완료, {}개 건너뜀",
+                    day.date, weekday, day.completed_tasks, day.total_tasks, day.skipped_tasks
+                ),
+            });
+        }
+
+        if let Some(ratio) = self.duration_ratio {
+            lines.push(match locale {
+                Locale::En => format!(
+                    "- Estimated {} min vs. actual {} min ({:.1}x)",
+                    self.estimated_minutes, self.actual_minutes, ratio
+                ),
+                Locale::Ko => format!(
+                    "- 예상 {}분, 실제 {}분 ({:.1}배)",
+                    self.estimated_minutes, self.actual_minutes, ratio
+                ),
+            });
+        }
+        for miss in &self.estimate_misses {
+            lines.push(match locale {
+                Locale::En => format!(
+                    "  - {}: estimated {} min, took {} min",
+                    miss.title, miss.estimated_minutes, miss.actual_minutes
+                ),
+                Locale::Ko => format!(
+                    "  - {}: 예상 {}분, 실제 {}분",
+                    miss.title, miss.estimated_minutes, miss.actual_minutes
+                ),
+            });
+        }
+
+        lines.push(match locale {
+            Locale::En => "- Slipped tasks:".to_string(),
+            Locale::Ko => "- 밀린 태스크:".to_string(),
+        });
+        if self.slipped.is_empty() {
+            lines.push(format!("  - {}", locale.none_label()));
+        }
+        for task in &self.slipped {
+            let status = match (locale, &task.status) {
+                (Locale::En, TaskStatus::Skipped) => "skipped",
+                (Locale::En, _) => "not done",
+                (Locale::Ko, TaskStatus::Skipped) => "건너뜀",
+                (Locale::Ko, _) => "못 함",
+            };
+            lines.push(format!("  - {} {} ({})", task.scheduled_date, task.title, status));
+        }
+        for skip in &self.repeated_skips {
+            lines.push(match locale {
+                Locale::En => format!("- Skipped {} times this week: {}", skip.count, skip.name),
+                Locale::Ko => format!("- 이번 주 {}번 건너뜀: {}", skip.count, skip.name),
+            });
+        }
+
+        let apps = self
+            .focus
+            .top_apps
+            .iter()
+            .map(|a| format!("{} ({})", a.name, a.count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(match locale {
+            Locale::En => format!("- Focus mode app blocks: {}", self.focus.blocks),
+            Locale::Ko => format!("- 집중 모드 앱 차단: {}회", self.focus.blocks),
+        });
+        if !apps.is_empty() {
+            lines.push(match locale {
+                Locale::En => format!("  - Most blocked: {}", apps),
+                Locale::Ko => format!("  - 가장 많이 차단된 앱: {}", apps),
+            });
+        }
+        if let Some(date) = &self.focus.busiest_date {
+            lines.push(match locale {
+                Locale::En => format!("  - Most blocks on {}", date),
+                Locale::Ko => format!("  - 차단이 가장 많았던 날: {}", date),
+            });
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// 밀린 태스크에 대한 회고
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SlippedTaskNote {
+    pub title: String,
+    /// 밀린 이유로 보이는 것과 다음에 다르게 할 점
+    pub note: String,
+}
+
+/// AI 주간 회고
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyReviewResponse {
+    /// 한두 문장 요약
+    pub summary: String,
+    /// 잘한 점
+    pub wins: Vec<String>,
+    pub slipped_tasks: Vec<SlippedTaskNote>,
+    /// 예상 소요시간이 어느 쪽으로 얼마나 틀렸는지
+    pub estimation_bias: String,
+    /// 다음 주를 위한 제안 (1-5개)
+    pub suggestions: Vec<String>,
+}
+
+impl StructuredOutput for WeeklyReviewResponse {
+    const TOOL_NAME: &'static str = "weekly_review";
+
+    fn validate(&self) -> Result<(), String> {
+        if self.summary.trim().is_empty() || self.estimation_bias.trim().is_empty() {
+            return Err("summary and estimationBias must not be empty".to_string());
+        }
+        if self.suggestions.is_empty() || self.suggestions.len() > 5 {
+            return Err(format!(
+                "suggestions must contain 1-5 items, got {}",
+                self.suggestions.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 저장된 주간 회고
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyReview {
+    pub id: String,
+    pub week_start: String,
+    pub week_end: String,
+    pub stats: WeeklyStats,
+    pub review: WeeklyReviewResponse,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn review_from_row(row: &SqliteRow) -> Result<WeeklyReview, sqlx::Error> {
+    let decode = |column: &str| sqlx::Error::ColumnDecode {
+        index: column.to_string(),
+        source: format!("invalid {} JSON", column).into(),
+    };
+    Ok(WeeklyReview {
+        id: row.get("id"),
+        week_start: row.get("week_start"),
+        week_end: row.get("week_end"),
+        stats: serde_json::from_str(row.get("stats")).map_err(|_| decode("stats"))?,
+        review: serde_json::from_str(row.get("review")).map_err(|_| decode("review"))?,
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+const REVIEW_QUERY: &str =
+    "SELECT id, week_start, week_end, stats, review, created_at, updated_at FROM weekly_reviews";
+
+/// 회고 저장. 같은 주의 회고가 있으면 덮어씀
+pub async fn save_review(
+    pool: &SqlitePool,
+    stats: &WeeklyStats,
+    review: &WeeklyReviewResponse,
+) -> Result<WeeklyReview, sqlx::Error> {
+    let now = now_iso();
+    sqlx::query(
+        "INSERT INTO weekly_reviews (id, week_start, week_end, stats, review, created_at, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(week_start) DO UPDATE SET \
+         week_end = excluded.week_end, stats = excluded.stats, review = excluded.review, \
+         updated_at = excluded.updated_at",
+    )
+    .bind(generate_id())
+    .bind(&stats.week_start)
+    .bind(&stats.week_end)
+    .bind(serde_json::to_string(stats).unwrap_or_default())
+    .bind(serde_json::to_string(review).unwrap_or_default())
+    .bind(&now)
+    .bind(&now)
+    .execute(pool)
+    .await?;
+
+    get_review(pool, &stats.week_start)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// 주 시작일(월요일)로 회고 조회
+pub async fn get_review(pool: &SqlitePool, week_start: &str) -> Result<Option<WeeklyReview>, sqlx::Error> {
+    let row = sqlx::query(&format!("{} WHERE week_start = ?", REVIEW_QUERY))
+        .bind(week_start)
+        .fetch_optional(pool)
+        .await?;
+    row.as_ref().map(review_from_row).transpose()
+}
+
+/// 최근 주 순 회고 목록
+pub async fn list_reviews(pool: &SqlitePool) -> Result<Vec<WeeklyReview>, sqlx::Error> {
+    let rows = sqlx::query(&format!("{} ORDER BY week_start DESC", REVIEW_QUERY))
+        .fetch_all(pool)
+        .await?;
+    rows.iter().map(review_from_row).collect()
+}
+
+/// 회고 삭제. 있었으면 true
+pub async fn delete_review(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM weekly_reviews WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(title: &str, date: &str, status: TaskStatus, estimated: Option<i32>, actual: Option<i32>) -> Task {
+        Task {
+            title: title.to_string(),
+            estimated_duration: estimated,
+            actual_duration: actual,
+            status,
+            ..Task::test(&generate_id(), date)
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn week() -> Vec<Task> {
+        vec![
+            task("Gym", "2026-03-02", TaskStatus::Skipped, None, None),
+            task("Report", "2026-03-02", TaskStatus::Completed, Some(60), Some(120)),
+            task("Gym", "2026-03-04", TaskStatus::Skipped, None, None),
+            task("Email", "2026-03-04", TaskStatus::Completed, Some(30), Some(20)),
+            task("Taxes", "2026-03-05", TaskStatus::Pending, Some(45), None),
+            task("Groceries", "2026-03-07", TaskStatus::Pending, None, None),
+        ]
+    }
+
+    #[test]
+    fn test_week_bounds_start_on_monday() {
+        // 2026-03-06은 금요일
+        assert_eq!(week_bounds(date("2026-03-06")), (date("2026-03-02"), date("2026-03-08")));
+        assert_eq!(week_bounds(date("2026-03-02")), (date("2026-03-02"), date("2026-03-08")));
+        assert_eq!(week_bounds(date("2026-03-08")), (date("2026-03-02"), date("2026-03-08")));
+    }
+
+    #[test]
+    fn test_summarize_week() {
+        let blocks = vec![
+            ("YouTube".to_string(), "2026-03-03".to_string()),
+            ("YouTube".to_string(), "2026-03-04".to_string()),
+            ("Slack".to_string(), "2026-03-04".to_string()),
+        ];
+        let stats = summarize(date("2026-03-06"), &week(), &blocks, "2026-03-06");
+
+        assert_eq!(stats.week_start, "2026-03-02");
+        assert_eq!(stats.days.len(), 7);
+        assert_eq!(stats.days[2].completed_tasks, 1);
+        assert_eq!((stats.total_tasks, stats.completed_tasks, stats.skipped_tasks), (6, 2, 2));
+        assert_eq!(stats.completion_rate, 0.5);
+
+        // 토요일 장보기는 아직 밀린 것이 아님
+        let slipped: Vec<&str> = stats.slipped.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(slipped, vec!["Gym", "Gym", "Taxes"]);
+        assert_eq!(stats.unfinished_tasks, 1);
+        assert_eq!(
+            stats.repeated_skips,
+            vec![NameCount {
+                name: "Gym".to_string(),
+                count: 2
+            }]
+        );
+
+        assert_eq!((stats.estimated_minutes, stats.actual_minutes), (90, 140));
+        assert_eq!(stats.estimate_misses[0].title, "Report");
+        assert!(stats.duration_ratio.unwrap() > 1.5);
+
+        assert_eq!(stats.focus.blocks, 3);
+        assert_eq!(stats.focus.top_apps[0].name, "YouTube");
+        assert_eq!(stats.focus.busiest_date.as_deref(), Some("2026-03-04"));
+
+        let described = stats.describe(Locale::En);
+        assert!(described.contains("- Tasks: 6 total, 2 completed, 2 skipped, 1 left unfinished"));
+        assert!(described.contains("  - 2026-03-05 Taxes (not done)"));
+        assert!(described.contains("- Skipped 2 times this week: Gym"));
+        assert!(described.contains("  - Most blocked: YouTube (2), Slack (1)"));
+    }
+
+    #[tokio::test]
+    async fn test_save_review_replaces_same_week() {
+        let pool = db::test_pool().await;
+        let stats = collect(&pool, date("2026-03-06"), "2026-03-06").await.unwrap();
+        assert!(stats.is_empty());
+
+        let mut review = WeeklyReviewResponse {
+            summary: "Quiet week".to_string(),
+            wins: vec![],
+            slipped_tasks: vec![],
+            estimation_bias: "No data".to_string(),
+            suggestions: vec!["Plan Monday".to_string()],
+        };
+        let first = save_review(&pool, &stats, &review).await.unwrap();
+        review.summary = "Rewritten".to_string();
+        let second = save_review(&pool, &stats, &review).await.unwrap();
+
+        assert_eq!(first.id, second.id);
+        let reviews = list_reviews(&pool).await.unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].review.summary, "Rewritten");
+        assert_eq!(reviews[0].stats.week_end, "2026-03-08");
+
+        assert!(delete_review(&pool, &first.id).await.unwrap());
+        assert!(get_review(&pool, "2026-03-02").await.unwrap().is_none());
+    }
+}
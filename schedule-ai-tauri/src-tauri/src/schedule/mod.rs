@@ -0,0 +1,496 @@
+//! 하루 일정의 빈 시간 계산
+//!
+//! 그날의 태스크와 반복 일정이 차지한 시간을 활동 시간대에서 빼서 빈 슬롯을 구하고,
+//! AI가 만든 태스크를 기존 일정과 겹치지 않게 배치합니다.
+
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::llm::prompts::Locale;
+use crate::models::{CoreTime, Task, TaskStatus};
+use crate::progress::{calculate_daily_progress, calculate_streak, DailyProgress};
+use crate::recurring::{occurrence_on, RecurringPlan};
+
+/// 기본 활동 시간대
+pub const DAY_START: &str = "08:00";
+pub const DAY_END: &str = "22:00";
+/// 소요시간이 없는 일정이 차지하는 시간(분)
+pub const DEFAULT_DURATION: i32 = 30;
+/// 이보다 짧은 빈 시간은 슬롯으로 보지 않음(분)
+pub const MIN_SLOT_MINUTES: i32 = 15;
+/// 최근 완료 통계를 계산하는 기간(일)
+pub const HISTORY_DAYS: i64 = 14;
+
+/// "HH:MM" → 자정부터의 분
+pub fn to_minutes(time: &str) -> Option<i32> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .ok()
+        .map(|t| (t.hour() * 60 + t.minute()) as i32)
+}
+
+/// 자정부터의 분 → "HH:MM" (24:00 이상은 23:59로 자름)
+pub fn format_minutes(minutes: i32) -> String {
+    let minutes = minutes.clamp(0, 24 * 60 - 1);
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// 현재 시각을 5분 단위로 올림한 "HH:MM"
+pub fn now_rounded() -> String {
+    let now = chrono::Local::now();
+    let minutes = (now.hour() * 60 + now.minute()) as i32;
+    format_minutes((minutes + 4) / 5 * 5)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeSlot {
+    pub start: String,
+    pub end: String,
+}
+
+impl TimeSlot {
+    fn from_minutes(start: i32, end: i32) -> Self {
+        Self {
+            start: format_minutes(start),
+            end: format_minutes(end),
+        }
+    }
+
+    fn range(&self) -> Option<(i32, i32)> {
+        Some((to_minutes(&self.start)?, to_minutes(&self.end)?))
+    }
+
+    pub fn minutes(&self) -> i32 {
+        self.range().map(|(start, end)| end - start).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BusyKind {
+    Task,
+    Recurring,
+}
+
+/// 이미 시간이 정해진 일정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BusyBlock {
+    pub title: String,
+    pub start: String,
+    pub end: String,
+    pub kind: BusyKind,
+}
+
+/// 코어 타임 (집중 시간대)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusWindow {
+    pub name: String,
+    pub start: String,
+    pub end: String,
+}
+
+/// 하루 일정과 빈 시간
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaySchedule {
+    pub date: String,
+    pub busy: Vec<BusyBlock>,
+    /// 시간이 정해지지 않은 일정 제목
+    pub unscheduled: Vec<String>,
+    pub focus_windows: Vec<FocusWindow>,
+    pub free_slots: Vec<TimeSlot>,
+}
+
+/// 그날의 일정에서 빈 슬롯 계산
+///
+/// `tasks`는 그날 태스크, `recurring`은 아직 태스크로 생성되지 않은 활성 반복 일정입니다.
+/// 건너뛴 태스크는 시간을 차지하지 않습니다.
+pub fn build_day_schedule(
+    date: &str,
+    tasks: &[Task],
+    recurring: &[RecurringPlan],
+    core_times: &[CoreTime],
+    window: &TimeSlot,
+) -> DaySchedule {
+    let mut busy = Vec::new();
+    let mut unscheduled = Vec::new();
+
+    let mut push = |title: &str, start: Option<&str>, end: Option<i32>, kind: BusyKind| {
+        match start.and_then(to_minutes) {
+            Some(start) => {
+                let end = end.filter(|end| *end > start).unwrap_or(start + DEFAULT_DURATION);
+                busy.push(BusyBlock {
+                    title: title.to_string(),
+                    start: format_minutes(start),
+                    end: format_minutes(end),
+                    kind,
+                });
+            }
+            None => unscheduled.push(title.to_string()),
+        }
+    };
+
+    for task in tasks.iter().filter(|t| t.scheduled_date == date) {
+        if matches!(task.status, TaskStatus::Skipped) {
+            continue;
+        }
+        let start = task.scheduled_time.as_deref();
+        let end = start
+            .and_then(to_minutes)
+            .map(|s| s + task.estimated_duration.unwrap_or(DEFAULT_DURATION));
+        push(&task.title, start, end, BusyKind::Task);
+    }
+
+    for (plan, occurrence) in recurring.iter().filter_map(|p| Some((p, occurrence_on(p, date)?))) {
+        let start = occurrence.scheduled_time.as_deref();
+        let end = occurrence.end_time.as_deref().and_then(to_minutes).or_else(|| {
+            start
+                .and_then(to_minutes)
+                .map(|s| s + plan.estimated_duration.unwrap_or(DEFAULT_DURATION))
+        });
+        push(&occurrence.title, start, end, BusyKind::Recurring);
+    }
+
+    busy.sort_by(|a, b| a.start.cmp(&b.start));
+
+    let weekday = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.weekday().num_days_from_sunday() as i32)
+        .ok();
+    let focus_windows = core_times
+        .iter()
+        .filter(|c| c.is_active && weekday.is_some_and(|w| c.days_of_week.contains(&w)))
+        .map(|c| FocusWindow {
+            name: c.name.clone(),
+            start: c.start_time.clone(),
+            end: c.end_time.clone(),
+        })
+        .collect();
+
+    let free_slots = free_slots(window, &busy);
+
+    DaySchedule {
+        date: date.to_string(),
+        busy,
+        unscheduled,
+        focus_windows,
+        free_slots,
+    }
+}
+
+/// 활동 시간대에서 바쁜 시간을 뺀 빈 슬롯
+fn free_slots(window: &TimeSlot, busy: &[BusyBlock]) -> Vec<TimeSlot> {
+    let Some((day_start, day_end)) = window.range() else {
+        return Vec::new();
+    };
+
+    let mut ranges: Vec<(i32, i32)> = busy
+        .iter()
+        .filter_map(|b| Some((to_minutes(&b.start)?, to_minutes(&b.end)?)))
+        .collect();
+    ranges.sort();
+
+    let mut slots = Vec::new();
+    let mut cursor = day_start;
+    for (start, end) in ranges {
+        if start > cursor {
+            slots.push((cursor, start.min(day_end)));
+        }
+        cursor = cursor.max(end);
+        if cursor >= day_end {
+            break;
+        }
+    }
+    slots.push((cursor, day_end));
+
+    slots
+        .into_iter()
+        .filter(|(start, end)| end - start >= MIN_SLOT_MINUTES)
+        .map(|(start, end)| TimeSlot::from_minutes(start, end))
+        .collect()
+}
+
+/// 빈 슬롯에 새 일정을 겹치지 않게 배치
+pub struct SlotAllocator {
+    free: Vec<(i32, i32)>,
+}
+
+impl SlotAllocator {
+    pub fn new(slots: &[TimeSlot]) -> Self {
+        Self {
+            free: slots.iter().filter_map(TimeSlot::range).collect(),
+        }
+    }
+
+    /// 원하는 시작 시간에 들어갈 수 있으면 그 시간을 차지
+    pub fn take_at(&mut self, start: &str, duration: i32) -> Option<String> {
+        let start = to_minutes(start)?;
+        let index = self
+            .free
+            .iter()
+            .position(|(s, e)| *s <= start && start + duration <= *e)?;
+        self.take(index, start, duration);
+        Some(format_minutes(start))
+    }
+
+    /// 들어갈 수 있는 가장 이른 시간을 차지
+    pub fn take_earliest(&mut self, duration: i32) -> Option<String> {
+        let index = self.free.iter().position(|(s, e)| e - s >= duration)?;
+        let start = self.free[index].0;
+        self.take(index, start, duration);
+        Some(format_minutes(start))
+    }
+
+    /// 남은 빈 시간
+    pub fn slots(&self) -> Vec<TimeSlot> {
+        self.free.iter().map(|(start, end)| TimeSlot::from_minutes(*start, *end)).collect()
+    }
+
+    fn take(&mut self, index: usize, start: i32, duration: i32) {
+        let (slot_start, slot_end) = self.free.remove(index);
+        let rest = [(slot_start, start), (start + duration, slot_end)];
+        for (offset, range) in rest.into_iter().filter(|(s, e)| e > s).enumerate() {
+            self.free.insert(index + offset, range);
+        }
+    }
+}
+
+/// 최근 완료 통계
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentStats {
+    /// 태스크가 있었던 날 수
+    pub tracked_days: i32,
+    /// 하루 평균 완료율 (0~1)
+    pub completion_rate: f64,
+    pub completed_per_day: f64,
+    /// 실제 소요시간 / 예상 소요시간 (완료된 태스크 기준)
+    pub duration_ratio: Option<f64>,
+    pub streak: i32,
+}
+
+/// `today` 이전 태스크로 최근 통계 계산
+pub fn recent_stats(tasks: &[Task], today: &str) -> RecentStats {
+    let mut by_date: BTreeMap<&str, Vec<Task>> = BTreeMap::new();
+    for task in tasks.iter().filter(|t| t.scheduled_date.as_str() < today) {
+        by_date.entry(&task.scheduled_date).or_default().push(task.clone());
+    }
+
+    let history: Vec<DailyProgress> = by_date
+        .iter()
+        .map(|(date, tasks)| calculate_daily_progress(date, tasks))
+        .collect();
+    if history.is_empty() {
+        return RecentStats::default();
+    }
+
+    let days = history.len() as f64;
+    let (estimated, actual) = tasks
+        .iter()
+        .filter(|t| t.scheduled_date.as_str() < today && matches!(t.status, TaskStatus::Completed))
+        .filter_map(|t| Some((t.estimated_duration?, t.actual_duration?)))
+        .fold((0, 0), |(e, a), (estimated, actual)| (e + estimated, a + actual));
+
+    RecentStats {
+        tracked_days: history.len() as i32,
+        completion_rate: history.iter().map(|p| p.completion_rate).sum::<f64>() / days,
+        completed_per_day: history.iter().map(|p| p.completed_tasks as f64).sum::<f64>() / days,
+        duration_ratio: (estimated > 0).then(|| actual as f64 / estimated as f64),
+        streak: calculate_streak(&history, today),
+    }
+}
+
+impl DaySchedule {
+    /// prompt용 기존 일정 목록
+    pub fn describe_busy(&self, locale: Locale) -> String {
+        let no_time = match locale {
+            Locale::En => "no time set",
+            Locale::Ko => "시간 미정",
+        };
+        let lines: Vec<String> = self
+            .busy
+            .iter()
+            .map(|b| format!("- {}-{} {}", b.start, b.end, b.title))
+            .chain(self.unscheduled.iter().map(|title| format!("- ({}) {}", no_time, title)))
+            .collect();
+        join_or_none(lines, locale)
+    }
+
+    /// prompt용 빈 슬롯 목록
+    pub fn describe_free_slots(&self, locale: Locale) -> String {
+        let unit = match locale {
+            Locale::En => "min",
+            Locale::Ko => "분",
+        };
+        let lines = self
+            .free_slots
+            .iter()
+            .map(|s| format!("- {}-{} ({}{})", s.start, s.end, s.minutes(), unit))
+            .collect();
+        join_or_none(lines, locale)
+    }
+
+    /// prompt용 코어 타임 목록
+    pub fn describe_focus_windows(&self, locale: Locale) -> String {
+        let lines = self
+            .focus_windows
+            .iter()
+            .map(|f| format!("- {}-{} {}", f.start, f.end, f.name))
+            .collect();
+        join_or_none(lines, locale)
+    }
+}
+
+impl RecentStats {
+    /// prompt용 통계 요약
+    pub fn describe(&self, locale: Locale) -> String {
+        if self.tracked_days == 0 {
+            return locale.none_label().to_string();
+        }
+        let rate = (self.completion_rate * 100.0).round();
+        let mut lines = match locale {
+            Locale::En => vec![
+                format!("- Average completion rate: {}% over {} days", rate, self.tracked_days),
+                format!("- Completed tasks per day: {:.1}", self.completed_per_day),
+                format!("- Current streak: {} days", self.streak),
+            ],
+            Locale::Ko => vec![
+                format!("- 평균 완료율: {}% ({}일 기준)", rate, self.tracked_days),
+                format!("- 하루 평균 완료 태스크: {:.1}개", self.completed_per_day),
+                format!("- 현재 연속 달성: {}일", self.streak),
+            ],
+        };
+        if let Some(ratio) = self.duration_ratio {
+            lines.push(match locale {
+                Locale::En => format!("- Actual time vs. estimate: {:.1}x", ratio),
+                Locale::Ko => format!("- 예상 대비 실제 소요시간: {:.1}배", ratio),
+            });
+        }
+        lines.join("\n")
+    }
+}
+
+fn join_or_none(lines: Vec<String>, locale: Locale) -> String {
+    if lines.is_empty() {
+        locale.none_label().to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recurring::{MonthDayPolicy, RecurrenceType};
+
+    fn task(date: &str, time: Option<&str>, duration: Option<i32>, status: TaskStatus) -> Task {
+        Task {
+            title: format!("task {}", time.unwrap_or("-")),
+            scheduled_time: time.map(String::from),
+            estimated_duration: duration,
+            status,
+            ..Task::test("t", date)
+        }
+    }
+
+    fn window(start: &str, end: &str) -> TimeSlot {
+        TimeSlot {
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_day_schedule() {
+        let date = "2026-03-02"; // 월요일
+        let tasks = vec![
+            task(date, Some("09:00"), Some(60), TaskStatus::Pending),
+            task(date, Some("09:30"), Some(60), TaskStatus::Completed),
+            task(date, Some("15:00"), Some(60), TaskStatus::Skipped),
+            task(date, None, Some(30), TaskStatus::Pending),
+            task("2026-03-03", Some("12:00"), None, TaskStatus::Pending),
+        ];
+        let class = RecurringPlan {
+            id: "r".to_string(),
+            plan_id: None,
+            title: "수업".to_string(),
+            description: None,
+            location: None,
+            recurrence_type: RecurrenceType::Weekly,
+            interval_value: 1,
+            days_of_week: Some(vec![1, 3]),
+            day_of_month: None,
+            scheduled_time: Some("13:00".to_string()),
+            end_time: Some("14:30".to_string()),
+            estimated_duration: None,
+            start_date: "2026-03-01".to_string(),
+            end_date: Some("2026-06-30".to_string()),
+            rrule: None,
+            month_day_policy: MonthDayPolicy::Clamp,
+            exceptions: Vec::new(),
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let core = CoreTime {
+            id: "c".to_string(),
+            name: "Deep work".to_string(),
+            start_time: "10:30".to_string(),
+            end_time: "12:00".to_string(),
+            days_of_week: vec![1, 2, 3, 4, 5],
+            blocked_apps: None,
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+
+        let schedule = build_day_schedule(date, &tasks, &[class], &[core], &window("08:00", "18:00"));
+
+        assert_eq!(schedule.busy.len(), 3);
+        assert_eq!(schedule.busy[2].kind, BusyKind::Recurring);
+        assert_eq!(schedule.unscheduled, vec!["task -"]);
+        assert_eq!(schedule.focus_windows.len(), 1);
+        assert_eq!(
+            schedule.free_slots,
+            vec![window("08:00", "09:00"), window("10:30", "13:00"), window("14:30", "18:00")]
+        );
+        assert!(schedule.describe_busy(Locale::En).contains("- 13:00-14:30 수업"));
+    }
+
+    #[test]
+    fn test_slot_allocator_avoids_overlap() {
+        let mut slots = SlotAllocator::new(&[window("08:00", "09:00"), window("10:30", "13:00")]);
+
+        assert_eq!(slots.take_at("11:00", 60), Some("11:00".to_string()));
+        // 이미 차지한 시간, 슬롯 밖의 시간은 거부
+        assert_eq!(slots.take_at("11:30", 30), None);
+        assert_eq!(slots.take_at("09:00", 30), None);
+        assert_eq!(slots.take_earliest(45), Some("08:00".to_string()));
+        assert_eq!(slots.take_earliest(30), Some("10:30".to_string()));
+        assert_eq!(slots.take_earliest(60), Some("12:00".to_string()));
+        assert_eq!(slots.take_earliest(15), Some("08:45".to_string()));
+        assert_eq!(slots.take_earliest(15), None);
+    }
+
+    #[test]
+    fn test_recent_stats() {
+        let mut done = task("2026-03-01", None, Some(30), TaskStatus::Completed);
+        done.actual_duration = Some(45);
+        let tasks = vec![
+            done,
+            task("2026-03-01", None, None, TaskStatus::Pending),
+            task("2026-02-28", None, None, TaskStatus::Completed),
+            task("2026-03-02", None, None, TaskStatus::Pending),
+        ];
+
+        let stats = recent_stats(&tasks, "2026-03-02");
+        assert_eq!(stats.tracked_days, 2);
+        assert!((stats.completion_rate - 0.75).abs() < 1e-9);
+        assert_eq!(stats.duration_ratio, Some(1.5));
+        assert_eq!(stats.streak, 2);
+        assert!(stats.describe(Locale::Ko).contains("평균 완료율: 75%"));
+        assert_eq!(recent_stats(&[], "2026-03-02").describe(Locale::En), "None");
+    }
+}
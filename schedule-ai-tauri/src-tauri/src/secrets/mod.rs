@@ -0,0 +1,541 @@
+//! API 키 같은 비밀값 저장
+//!
+//! OS 키체인(macOS Keychain, Linux Secret Service)을 먼저 쓰고, 쓸 수 없으면 앱 데이터 폴더의
+//! 암호화 파일(`secrets.enc.json`)에 저장합니다. 파일은 사용자가 `SCHEDULE_AI_SECRETS_PASSPHRASE`로
+//! 지정한 passphrase에서 Argon2id로 만든 키와 AES-256-GCM으로 항목마다 암호화합니다.
+//! passphrase가 없으면 저장소는 잠긴 상태로 시작하고, 사용자가 설정 화면에서 passphrase를 입력하면
+//! (`Secrets::unlock`) 열립니다.
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use zeroize::Zeroizing;
+
+/// 키체인 항목의 service 이름
+pub const SERVICE: &str = "com.scheduleai.app";
+/// Claude API 키 항목 이름
+pub const CLAUDE_API_KEY: &str = "claude_api_key";
+/// OpenAI 호환 provider API 키 항목 이름 (`llm_provider.apiKey`)
+pub const PROVIDER_API_KEY: &str = "llm_provider_api_key";
+/// 암호화 파일 이름 (앱 데이터 폴더)
+pub const SECRETS_FILE: &str = "secrets.enc.json";
+/// 암호화 파일의 passphrase를 지정하는 환경 변수 (headless 실행, 테스트용. 없으면 사용자가 입력)
+pub const PASSPHRASE_ENV: &str = "SCHEDULE_AI_SECRETS_PASSPHRASE";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretError {
+    #[error("Keyring error: {0}")]
+    Keyring(String),
+    #[error("Secret file error: {0}")]
+    File(String),
+    #[error("Failed to decrypt secret (wrong passphrase or corrupted file)")]
+    Decrypt,
+    #[error("Secret store is locked: enter the passphrase in Settings (or set {0})")]
+    Locked(&'static str),
+}
+
+/// 비밀값 저장소
+pub trait SecretStore: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn get(&self, key: &str) -> Result<Option<String>, SecretError>;
+    fn set(&self, key: &str, value: &str) -> Result<(), SecretError>;
+    fn delete(&self, key: &str) -> Result<(), SecretError>;
+}
+
+/// 화면에 보여줄 가린 값 ("sk-ant-...abcd")
+pub fn mask(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() < 16 {
+        return "*".repeat(chars.len().min(8));
+    }
+    let prefix: String = chars[..7].iter().collect();
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}...{}", prefix, suffix)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedEntry {
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SecretFile {
+    version: u32,
+    /// Argon2id salt (base64)
+    salt: String,
+    entries: BTreeMap<String, EncryptedEntry>,
+}
+
+/// passphrase로 암호화한 파일 저장소
+pub struct EncryptedFile {
+    path: PathBuf,
+    passphrase: Zeroizing<String>,
+    lock: Mutex<()>,
+}
+
+impl EncryptedFile {
+    pub fn new(path: PathBuf, passphrase: Zeroizing<String>) -> Self {
+        Self {
+            path,
+            passphrase,
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn read(&self) -> Result<SecretFile, SecretError> {
+        if !self.path.exists() {
+            let mut salt = [0u8; 16];
+            OsRng.fill_bytes(&mut salt);
+            return Ok(SecretFile {
+                version: 1,
+                salt: BASE64.encode(salt),
+                entries: BTreeMap::new(),
+            });
+        }
+        let text = std::fs::read_to_string(&self.path).map_err(|e| SecretError::File(e.to_string()))?;
+        serde_json::from_str(&text).map_err(|e| SecretError::File(e.to_string()))
+    }
+
+    /// 임시 파일에 쓰고 교체 (Unix에서는 소유자만 읽기/쓰기)
+    fn write(&self, file: &SecretFile) -> Result<(), SecretError> {
+        let error = |e: std::io::Error| SecretError::File(e.to_string());
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(error)?;
+        }
+        let text = serde_json::to_string_pretty(file).map_err(|e| SecretError::File(e.to_string()))?;
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, text).map_err(error)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600)).map_err(error)?;
+        }
+        std::fs::rename(&tmp, &self.path).map_err(error)
+    }
+
+    /// 저장된 항목 하나를 복호화해 passphrase 확인 (항목이 없으면 어떤 passphrase든 통과)
+    pub fn verify(&self) -> Result<(), SecretError> {
+        let first = {
+            let _guard = self.lock.lock().unwrap();
+            self.read()?.entries.keys().next().cloned()
+        };
+        match first {
+            Some(key) => self.get(&key).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    fn cipher(&self, salt: &str) -> Result<Aes256Gcm, SecretError> {
+        let salt = BASE64.decode(salt).map_err(|_| SecretError::Decrypt)?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        argon2::Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), &salt, key.as_mut())
+            .map_err(|e| SecretError::File(e.to_string()))?;
+        Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_ref())))
+    }
+}
+
+impl SecretStore for EncryptedFile {
+    fn name(&self) -> &'static str {
+        "encrypted-file"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, SecretError> {
+        let _guard = self.lock.lock().unwrap();
+        let file = self.read()?;
+        let Some(entry) = file.entries.get(key) else {
+            return Ok(None);
+        };
+        let nonce = BASE64.decode(&entry.nonce).map_err(|_| SecretError::Decrypt)?;
+        let ciphertext = BASE64.decode(&entry.ciphertext).map_err(|_| SecretError::Decrypt)?;
+        if nonce.len() != 12 {
+            return Err(SecretError::Decrypt);
+        }
+        // 항목 이름을 AAD로 묶어 다른 항목으로 바꿔치기할 수 없게 함
+        let plain = Zeroizing::new(
+            self.cipher(&file.salt)?
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &ciphertext,
+                        aad: key.as_bytes(),
+                    },
+                )
+                .map_err(|_| SecretError::Decrypt)?,
+        );
+        String::from_utf8(plain.to_vec()).map(Some).map_err(|_| SecretError::Decrypt)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), SecretError> {
+        let _guard = self.lock.lock().unwrap();
+        let mut file = self.read()?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher(&file.salt)?
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: value.as_bytes(),
+                    aad: key.as_bytes(),
+                },
+            )
+            .map_err(|_| SecretError::File("encryption failed".to_string()))?;
+        file.entries.insert(
+            key.to_string(),
+            EncryptedEntry {
+                nonce: BASE64.encode(nonce),
+                ciphertext: BASE64.encode(ciphertext),
+            },
+        );
+        self.write(&file)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), SecretError> {
+        let _guard = self.lock.lock().unwrap();
+        if !self.path.exists() {
+            return Ok(());
+        }
+        let mut file = self.read()?;
+        if file.entries.remove(key).is_some() {
+            self.write(&file)?;
+        }
+        Ok(())
+    }
+}
+
+/// 환경 변수로 지정한 암호화 파일의 passphrase
+///
+/// 기기 정보처럼 같은 기기에서 누구나 알아낼 수 있는 값으로는 만들지 않습니다.
+pub fn env_passphrase() -> Option<Zeroizing<String>> {
+    std::env::var(PASSPHRASE_ENV)
+        .ok()
+        .filter(|passphrase| !passphrase.is_empty())
+        .map(Zeroizing::new)
+}
+
+/// passphrase를 받기 전의 암호화 파일. 모든 작업을 `Locked`로 거부합니다.
+struct Locked;
+
+impl SecretStore for Locked {
+    fn name(&self) -> &'static str {
+        "locked"
+    }
+
+    fn get(&self, _key: &str) -> Result<Option<String>, SecretError> {
+        Err(SecretError::Locked(PASSPHRASE_ENV))
+    }
+
+    fn set(&self, _key: &str, _value: &str) -> Result<(), SecretError> {
+        Err(SecretError::Locked(PASSPHRASE_ENV))
+    }
+
+    fn delete(&self, _key: &str) -> Result<(), SecretError> {
+        Err(SecretError::Locked(PASSPHRASE_ENV))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod keychain {
+    use super::{SecretError, SecretStore, SERVICE};
+    use security_framework::passwords::{delete_generic_password, get_generic_password, set_generic_password};
+
+    const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+    /// macOS Keychain
+    pub struct Keychain;
+
+    impl SecretStore for Keychain {
+        fn name(&self) -> &'static str {
+            "keychain"
+        }
+
+        fn get(&self, key: &str) -> Result<Option<String>, SecretError> {
+            match get_generic_password(SERVICE, key) {
+                Ok(bytes) => String::from_utf8(bytes).map(Some).map_err(|_| SecretError::Decrypt),
+                Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+                Err(e) => Err(SecretError::Keyring(e.to_string())),
+            }
+        }
+
+        fn set(&self, key: &str, value: &str) -> Result<(), SecretError> {
+            set_generic_password(SERVICE, key, value.as_bytes()).map_err(|e| SecretError::Keyring(e.to_string()))
+        }
+
+        fn delete(&self, key: &str) -> Result<(), SecretError> {
+            match delete_generic_password(SERVICE, key) {
+                Err(e) if e.code() != ERR_SEC_ITEM_NOT_FOUND => Err(SecretError::Keyring(e.to_string())),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    pub fn open() -> Option<Box<dyn SecretStore>> {
+        Some(Box::new(Keychain))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod keychain {
+    use super::{SecretError, SecretStore, SERVICE};
+    use secret_service::blocking::SecretService;
+    use secret_service::EncryptionType;
+    use std::collections::HashMap;
+
+    /// Secret Service (GNOME Keyring, KWallet 등)
+    pub struct SecretServiceStore;
+
+    /// D-Bus 호출은 자체 runtime을 쓰므로 tokio 작업 밖의 스레드에서 실행
+    fn on_thread<T: Send>(
+        f: impl FnOnce(&SecretService) -> Result<T, secret_service::Error> + Send,
+    ) -> Result<T, SecretError> {
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let service = SecretService::connect(EncryptionType::Dh)?;
+                    f(&service)
+                })
+                .join()
+        })
+        .map_err(|_| SecretError::Keyring("keyring thread panicked".to_string()))?
+        .map_err(|e| SecretError::Keyring(e.to_string()))
+    }
+
+    fn attributes(key: &str) -> HashMap<&str, &str> {
+        HashMap::from([("service", SERVICE), ("account", key)])
+    }
+
+    impl SecretStore for SecretServiceStore {
+        fn name(&self) -> &'static str {
+            "secret-service"
+        }
+
+        fn get(&self, key: &str) -> Result<Option<String>, SecretError> {
+            let secret = on_thread(|service| {
+                let found = service.search_items(attributes(key))?;
+                let Some(item) = found.unlocked.into_iter().chain(found.locked).next() else {
+                    return Ok(None);
+                };
+                item.ensure_unlocked()?;
+                item.get_secret().map(Some)
+            })?;
+            secret
+                .map(|bytes| String::from_utf8(bytes).map_err(|_| SecretError::Decrypt))
+                .transpose()
+        }
+
+        fn set(&self, key: &str, value: &str) -> Result<(), SecretError> {
+            on_thread(|service| {
+                let collection = service.get_default_collection()?;
+                collection.ensure_unlocked()?;
+                let label = format!("Schedule AI ({})", key);
+                collection.create_item(&label, attributes(key), value.as_bytes(), true, "text/plain")?;
+                Ok(())
+            })
+        }
+
+        fn delete(&self, key: &str) -> Result<(), SecretError> {
+            on_thread(|service| {
+                let found = service.search_items(attributes(key))?;
+                for item in found.unlocked.into_iter().chain(found.locked) {
+                    item.delete()?;
+                }
+                Ok(())
+            })
+        }
+    }
+
+    /// Secret Service에 연결할 수 있으면 사용
+    pub fn open() -> Option<Box<dyn SecretStore>> {
+        match on_thread(|service| service.get_default_collection().map(|_| ())) {
+            Ok(()) => Some(Box::new(SecretServiceStore)),
+            Err(e) => {
+                eprintln!("Secret Service unavailable, using encrypted file: {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+mod keychain {
+    use super::SecretStore;
+
+    pub fn open() -> Option<Box<dyn SecretStore>> {
+        None
+    }
+}
+
+/// 앱의 비밀값 저장소 (OS 키체인 또는 암호화 파일)
+pub struct Secrets {
+    store: RwLock<Box<dyn SecretStore>>,
+    /// 키체인이 없을 때 쓰는 암호화 파일 경로
+    file: PathBuf,
+}
+
+impl Secrets {
+    pub fn new(store: Box<dyn SecretStore>, file: PathBuf) -> Self {
+        Self {
+            store: RwLock::new(store),
+            file,
+        }
+    }
+
+    /// OS 키체인을 쓸 수 없으면 `data_dir`의 암호화 파일 사용
+    ///
+    /// 환경 변수로 passphrase를 지정하지 않았으면 `unlock`할 때까지 잠겨 있습니다.
+    pub fn open(data_dir: &Path) -> Self {
+        let file = data_dir.join(SECRETS_FILE);
+        let store = keychain::open().unwrap_or_else(|| match env_passphrase() {
+            Some(passphrase) => Box::new(EncryptedFile::new(file.clone(), passphrase)),
+            None => Box::new(Locked),
+        });
+        Self::new(store, file)
+    }
+
+    pub fn backend(&self) -> &'static str {
+        self.store.read().unwrap().name()
+    }
+
+    /// passphrase 입력을 기다리는 중
+    pub fn is_locked(&self) -> bool {
+        self.backend() == Locked.name()
+    }
+
+    /// 암호화 파일이 이미 있음 (없으면 처음 입력한 passphrase로 새로 만듦)
+    pub fn has_file(&self) -> bool {
+        self.file.exists()
+    }
+
+    /// 사용자가 입력한 passphrase로 암호화 파일을 엶. 기존 파일과 맞지 않으면 `Decrypt`
+    ///
+    /// Argon2id 키 유도가 느리므로 blocking 스레드에서 호출해야 합니다.
+    pub fn unlock(&self, passphrase: Zeroizing<String>) -> Result<(), SecretError> {
+        if !self.is_locked() {
+            return Ok(());
+        }
+        let file = EncryptedFile::new(self.file.clone(), passphrase);
+        file.verify()?;
+        *self.store.write().unwrap() = Box::new(file);
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<String>, SecretError> {
+        self.store.read().unwrap().get(key)
+    }
+
+    pub fn set(&self, key: &str, value: &str) -> Result<(), SecretError> {
+        self.store.read().unwrap().set(key, value)
+    }
+
+    pub fn delete(&self, key: &str) -> Result<(), SecretError> {
+        self.store.read().unwrap().delete(key)
+    }
+
+    /// 평문으로 저장돼 있던 값을 옮김. 이미 저장소에 값이 있으면 그 값을 유지합니다.
+    ///
+    /// `Ok`가 나온 뒤에만 원래 평문을 지워야 합니다.
+    pub fn migrate(&self, key: &str, plain: &str) -> Result<(), SecretError> {
+        if !plain.is_empty() && self.get(key)?.is_none() {
+            self.set(key, plain)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(dir: &Path, passphrase: &str) -> EncryptedFile {
+        EncryptedFile::new(dir.join(SECRETS_FILE), Zeroizing::new(passphrase.to_string()))
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("sk-ant-REDACTED"), "sk-ant-...wxyz");
+        assert_eq!(mask("short"), "*****");
+        assert_eq!(mask(""), "");
+    }
+
+    #[test]
+    fn test_encrypted_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = file(dir.path(), "correct horse");
+        assert_eq!(store.get(CLAUDE_API_KEY).unwrap(), None);
+
+        store.set(CLAUDE_API_KEY, "sk-ant-secret-value").unwrap();
+        store.set("other", "x").unwrap();
+        assert_eq!(store.get(CLAUDE_API_KEY).unwrap().as_deref(), Some("sk-ant-secret-value"));
+
+        // 평문이 파일에 남지 않음
+        let text = std::fs::read_to_string(dir.path().join(SECRETS_FILE)).unwrap();
+        assert!(!text.contains("sk-ant-secret-value"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dir.path().join(SECRETS_FILE)).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let wrong = file(dir.path(), "wrong");
+        assert!(matches!(wrong.get(CLAUDE_API_KEY), Err(SecretError::Decrypt)));
+
+        // 항목끼리 암호문을 바꾸면 복호화 실패
+        let mut swapped: SecretFile = serde_json::from_str(&text).unwrap();
+        let other = swapped.entries.remove("other").unwrap();
+        swapped.entries.insert(CLAUDE_API_KEY.to_string(), other);
+        store.write(&swapped).unwrap();
+        assert!(matches!(store.get(CLAUDE_API_KEY), Err(SecretError::Decrypt)));
+
+        store.delete(CLAUDE_API_KEY).unwrap();
+        assert_eq!(store.get(CLAUDE_API_KEY).unwrap(), None);
+    }
+
+    #[test]
+    fn test_migrate_keeps_existing_secret() {
+        let dir = tempfile::tempdir().unwrap();
+        let secrets = Secrets::new(Box::new(file(dir.path(), "pass")), dir.path().join(SECRETS_FILE));
+
+        secrets.migrate(CLAUDE_API_KEY, "sk-ant-old").unwrap();
+        assert_eq!(secrets.get(CLAUDE_API_KEY).unwrap().as_deref(), Some("sk-ant-old"));
+        secrets.set(CLAUDE_API_KEY, "sk-ant-new").unwrap();
+        secrets.migrate(CLAUDE_API_KEY, "sk-ant-old").unwrap();
+        assert_eq!(secrets.get(CLAUDE_API_KEY).unwrap().as_deref(), Some("sk-ant-new"));
+    }
+
+    #[test]
+    fn test_locked_store_unlocks_with_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SECRETS_FILE);
+        let secrets = Secrets::new(Box::new(Locked), path.clone());
+        assert!(secrets.is_locked());
+        assert!(!secrets.has_file());
+
+        assert!(matches!(secrets.set(CLAUDE_API_KEY, "sk-ant-key"), Err(SecretError::Locked(_))));
+        // 옮기지 못했으므로 평문을 지우면 안 됨
+        assert!(secrets.migrate(CLAUDE_API_KEY, "sk-ant-key").is_err());
+
+        // 처음 입력한 passphrase로 파일을 만듦
+        secrets.unlock(Zeroizing::new("correct horse".to_string())).unwrap();
+        assert_eq!(secrets.backend(), "encrypted-file");
+        secrets.set(CLAUDE_API_KEY, "sk-ant-key").unwrap();
+
+        // 다음 실행: 틀린 passphrase는 거부하고 잠긴 상태 유지
+        let reopened = Secrets::new(Box::new(Locked), path);
+        assert!(reopened.has_file());
+        assert!(matches!(
+            reopened.unlock(Zeroizing::new("wrong".to_string())),
+            Err(SecretError::Decrypt)
+        ));
+        assert!(reopened.is_locked());
+        reopened.unlock(Zeroizing::new("correct horse".to_string())).unwrap();
+        assert_eq!(reopened.get(CLAUDE_API_KEY).unwrap().as_deref(), Some("sk-ant-key"));
+    }
+}
@@ -16,13 +16,14 @@ import {
   getTotalBlockCount,
 } from './db';
 import type { DailyProgress as DBDailyProgress, BlockStat, DailyBlockStat } from './db';
-import type { Task, SubTask, Plan, RecurringPlan, ParsedRecurrencePattern, RecurrenceType } from '@schedule-ai/core';
+import type { Task, SubTask, Plan, RecurringPlan, ParsedRecurrencePattern, RecurrenceType, LLMErrorInfo } from '@schedule-ai/core';
 import {
   createRecurringPlan,
   getRecurringPlans,
   deleteRecurringPlan,
-  generateTasksFromRecurringPlan,
+  materializeRecurringTasks,
 } from './db';
+import { createRequestId, invokeStreaming, cancelLlmRequest } from './llm/stream';
 import './App.css';
 
 type Tab = 'today' | 'plans' | 'progress' | 'focus' | 'settings';
@@ -36,6 +37,27 @@ function formatDuration(minutes: number): string {
   return `${hours}h ${mins}m`;
 }
 
+// AI 커맨드 에러를 { kind, message, retryAfter } 형태로 정리
+function toLLMError(error: unknown): LLMErrorInfo {
+  if (typeof error === 'object' && error !== null && 'kind' in error && 'message' in error) {
+    return error as LLMErrorInfo;
+  }
+  return { kind: 'api', message: error instanceof Error ? error.message : String(error) };
+}
+
+// AI 응답이 오는 동안 받은 내용과 취소 버튼
+function AiStreamPreview({ text, onCancel }: { text: string; onCancel: () => void }) {
+  const { t } = useTranslation();
+  return (
+    <div className="ai-stream-preview">
+      <pre className="ai-stream-text">{text || t('common:ai.waiting')}</pre>
+      <button type="button" className="ai-stream-cancel" onClick={onCancel}>
+        {t('common:buttons.cancel')}
+      </button>
+    </div>
+  );
+}
+
 // Progress types
 interface DailyProgress {
   date: string;
@@ -463,12 +485,20 @@ ${dailyText}
       setAiInsight(response);
     } catch (error) {
       console.error('Failed to generate AI insight:', error);
-      const errorMsg = error instanceof Error ? error.message : String(error);
+      const llmError = toLLMError(error);
+      let insight = t('stats.aiError');
+      if (llmError.kind === 'rateLimited') {
+        insight = llmError.retryAfter
+          ? t('common:llmError.rateLimitedRetry', { seconds: llmError.retryAfter })
+          : t('common:llmError.rateLimited');
+      } else if (llmError.kind === 'auth') {
+        insight = t('common:llmError.auth');
+      }
       // 에러 시 기본 구조로 표시
       setAiInsight({
-        insight: t('stats.aiError'),
+        insight,
         advice: [],
-        encouragement: `Error: ${errorMsg}`,
+        encouragement: `Error: ${llmError.message}`,
       });
     } finally {
       setIsGeneratingInsight(false);
@@ -1222,6 +1252,7 @@ function App() {
   const [inputMode, setInputMode] = useState<'manual' | 'ai'>('manual');
   const [aiInputShortcut, setAiInputShortcut] = useState('shift+tab');
   const [isParsingTask, setIsParsingTask] = useState(false);
+  const [taskStream, setTaskStream] = useState<{ requestId: string; text: string } | null>(null);
   const [recordingAiShortcut, setRecordingAiShortcut] = useState(false);
   const [recordedAiShortcutKey, setRecordedAiShortcutKey] = useState('');
 
@@ -1242,11 +1273,17 @@ function App() {
   const [apiKeyInput, setApiKeyInput] = useState('');
   const [isApiKeyVisible, setIsApiKeyVisible] = useState(false);
   const [apiKeyStatus, setApiKeyStatus] = useState<'none' | 'saved' | 'validating' | 'valid' | 'invalid'>('none');
+  // OS 키체인이 없으면 암호화 파일의 passphrase를 입력받음
+  const [secretsStatus, setSecretsStatus] = useState<{ backend: string; locked: boolean; hasFile: boolean } | null>(null);
+  const [passphraseInput, setPassphraseInput] = useState('');
+  const [passphraseError, setPassphraseError] = useState('');
+  const [isUnlockingSecrets, setIsUnlockingSecrets] = useState(false);
 
   // AI generation state
   const [isGenerating, setIsGenerating] = useState(false);
   const [, setAiSplitSuggestions] = useState<string[]>([]);
   const [isParsingPlan, setIsParsingPlan] = useState(false);
+  const [planStream, setPlanStream] = useState<{ requestId: string; text: string } | null>(null);
   const [isGeneratingTasks, setIsGeneratingTasks] = useState(false);
 
   // Plan rules state
@@ -1287,14 +1324,16 @@ function App() {
     loadRecurringPlans();
     // Load current shortcut
     invoke<string>('get_current_shortcut').then(setCurrentShortcut).catch(console.error);
-    // Load API key
+    // Load API key (masked preview only)
     invoke<string>('get_api_key').then((key) => {
       if (key) {
         setApiKey(key);
-        setApiKeyInput(key);
         setApiKeyStatus('saved');
       }
     }).catch(console.error);
+    invoke<{ backend: string; locked: boolean; hasFile: boolean }>('get_secrets_status')
+      .then(setSecretsStatus)
+      .catch(console.error);
     // Load plan rules
     invoke<string>('get_plan_rules').then((rules) => {
       if (rules) {
@@ -1768,12 +1807,16 @@ function App() {
     if (!newTaskTitle.trim() || isParsingTask) return;
 
     setIsParsingTask(true);
+    const requestId = createRequestId();
+    setTaskStream({ requestId, text: '' });
     try {
       const today = formatDate(new Date());
-      const parsed = await invoke<ParseTaskResponse>('parse_task_with_ai', {
-        input: newTaskTitle,
-        currentDate: today,
-      });
+      const parsed = await invokeStreaming<ParseTaskResponse>(
+        'parse_task_with_ai',
+        { input: newTaskTitle, currentDate: today },
+        requestId,
+        (delta) => setTaskStream((s) => (s && s.requestId === requestId ? { ...s, text: s.text + delta } : s))
+      );
 
       console.log('AI parsed task:', parsed);
 
@@ -1800,6 +1843,10 @@ function App() {
       setInputMode('manual'); // 성공 후 수동 모드로 전환
     } catch (error) {
       console.error('Failed to parse/create task with AI:', error);
+      const { kind } = toLLMError(error);
+      if (kind === 'auth') setApiKeyStatus('invalid');
+      // 사용자가 취소하면 입력을 그대로 둠
+      if (kind === 'cancelled') return;
       // AI 파싱 실패 시 일반 태스크로 폴백
       try {
         await createTask({
@@ -1812,6 +1859,7 @@ function App() {
       }
     } finally {
       setIsParsingTask(false);
+      setTaskStream(null);
     }
   };
 
@@ -1935,7 +1983,8 @@ function App() {
       // Validate by making a simple call
       const isValid = await invoke<boolean>('validate_api_key');
       if (isValid) {
-        setApiKey(apiKeyInput.trim());
+        setApiKey(await invoke<string>('get_api_key'));
+        setApiKeyInput('');
         setApiKeyStatus('valid');
         setTimeout(() => setApiKeyStatus('saved'), 2000);
       } else {
@@ -1947,6 +1996,26 @@ function App() {
     }
   };
 
+  const handleUnlockSecrets = async () => {
+    if (!passphraseInput) return;
+    setIsUnlockingSecrets(true);
+    setPassphraseError('');
+    try {
+      setSecretsStatus(await invoke<{ backend: string; locked: boolean; hasFile: boolean }>('unlock_secrets', {
+        passphrase: passphraseInput,
+      }));
+      setPassphraseInput('');
+      const key = await invoke<string>('get_api_key');
+      setApiKey(key);
+      setApiKeyStatus(key ? 'saved' : 'none');
+    } catch (error) {
+      console.error('Failed to unlock secrets:', error);
+      setPassphraseError(secretsStatus?.hasFile ? t('settings:secrets.wrongPassphrase') : String(error));
+    } finally {
+      setIsUnlockingSecrets(false);
+    }
+  };
+
   const handleDeleteApiKey = async () => {
     try {
       await invoke('delete_api_key');
@@ -1982,6 +2051,7 @@ function App() {
       setSplitSubtasks([...suggestions, '']);
     } catch (error) {
       console.error('Failed to generate split suggestions:', error);
+      if (toLLMError(error).kind === 'auth') setApiKeyStatus('invalid');
     } finally {
       setIsGenerating(false);
     }
@@ -1990,14 +2060,21 @@ function App() {
   const handleAiParsePlan = async () => {
     if (!newPlanInput.trim() || !apiKey) return;
     setIsParsingPlan(true);
+    const requestId = createRequestId();
+    setPlanStream({ requestId, text: '' });
     try {
-      const response = await invoke<{
+      const response = await invokeStreaming<{
         parsed_content: {
           goals: string[];
           milestones: unknown[];
           suggested_tasks: { title: string; estimated_duration: number; priority: number }[];
         };
-      }>('parse_plan_with_ai', { planInput: newPlanInput, planRules: planRules || null });
+      }>(
+        'parse_plan_with_ai',
+        { planInput: newPlanInput, planRules: planRules || null },
+        requestId,
+        (delta) => setPlanStream((s) => (s && s.requestId === requestId ? { ...s, text: s.text + delta } : s))
+      );
 
       // Create the plan with parsed content
       await createPlan({
@@ -2008,6 +2085,9 @@ function App() {
       setNewPlanInput('');
     } catch (error) {
       console.error('Failed to parse plan with AI:', error);
+      const { kind } = toLLMError(error);
+      if (kind === 'auth') setApiKeyStatus('invalid');
+      if (kind === 'cancelled') return;
       // Fallback to regular plan creation
       await createPlan({
         title: newPlanInput.split('\n')[0] || 'New Plan',
@@ -2016,6 +2096,7 @@ function App() {
       setNewPlanInput('');
     } finally {
       setIsParsingPlan(false);
+      setPlanStream(null);
     }
   };
 
@@ -2048,6 +2129,7 @@ function App() {
       setActiveTab('today');
     } catch (error) {
       console.error('Failed to generate daily tasks:', error);
+      if (toLLMError(error).kind === 'auth') setApiKeyStatus('invalid');
     } finally {
       setIsGeneratingTasks(false);
     }
@@ -2091,6 +2173,7 @@ function App() {
       }
     } catch (error) {
       console.error('Failed to parse recurrence pattern:', error);
+      if (toLLMError(error).kind === 'auth') setApiKeyStatus('invalid');
       // LLM 실패 시 규칙 기반으로 fallback
       try {
         const pattern = await invoke<ParsedRecurrencePattern | null>('parse_recurrence_pattern', {
@@ -2130,6 +2213,7 @@ function App() {
       estimatedDuration: pattern.estimatedDuration,
       startDate: pattern.startDate || startDate || formatDate(new Date()),
       endDate: pattern.endDate || endDate,
+      monthDayPolicy: 'clamp',
       isActive: true,
       createdAt: '',
       updatedAt: '',
@@ -2170,6 +2254,7 @@ function App() {
       estimatedDuration: scheduledTime && endTime ? calculateDuration(scheduledTime, endTime) : undefined,
       startDate,
       endDate: endDate || undefined,
+      monthDayPolicy: 'clamp',
       isActive: true,
       createdAt: '',
       updatedAt: '',
@@ -2202,7 +2287,7 @@ function App() {
 
     try {
       // Create the recurring plan
-      const newRecurringPlan = await createRecurringPlan({
+      await createRecurringPlan({
         title: recurringTitle,
         location: recurringLocation || undefined,
         recurrenceType,
@@ -2215,16 +2300,8 @@ function App() {
         endDate: endDate || undefined,
       });
 
-      // Generate tasks from the recurring plan
-      const tasksToCreate = taskPreview.map(t => ({
-        scheduledDate: t.scheduledDate,
-        scheduledTime: scheduledTime || undefined,
-        title: recurringTitle,
-        location: recurringLocation || undefined,
-        estimatedDuration: scheduledTime && endTime ? calculateDuration(scheduledTime, endTime) : undefined,
-      }));
-
-      await generateTasksFromRecurringPlan(newRecurringPlan, tasksToCreate);
+      // Generate tasks for the rolling window (the backend tops it up daily)
+      await materializeRecurringTasks();
 
       // Reset form
       setShowRecurringForm(false);
@@ -2670,6 +2747,9 @@ function App() {
                 {isParsingTask ? t('common:status.analyzing') : (inputMode === 'ai' ? t('today:addTask.aiButton') : t('today:addTask.button'))}
               </button>
             </form>
+            {taskStream && (
+              <AiStreamPreview text={taskStream.text} onCancel={() => cancelLlmRequest(taskStream.requestId)} />
+            )}
           </div>
         )}
 
@@ -2926,6 +3006,9 @@ function App() {
                   )}
               </div>
             </form>
+            {planStream && (
+              <AiStreamPreview text={planStream.text} onCancel={() => cancelLlmRequest(planStream.requestId)} />
+            )}
             </div>
 
             {/* Plans List */}
@@ -3147,12 +3230,42 @@ function App() {
                 </a>
               </p>
 
+              {secretsStatus?.locked && (
+                <div className="api-key-setting">
+                  <p className="settings-description">
+                    {secretsStatus.hasFile ? t('settings:secrets.unlockDescription') : t('settings:secrets.createDescription')}
+                  </p>
+                  <div className="api-key-input-wrapper">
+                    <input
+                      type="password"
+                      className="api-key-input"
+                      placeholder={t('settings:secrets.placeholder')}
+                      value={passphraseInput}
+                      onChange={(e) => setPassphraseInput(e.target.value)}
+                      onKeyDown={(e) => e.key === 'Enter' && handleUnlockSecrets()}
+                    />
+                  </div>
+                  <div className="api-key-actions">
+                    <button
+                      className="api-key-save"
+                      onClick={handleUnlockSecrets}
+                      disabled={!passphraseInput || isUnlockingSecrets}
+                    >
+                      {isUnlockingSecrets
+                        ? t('common:status.checking')
+                        : secretsStatus.hasFile ? t('settings:secrets.unlock') : t('settings:secrets.create')}
+                    </button>
+                  </div>
+                  {passphraseError && <div className="api-key-status status-invalid">{passphraseError}</div>}
+                </div>
+              )}
+
               <div className="api-key-setting">
                 <div className="api-key-input-wrapper">
                   <input
                     type={isApiKeyVisible ? 'text' : 'password'}
                     className="api-key-input"
-                    placeholder={t('settings:apiKey.placeholder')}
+                    placeholder={apiKey || t('settings:apiKey.placeholder')}
                     value={apiKeyInput}
                     onChange={(e) => setApiKeyInput(e.target.value)}
                     onKeyDown={(e) => e.key === 'Enter' && handleSaveApiKey()}
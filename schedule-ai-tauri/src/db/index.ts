@@ -1,5 +1,6 @@
 // Database wrapper using tauri-plugin-sql
 import Database from '@tauri-apps/plugin-sql';
+import { invoke } from '@tauri-apps/api/core';
 import type {
   Plan,
   Task,
@@ -14,6 +15,7 @@ import type {
   CreateRecurringPlanInput,
   UpdateRecurringPlanInput,
   RecurrenceType,
+  MonthDayPolicy,
 } from '@schedule-ai/core';
 import { generateId, formatDateTime, formatDate } from '@schedule-ai/core';
 
@@ -768,6 +770,8 @@ interface RecurringPlanRow {
   estimated_duration: number | null;
   start_date: string;
   end_date: string | null;
+  rrule: string | null;
+  month_day_policy: string;
   is_active: number;
   created_at: string;
   updated_at: string;
@@ -789,6 +793,8 @@ function rowToRecurringPlan(row: RecurringPlanRow): RecurringPlan {
     estimatedDuration: row.estimated_duration ?? undefined,
     startDate: row.start_date,
     endDate: row.end_date ?? undefined,
+    rrule: row.rrule ?? undefined,
+    monthDayPolicy: row.month_day_policy as MonthDayPolicy,
     isActive: row.is_active === 1,
     createdAt: row.created_at,
     updatedAt: row.updated_at,
@@ -804,8 +810,8 @@ export async function createRecurringPlan(input: CreateRecurringPlanInput): Prom
     `INSERT INTO recurring_plans (
       id, plan_id, title, description, location, recurrence_type, interval_value,
       days_of_week, day_of_month, scheduled_time, end_time,
-      estimated_duration, start_date, end_date, is_active, created_at, updated_at
-    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, 1, $15, $16)`,
+      estimated_duration, start_date, end_date, rrule, month_day_policy, is_active, created_at, updated_at
+    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, 1, $17, $18)`,
     [
       id,
       input.planId ?? null,
@@ -821,6 +827,8 @@ export async function createRecurringPlan(input: CreateRecurringPlanInput): Prom
       input.estimatedDuration ?? null,
       input.startDate,
       input.endDate ?? null,
+      input.rrule ?? null,
+      input.monthDayPolicy ?? 'clamp',
       now,
       now,
     ]
@@ -841,6 +849,8 @@ export async function createRecurringPlan(input: CreateRecurringPlanInput): Prom
     estimatedDuration: input.estimatedDuration,
     startDate: input.startDate,
     endDate: input.endDate,
+    rrule: input.rrule,
+    monthDayPolicy: input.monthDayPolicy ?? 'clamp',
     isActive: true,
     createdAt: now,
     updatedAt: now,
@@ -871,6 +881,13 @@ export async function updateRecurringPlan(
   const database = await getDb();
   const now = formatDateTime(new Date());
 
+  // 수정 전 반복 일정 (태스크를 맞출 때 사용자가 지운 발생을 구분하기 위해)
+  const previousRows = await database.select<RecurringPlanRow[]>(
+    `SELECT * FROM recurring_plans WHERE id = $1`,
+    [id]
+  );
+  if (previousRows.length === 0) return null;
+
   const updates: string[] = ['updated_at = $1'];
   const values: unknown[] = [now];
   let paramIndex = 2;
@@ -923,6 +940,14 @@ export async function updateRecurringPlan(
     updates.push(`end_date = $${paramIndex++}`);
     values.push(input.endDate);
   }
+  if (input.rrule !== undefined) {
+    updates.push(`rrule = $${paramIndex++}`);
+    values.push(input.rrule || null);
+  }
+  if (input.monthDayPolicy !== undefined) {
+    updates.push(`month_day_policy = $${paramIndex++}`);
+    values.push(input.monthDayPolicy);
+  }
   if (input.isActive !== undefined) {
     updates.push(`is_active = $${paramIndex++}`);
     values.push(input.isActive ? 1 : 0);
@@ -935,6 +960,9 @@ export async function updateRecurringPlan(
     values
   );
 
+  // 이미 만든 태스크를 바뀐 규칙에 맞춤 (완료했거나 직접 고친 태스크는 그대로)
+  await invoke('refresh_recurring_plan_tasks', { previous: rowToRecurringPlan(previousRows[0]) });
+
   const rows = await database.select<RecurringPlanRow[]>(
     `SELECT * FROM recurring_plans WHERE id = $1`,
     [id]
@@ -979,83 +1007,9 @@ export async function deleteRecurringPlan(id: string): Promise<void> {
   }
 }
 
-// generated_tasks 테이블에 기록 (중복 생성 방지)
-export async function recordGeneratedTask(
-  recurringPlanId: string,
-  taskId: string,
-  scheduledDate: string
-): Promise<void> {
-  const database = await getDb();
-  const id = generateId();
-  const now = formatDateTime(new Date());
-
-  await database.execute(
-    `INSERT OR IGNORE INTO generated_tasks (id, recurring_plan_id, task_id, scheduled_date, created_at)
-     VALUES ($1, $2, $3, $4, $5)`,
-    [id, recurringPlanId, taskId, scheduledDate, now]
-  );
-}
-
-// 이미 생성된 태스크인지 확인
-export async function isTaskAlreadyGenerated(
-  recurringPlanId: string,
-  scheduledDate: string
-): Promise<boolean> {
-  const database = await getDb();
-  const rows = await database.select<{ count: number }[]>(
-    `SELECT COUNT(*) as count FROM generated_tasks
-     WHERE recurring_plan_id = $1 AND scheduled_date = $2`,
-    [recurringPlanId, scheduledDate]
-  );
-  return (rows[0]?.count ?? 0) > 0;
-}
-
-// 반복 플랜에서 태스크 일괄 생성
-export async function generateTasksFromRecurringPlan(
-  recurringPlan: RecurringPlan,
-  tasksToGenerate: Array<{
-    scheduledDate: string;
-    scheduledTime?: string;
-    title: string;
-    description?: string;
-    location?: string;
-    estimatedDuration?: number;
-  }>
-): Promise<Task[]> {
-  const createdTasks: Task[] = [];
-
-  for (const taskInput of tasksToGenerate) {
-    // 이미 생성된 태스크인지 확인
-    const alreadyGenerated = await isTaskAlreadyGenerated(
-      recurringPlan.id,
-      taskInput.scheduledDate
-    );
-
-    if (alreadyGenerated) continue;
-
-    // 태스크 생성 (location은 taskInput 또는 recurringPlan에서 가져옴)
-    const task = await createTask({
-      planId: recurringPlan.planId,
-      title: taskInput.title,
-      description: taskInput.description,
-      location: taskInput.location || recurringPlan.location,
-      scheduledDate: taskInput.scheduledDate,
-      scheduledTime: taskInput.scheduledTime,
-      estimatedDuration: taskInput.estimatedDuration,
-      priority: 0,
-    });
-
-    // 생성 기록
-    await recordGeneratedTask(
-      recurringPlan.id,
-      task.id,
-      taskInput.scheduledDate
-    );
-
-    createdTasks.push(task);
-  }
-
-  return createdTasks;
+// 반복 일정 태스크 채우기 (오늘부터 설정한 기간까지만 생성, 이미 만든 날짜는 건너뜀)
+export async function materializeRecurringTasks(): Promise<void> {
+  await invoke('materialize_recurring_tasks');
 }
 
 // Focus Block Stats operations
@@ -0,0 +1,33 @@
+// Streaming AI commands (llm-delta / llm-done events)
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { generateId } from '@schedule-ai/core';
+import type { LlmDeltaEvent } from '@schedule-ai/core';
+
+export function createRequestId(): string {
+  return generateId();
+}
+
+// requestId를 붙여 커맨드를 실행하고, 결과가 오기 전까지 받은 delta를 onDelta로 전달
+export async function invokeStreaming<T>(
+  command: string,
+  args: Record<string, unknown>,
+  requestId: string,
+  onDelta: (delta: string) => void
+): Promise<T> {
+  const unlisten = await listen<LlmDeltaEvent>('llm-delta', (event) => {
+    if (event.payload.requestId === requestId) {
+      onDelta(event.payload.delta);
+    }
+  });
+  try {
+    return await invoke<T>(command, { ...args, requestId });
+  } finally {
+    unlisten();
+  }
+}
+
+// 진행 중인 요청 취소 (커맨드는 kind: 'cancelled' 에러로 끝남)
+export function cancelLlmRequest(requestId: string): Promise<boolean> {
+  return invoke<boolean>('cancel_llm_request', { requestId });
+}